@@ -3,15 +3,33 @@
 #![allow(clippy::too_many_arguments)]
 
 use soroban_sdk::{
-    contract, contractclient, contracterror, contractimpl, contracttype, token, Address, Env,
-    String, Symbol, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, token, Address, BytesN,
+    Env, IntoVal, String, Symbol, Vec,
 };
 
 #[cfg(test)]
 mod test;
 
 const CONTRACT_VERSION: u32 = 3;
-const CONTRIBUTION_COOLDOWN: u64 = 60; // 60 seconds cooldown
+const CONTRIBUTION_COOLDOWN: u64 = 60; // default 60 seconds cooldown
+const MAX_PAGE_SIZE: u32 = 100;
+const MAX_BATCH_SIZE: u32 = 50;
+const MAX_LEADERBOARD_SIZE: u32 = 25;
+const MAX_PAYOUT_SPLITS: u32 = 10;
+const MAX_UPDATE_LENGTH: u32 = 1_000;
+const MAX_MEMO_LENGTH: u32 = 140;
+/// Most entries kept in each address's contribution log; older ones drop off.
+const MAX_CONTRIBUTION_LOG: u32 = 50;
+/// Share of contributors (in basis points) whose flags trigger a review event.
+const FLAG_THRESHOLD_BPS: u32 = 2_500;
+/// Fixed-point scale for square roots in `qf_weight` (three decimal places).
+const QF_SCALE: i128 = 1_000;
+/// Oldest oracle price (in seconds) accepted when converting a reference goal.
+const MAX_PRICE_AGE: u64 = 3_600;
+/// Shortest window (in seconds) between initialization and the deadline.
+const MIN_CAMPAIGN_DURATION: u64 = 3_600;
+/// Longest window (in seconds) between initialization and the deadline.
+const MAX_CAMPAIGN_DURATION: u64 = 365 * 24 * 3_600;
 
 #[derive(Clone, PartialEq)]
 #[contracttype]
@@ -20,6 +38,19 @@ pub enum Status {
     Successful,
     Refunded,
     Cancelled,
+    Paused,
+}
+
+/// Where a backer stands with respect to refunds.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub enum RefundStatus {
+    /// The campaign has not failed, or the address holds no refundable stake.
+    NotApplicable,
+    /// The campaign failed and the backer can still claim a refund.
+    Pending,
+    /// The campaign failed and the backer's stake has been returned.
+    Refunded,
 }
 
 #[derive(Clone)]
@@ -29,11 +60,251 @@ pub struct RoadmapItem {
     pub description: String,
 }
 
+/// A creator's proposed change to a roadmap item, decided by backer vote.
+#[derive(Clone)]
+#[contracttype]
+pub struct RoadmapProposal {
+    /// Index of the roadmap item whose description changes.
+    pub index: u32,
+    pub description: String,
+    /// Contribution-weighted votes for and against.
+    pub yes_weight: i128,
+    pub no_weight: i128,
+    /// `total_raised` when the proposal was made; the base both vote
+    /// thresholds are measured against.
+    pub total_raised: i128,
+    pub resolved: bool,
+    pub approved: bool,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct Update {
+    pub timestamp: u64,
+    pub message: String,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct PlatformConfig {
     pub address: Address,
     pub fee_bps: u32,
+    /// Fee taken from each contribution on deposit instead of at withdrawal;
+    /// cannot be combined with `fee_bps`.
+    pub entry_fee_bps: u32,
+    /// Recipients that share each fee instead of `address`, with shares in
+    /// basis points summing to 10_000. The last recipient also receives any
+    /// rounding remainder.
+    pub fee_recipients: Option<Vec<(Address, u32)>>,
+}
+
+/// Optional settings supplied to `initialize`.
+///
+/// Grouped into a single argument because contract functions are limited to
+/// ten parameters.
+#[derive(Clone, Default)]
+#[contracttype]
+pub struct CampaignOptions {
+    /// Secondary bonus goal; must exceed the primary goal.
+    pub bonus_goal: Option<i128>,
+    pub bonus_goal_description: Option<String>,
+    /// Hard cap on total contributions; defaults to twice the goal, and a
+    /// donation campaign with no goal is uncapped unless one is set.
+    pub hard_cap: Option<i128>,
+    /// Reject contributions that would push `total_raised` past the goal.
+    pub strict_goal: bool,
+    /// Per-address contribution limit that resets every period.
+    pub period_limit: Option<PeriodLimit>,
+    /// Maximum cumulative contribution from any single address.
+    pub max_contribution: Option<i128>,
+    /// Minimum number of contributors required for success.
+    pub min_contributors: u32,
+    /// Stop accepting contributions before the main deadline.
+    pub contribution_deadline: Option<u64>,
+    /// Seconds after the deadline that backers of a failed campaign have to
+    /// claim refunds before the creator may sweep what is left.
+    pub claim_window: Option<u64>,
+    /// Judge success against a reference-currency goal instead of `goal`.
+    pub reference_goal: Option<ReferenceGoal>,
+    /// Tokens the creator locks at initialization; returned to the creator
+    /// once the campaign settles, whether it succeeds or fails, and
+    /// forfeited to the platform on `cancel`.
+    pub bond: i128,
+    /// Maximum number of listed contributors; existing ones may top up.
+    pub max_contributors: Option<u32>,
+    /// Hold each contribution as pending until the creator accepts it.
+    pub escrow: bool,
+    /// Seconds an address must wait between contributions; defaults to
+    /// `CONTRIBUTION_COOLDOWN`, and zero disables the cooldown.
+    pub contribution_cooldown: Option<u64>,
+    /// Ledger timestamp before which contributions are refused; 0 opens the
+    /// campaign immediately.
+    pub start_time: u64,
+    /// Seconds contributions stay open once the goal is reached, if that is
+    /// earlier than the contribution deadline.
+    pub post_goal_window: Option<u64>,
+    /// Minimum contribution in whole tokens, scaled by the token's decimals;
+    /// overrides `min_contribution` when set.
+    pub min_contribution_whole: Option<i128>,
+    /// Seconds after contributing before an address may reduce or transfer
+    /// its contribution; no cooldown when unset.
+    pub churn_cooldown: Option<u64>,
+    /// How contributions past the hard cap are handled.
+    pub refund_policy: RefundPolicy,
+    /// Accept at most one contribution per address.
+    pub single_contribution: bool,
+    /// Hold back anything raised past the goal at payout for backers to
+    /// reclaim through `claim_overflow`.
+    pub overflow_refund: bool,
+}
+
+/// Caps how much one address may contribute within each period.
+#[derive(Clone)]
+#[contracttype]
+pub struct PeriodLimit {
+    pub cap: i128,
+    pub period_seconds: u64,
+}
+
+/// What `contribute` does with a contribution that would overshoot the
+/// hard cap.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[contracttype]
+pub enum RefundPolicy {
+    /// Reject the whole contribution.
+    #[default]
+    Reject,
+    /// Accept only what fits under the cap; the rest stays with the
+    /// contributor.
+    RefundExcess,
+}
+
+/// Confirmation returned by `contribute`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ContributionReceipt {
+    /// The contributor's cumulative contribution, including this one.
+    pub total_contributed: i128,
+    /// `total_raised` after this contribution and any sponsor match.
+    pub campaign_total: i128,
+    /// 1-based position on the contributors list; 0 for anonymous backers.
+    pub rank: u32,
+}
+
+/// Contributor balances frozen by `snapshot`.
+#[derive(Clone)]
+#[contracttype]
+pub struct ContributorSnapshot {
+    /// Ledger timestamp when the snapshot was taken.
+    pub timestamp: u64,
+    /// Every listed contributor with their contribution at that time.
+    pub contributions: Vec<(Address, i128)>,
+}
+
+/// A payout to the creator recorded in the withdrawal history.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Withdrawal {
+    pub timestamp: u64,
+    /// Amount paid to the creator (or payout splits), net of platform fees.
+    pub amount: i128,
+}
+
+/// One credited contribution in an address's contribution log.
+///
+/// Contributions, batch entries, accepted escrow and scheduled pledge
+/// instalments are logged. A `transfer_contribution` logs the moved stake
+/// on both sides.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ContributionEntry {
+    pub timestamp: u64,
+    /// Amount credited, net of any entry fee; negative on the sending side
+    /// of a transfer.
+    pub amount: i128,
+}
+
+/// A contract version this instance has run, and when it took effect.
+///
+/// `initialize` records its own `version()`; `upgrade` records the version
+/// the admin supplies, which the new code does not confirm.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct VersionRecord {
+    pub version: u32,
+    pub timestamp: u64,
+}
+
+/// Amount an address has contributed in its current period window.
+#[derive(Clone)]
+#[contracttype]
+pub struct PeriodUsage {
+    pub window_start: u64,
+    pub amount: i128,
+}
+
+/// A backer's commitment to contribute `amount` every `interval` seconds.
+#[derive(Clone)]
+#[contracttype]
+pub struct ScheduledPledge {
+    pub amount: i128,
+    pub interval: u64,
+    /// Executions left before the schedule is removed.
+    pub remaining: u32,
+    pub last_executed: Option<u64>,
+}
+
+/// A goal denominated in a reference currency (e.g. USD) rather than the
+/// campaign token, converted at the oracle's current price.
+#[derive(Clone)]
+#[contracttype]
+pub struct ReferenceGoal {
+    pub oracle: Address,
+    /// Goal in the oracle's reference units.
+    pub amount: i128,
+}
+
+/// A price quote: the value of one whole token in reference units.
+#[derive(Clone)]
+#[contracttype]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// A sponsor's commitment to match backer contributions.
+#[derive(Clone)]
+#[contracttype]
+pub struct Matching {
+    pub sponsor: Address,
+    pub match_bps: u32,
+    /// Total the sponsor deposited up front; matching stops once exhausted.
+    pub cap: i128,
+    /// Amount matched so far and still counted in `total_raised`.
+    pub matched: i128,
+}
+
+/// Schedule for releasing funds to the creator after a successful campaign.
+#[derive(Clone)]
+#[contracttype]
+pub struct Vesting {
+    pub periods: u32,
+    pub interval: u64,
+}
+
+/// Which optional features are configured on this campaign.
+#[derive(Clone)]
+#[contracttype]
+pub struct FeatureFlags {
+    pub platform_fee: bool,
+    pub bonus_goal: bool,
+    pub strict_goal: bool,
+    pub period_limit: bool,
+    pub quality_goal: bool,
+    pub nft_rewards: bool,
+    pub matching: bool,
+    pub max_contribution: bool,
+    pub vesting: bool,
 }
 
 #[derive(Clone)]
@@ -45,6 +316,10 @@ pub struct CampaignStats {
     pub contributor_count: u32,
     pub average_contribution: i128,
     pub largest_contribution: i128,
+    pub verified_raised: i128,
+    /// Anonymous backers, included in `contributor_count`.
+    pub anonymous_count: u32,
+    pub anonymous_total: i128,
 }
 
 #[derive(Clone)]
@@ -57,6 +332,24 @@ pub struct CampaignInfo {
     pub total_raised: i128,
 }
 
+/// Everything a campaign detail page needs, returned by `get_campaign_full`.
+#[derive(Clone)]
+#[contracttype]
+pub struct CampaignFull {
+    pub creator: Address,
+    pub token: Address,
+    pub goal: i128,
+    pub deadline: u64,
+    pub total_raised: i128,
+    pub status: Status,
+    pub title: String,
+    pub description: String,
+    pub socials: String,
+    pub min_contribution: i128,
+    pub contributor_count: u32,
+    pub progress_bps: u32,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
@@ -99,6 +392,162 @@ pub enum DataKey {
     NFTContract,
     /// Last contribution time for rate limiting.
     LastContributionTime(Address),
+    /// Whether an address has been verified (KYC'd) by the platform admin.
+    Verified(Address),
+    /// Total contributed by verified addresses.
+    VerifiedRaised,
+    /// Optional minimum verified total required for success.
+    QualityGoal,
+    /// Whether contributions are clamped so the total never exceeds the goal.
+    StrictGoal,
+    /// Per-address periodic contribution limit.
+    PeriodLimit,
+    /// Contribution usage within the current period, per address.
+    PeriodUsage(Address),
+    /// Sponsor matching configuration and running total.
+    Matching,
+    /// Factory that deployed this campaign; notified on status changes.
+    Factory,
+    /// Proposed platform admin awaiting acceptance.
+    PendingAdmin,
+    /// Optional cap on each address's cumulative contribution.
+    MaxContribution,
+    /// Whether a contributor has flagged the campaign.
+    Flags(Address),
+    /// Number of contributors who have flagged the campaign.
+    FlagCount,
+    /// Creator payout vesting schedule.
+    Vesting,
+    /// Total subject to vesting, pinned when the campaign settles.
+    VestingTotal,
+    /// Amount already released to the creator under vesting.
+    Withdrawn,
+    /// Ascending contribution thresholds selecting which NFT tier to mint.
+    NFTTiers,
+    /// Campaign category used for browsing (e.g. `art`, `tech`).
+    Category,
+    /// Running total of platform fees paid out.
+    FeesCollected,
+    /// Minimum number of contributors required for success.
+    MinContributors,
+    /// Cutoff for new contributions, if earlier than the main deadline.
+    ContributionDeadline,
+    /// Whether the platform fee of a successful campaign has been collected.
+    FeeCollected,
+    /// Platform fee owed (or paid) on the settled campaign total.
+    FeeDue,
+    /// Whether an address contributes anonymously (kept off `Contributors`).
+    Anonymous(Address),
+    /// Number of anonymous backers with a non-zero contribution.
+    AnonymousCount,
+    /// Total contributed by anonymous backers.
+    AnonymousTotal,
+    /// Amount raised past the goal, set aside at withdrawal for backers.
+    OverflowPool,
+    /// Backer stakes not yet paid an overflow share, the base for the
+    /// remaining shares.
+    OverflowBase,
+    /// Whether a contributor has claimed their overflow share.
+    OverflowClaimed(Address),
+    /// Number of project updates posted by the creator.
+    UpdateCount,
+    /// Project update by posting order.
+    Update(u32),
+    /// Total tips paid directly to the creator.
+    Tips,
+    /// Minimum contribution total required to receive an NFT.
+    NFTMinContribution,
+    /// Recurring contribution schedule by address.
+    ScheduledPledge(Address),
+    /// Number of roadmap change proposals created; the next proposal's id.
+    ProposalCount,
+    /// Roadmap change proposal by id.
+    Proposal(u32),
+    /// A contributor's vote on a proposal.
+    Vote(u32, Address),
+    /// Refund claim period after the deadline of a failed campaign.
+    ClaimWindow,
+    /// Token decimals, pinned at initialization.
+    TokenDecimals,
+    /// Whether the platform admin has barred an address from contributing.
+    Blocked(Address),
+    /// Reference-currency goal converted through a price oracle.
+    ReferenceGoal,
+    /// Recipients and basis-point shares of the creator payout.
+    PayoutSplits,
+    /// Creator's explanation for cancelling the campaign.
+    CancelReason,
+    /// Exact amounts a contribution must match, if any are set.
+    FixedLevels,
+    /// Set on first `initialize`; only `reset` clears it.
+    Initialized,
+    /// Public message a contributor left with their latest contribution.
+    Memo(Address),
+    /// How many of the first contributors receive a bonus NFT.
+    EarlyBackerCount,
+    /// Bitmask of the goal quartiles already announced.
+    MilestonesHit,
+    /// Creator bond still held by the contract.
+    Bond,
+    /// Goal set at initialization, kept once a stretch goal replaces it.
+    OriginalGoal,
+    /// Maximum number of listed contributors.
+    MaxContributors,
+    /// Whether payouts unlock with roadmap completion via `withdraw_milestone`.
+    RoadmapGated,
+    /// Whether the roadmap item at an index has been delivered.
+    RoadmapDone(u32),
+    /// Total raised, pinned when a roadmap-gated campaign settles.
+    MilestoneTotal,
+    /// Contributor balances recorded by `snapshot`, by snapshot id.
+    Snapshot(u32),
+    /// Number of snapshots taken; the next snapshot id.
+    SnapshotCount,
+    /// Whether contributions wait for creator acceptance.
+    Escrow,
+    /// Gross amount an address has parked awaiting creator acceptance.
+    Pending(Address),
+    /// Sum of all pending contributions.
+    PendingTotal,
+    /// Every payout made to the creator, oldest first.
+    WithdrawalHistory,
+    /// Seconds an address must wait between contributions.
+    ContributionCooldown,
+    /// Ledger timestamp at which contributions open.
+    StartTime,
+    /// Next nonce `contribute_with_auth` accepts from an address.
+    Nonce(Address),
+    /// Seconds contributions stay open once the goal is reached.
+    PostGoalWindow,
+    /// Ledger timestamp at which the goal was first reached, recorded only
+    /// with a post-goal window.
+    GoalReachedTime,
+    /// Set by `finalize` until a successful outcome is paid out.
+    Finalized,
+    /// Seconds after contributing before an address may reduce or transfer.
+    ChurnCooldown,
+    /// Maximum number of contributors `withdraw` mints NFTs for.
+    NftBatchLimit,
+    /// Whether a contributor's NFT has been minted.
+    NftClaimed(Address),
+    /// How contributions past the hard cap are handled.
+    RefundPolicy,
+    /// Every contract version this instance has run, oldest first.
+    VersionHistory,
+    /// Whether each address may contribute only once.
+    SingleContribution,
+    /// An address's most recent contributions, oldest first.
+    ContributionLog(Address),
+    /// Every address that has per-address state, so `reset` can clear it.
+    Participants,
+    /// Part of the sponsor's matching deposit not yet credited as matches.
+    SponsorBalance,
+    /// Sponsor match credited on an address's contributions.
+    MatchCredit(Address),
+    /// Whether funds raised past the goal are held back for backers.
+    OverflowRefund,
+    /// Reference goal in token base units, pinned at the price `finalize` saw.
+    PinnedGoal,
 }
 
 #[contracterror]
@@ -116,11 +565,69 @@ pub enum ContractError {
     RateLimitExceeded = 9,
     ContractPaused = 10,
     InvalidLimit = 11,
+    QualityGoalNotReached = 12,
+    CampaignNotSettled = 13,
+    BalanceNotZero = 14,
+    ContributionExceedsGoal = 15,
+    PeriodCapExceeded = 16,
+    NoPendingAdmin = 17,
+    NotPendingAdmin = 18,
+    MaxContributionExceeded = 19,
+    NotContributor = 20,
+    AlreadyFlagged = 21,
+    VestingConfigured = 22,
+    VestingNotConfigured = 23,
+    NothingToWithdraw = 24,
+    InsufficientContribution = 25,
+    NotEnoughContributors = 26,
+    FeeAlreadyCollected = 27,
+    NoPlatformFee = 28,
+    NoOverflow = 29,
+    OverflowAlreadyClaimed = 30,
+    NoScheduledPledge = 31,
+    PledgeNotDue = 32,
+    NoProposal = 33,
+    ProposalResolved = 34,
+    AlreadyVoted = 35,
+    ClaimWindowOpen = 36,
+    AddressBlocked = 37,
+    NoReferenceGoal = 38,
+    InvalidPrice = 39,
+    StalePrice = 40,
+    InvalidMinContribution = 41,
+    SelfReferral = 42,
+    InvalidPledgeLevel = 43,
+    RoadmapItemNotFound = 44,
+    TransferFailed = 45,
+    CampaignTokenNotSweepable = 46,
+    ContributorLimitReached = 47,
+    DonationMode = 48,
+    RoadmapGated = 49,
+    NotRoadmapGated = 50,
+    DurationTooShort = 51,
+    DurationTooLong = 52,
+    NoPendingContribution = 53,
+    CampaignNotStarted = 54,
+    InvalidNonce = 55,
+    CooldownActive = 56,
+    NftAlreadyClaimed = 57,
+    NoNftContract = 58,
+    AlreadyContributed = 59,
+    StakeLocked = 60,
+    ProposalOpen = 61,
+    EscrowUnsupported = 62,
+    CampaignNotActive = 63,
 }
 
 #[contractclient(name = "NftContractClient")]
 pub trait NftContract {
     fn mint(env: Env, to: Address) -> u128;
+    fn mint_tier(env: Env, to: Address, tier: u32) -> u128;
+}
+
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn price(env: Env, asset: Address) -> PriceData;
 }
 
 #[contract]
@@ -128,6 +635,13 @@ pub struct CrowdfundContract;
 
 #[contractimpl]
 impl CrowdfundContract {
+    /// Set up the campaign.
+    ///
+    /// A `goal` of zero starts a donation drive: any positive total can be
+    /// withdrawn after the deadline and backers never get refunds, short of
+    /// the creator cancelling.
+    ///
+    /// The deadline must fall between one hour and one year from now.
     pub fn initialize(
         env: Env,
         admin: Address,
@@ -136,58 +650,231 @@ impl CrowdfundContract {
         goal: i128,
         deadline: u64,
         min_contribution: i128,
+        category: Symbol,
         platform_config: Option<PlatformConfig>,
-        bonus_goal: Option<i128>,
-        bonus_goal_description: Option<String>,
-        hard_cap: Option<i128>,
+        factory: Option<Address>,
+        options: Option<CampaignOptions>,
     ) -> Result<(), ContractError> {
-        if env.storage().instance().has(&DataKey::Creator) {
+        // Checked alongside `Creator` so that code which clears the creator
+        // (e.g. after an upgrade) cannot reopen initialization.
+        let initialized: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Initialized)
+            .unwrap_or(false);
+        if initialized || env.storage().instance().has(&DataKey::Creator) {
             return Err(ContractError::AlreadyInitialized);
         }
 
-        creator.require_auth();
+        // Validate every parameter before any auth or storage writes so a
+        // rejected call leaves no partial state behind.
+        let options = options.unwrap_or_default();
+
+        let token_decimals = token::Client::new(&env, &token).decimals();
+        let min_contribution = match options.min_contribution_whole {
+            Some(whole) => whole
+                .checked_mul(decimal_scale(token_decimals)?)
+                .ok_or(ContractError::Overflow)?,
+            None => min_contribution,
+        };
 
         if let Some(ref config) = platform_config {
-            if config.fee_bps > 10_000 {
+            if config.fee_bps > 10_000 || config.entry_fee_bps > 10_000 {
                 panic!("platform fee cannot exceed 100%");
             }
-            env.storage()
-                .instance()
-                .set(&DataKey::PlatformConfig, config);
+            if config.fee_bps > 0 && config.entry_fee_bps > 0 {
+                panic!("platform fee and entry fee are mutually exclusive");
+            }
+            if let Some(ref recipients) = config.fee_recipients {
+                if recipients.is_empty() {
+                    panic!("fee recipients cannot be empty");
+                }
+                if recipients.len() > MAX_PAYOUT_SPLITS {
+                    panic!("too many fee recipients");
+                }
+                let total_bps: u64 = recipients.iter().map(|(_, bps)| bps as u64).sum();
+                if total_bps != 10_000 {
+                    panic!("fee recipients must sum to 10000 bps");
+                }
+            }
         }
 
-        let hard_cap_value = hard_cap.unwrap_or(goal * 2); // Default to 2x goal
-        if hard_cap_value < goal {
-            return Err(ContractError::InvalidHardCap);
-        }
+        let hard_cap = match options.hard_cap {
+            Some(hard_cap) if hard_cap <= 0 || hard_cap < goal => {
+                return Err(ContractError::InvalidHardCap);
+            }
+            Some(hard_cap) => Some(hard_cap),
+            // Twice the goal has no meaning without a goal to double.
+            None if goal > 0 => Some(goal.saturating_mul(2)),
+            None => None,
+        };
 
-        if let Some(bg) = bonus_goal {
+        if let Some(bg) = options.bonus_goal {
             if bg <= goal {
                 panic!("bonus goal must be greater than primary goal");
             }
-            env.storage().instance().set(&DataKey::BonusGoal, &bg);
         }
 
-        if let Some(bg_description) = bonus_goal_description {
+        let duration = deadline.saturating_sub(env.ledger().timestamp());
+        if duration < MIN_CAMPAIGN_DURATION {
+            return Err(ContractError::DurationTooShort);
+        }
+        if duration > MAX_CAMPAIGN_DURATION {
+            return Err(ContractError::DurationTooLong);
+        }
+
+        if let Some(contribution_deadline) = options.contribution_deadline {
+            if contribution_deadline > deadline {
+                panic!("contribution deadline cannot be after the deadline");
+            }
+        }
+
+        if options.start_time >= deadline {
+            panic!("start time must be before the deadline");
+        }
+
+        if let Some(max) = options.max_contribution {
+            if max < min_contribution {
+                panic!("max contribution cannot be below min contribution");
+            }
+        }
+
+        if let Some(ref limit) = options.period_limit {
+            if limit.cap <= 0 || limit.period_seconds == 0 {
+                panic!("period limit must have a positive cap and period");
+            }
+        }
+
+        if let Some(ref reference_goal) = options.reference_goal {
+            if reference_goal.amount <= 0 {
+                panic!("reference goal must be positive");
+            }
+        }
+
+        if options.bond < 0 {
+            panic!("bond cannot be negative");
+        }
+
+        creator.require_auth();
+
+        if let Some(bg) = options.bonus_goal {
+            env.storage().instance().set(&DataKey::BonusGoal, &bg);
+        }
+        if let Some(hard_cap) = hard_cap {
+            env.storage().instance().set(&DataKey::HardCap, &hard_cap);
+        }
+        if let Some(contribution_deadline) = options.contribution_deadline {
+            env.storage()
+                .instance()
+                .set(&DataKey::ContributionDeadline, &contribution_deadline);
+        }
+        if let Some(max) = options.max_contribution {
+            env.storage()
+                .instance()
+                .set(&DataKey::MaxContribution, &max);
+        }
+        if let Some(ref limit) = options.period_limit {
+            env.storage().instance().set(&DataKey::PeriodLimit, limit);
+        }
+        if let Some(ref reference_goal) = options.reference_goal {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReferenceGoal, reference_goal);
+        }
+        if let Some(max_contributors) = options.max_contributors {
+            env.storage()
+                .instance()
+                .set(&DataKey::MaxContributors, &max_contributors);
+        }
+        if options.escrow {
+            env.storage().instance().set(&DataKey::Escrow, &true);
+        }
+        if options.single_contribution {
+            env.storage()
+                .instance()
+                .set(&DataKey::SingleContribution, &true);
+        }
+        if options.overflow_refund {
+            env.storage()
+                .instance()
+                .set(&DataKey::OverflowRefund, &true);
+        }
+        if let Some(cooldown) = options.contribution_cooldown {
+            env.storage()
+                .instance()
+                .set(&DataKey::ContributionCooldown, &cooldown);
+        }
+        if options.start_time > 0 {
+            env.storage()
+                .instance()
+                .set(&DataKey::StartTime, &options.start_time);
+        }
+        if options.refund_policy != RefundPolicy::Reject {
+            env.storage()
+                .instance()
+                .set(&DataKey::RefundPolicy, &options.refund_policy);
+        }
+        if let Some(cooldown) = options.churn_cooldown {
+            env.storage()
+                .instance()
+                .set(&DataKey::ChurnCooldown, &cooldown);
+        }
+        if let Some(window) = options.post_goal_window {
+            env.storage()
+                .instance()
+                .set(&DataKey::PostGoalWindow, &window);
+        }
+        if let Some(claim_window) = options.claim_window {
+            env.storage()
+                .instance()
+                .set(&DataKey::ClaimWindow, &claim_window);
+        }
+        if let Some(bg_description) = options.bonus_goal_description {
             env.storage()
                 .instance()
                 .set(&DataKey::BonusGoalDescription, &bg_description);
         }
 
+        env.storage().instance().set(&DataKey::Initialized, &true);
+        record_version(&env, CONTRACT_VERSION);
+        env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::Creator, &creator);
         env.storage().instance().set(&DataKey::Token, &token);
+        env.storage()
+            .instance()
+            .set(&DataKey::TokenDecimals, &token_decimals);
         env.storage().instance().set(&DataKey::Goal, &goal);
-        env.storage().instance().set(&DataKey::HardCap, &hard_cap_value);
         env.storage().instance().set(&DataKey::Deadline, &deadline);
         env.storage()
             .instance()
             .set(&DataKey::MinContribution, &min_contribution);
+        env.storage().instance().set(&DataKey::Category, &category);
+        env.storage()
+            .instance()
+            .set(&DataKey::MinContributors, &options.min_contributors);
+        env.storage()
+            .instance()
+            .set(&DataKey::StrictGoal, &options.strict_goal);
+        if let Some(ref factory) = factory {
+            env.storage().instance().set(&DataKey::Factory, factory);
+        }
         if let Some(config) = platform_config {
             env.storage()
                 .instance()
                 .set(&DataKey::PlatformConfig, &config);
         }
         env.storage().instance().set(&DataKey::TotalRaised, &0i128);
+        if options.bond > 0 {
+            token::Client::new(&env, &token).transfer(
+                &creator,
+                &env.current_contract_address(),
+                &options.bond,
+            );
+            env.storage().instance().set(&DataKey::Bond, &options.bond);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::VerifiedRaised, &0i128);
         env.storage()
             .instance()
             .set(&DataKey::BonusGoalReachedEmitted, &false);
@@ -208,7 +895,16 @@ impl CrowdfundContract {
         Ok(())
     }
 
-    pub fn set_nft_contract(env: Env, creator: Address, nft_contract: Address) {
+    /// Set the NFT contract minted to at withdrawal.
+    ///
+    /// The first `early_backer_count` addresses on the contributors list
+    /// receive one extra NFT each.
+    pub fn set_nft_contract(
+        env: Env,
+        creator: Address,
+        nft_contract: Address,
+        early_backer_count: u32,
+    ) {
         let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
         if creator != stored_creator {
             panic!("not authorized");
@@ -218,575 +914,5845 @@ impl CrowdfundContract {
         env.storage()
             .instance()
             .set(&DataKey::NFTContract, &nft_contract);
+        env.storage()
+            .instance()
+            .set(&DataKey::EarlyBackerCount, &early_backer_count);
     }
 
-    /// Contribute tokens to the campaign.
+    /// Hand control of the campaign to `new_creator`.
     ///
-    /// The contributor must authorize the call. Contributions are rejected
-    /// after the deadline has passed.
-    pub fn contribute(
+    /// Requires the current creator's authorization. All creator-gated
+    /// functions read the stored creator, so they honor the new address
+    /// immediately.
+    pub fn transfer_creator(env: Env, new_creator: Address) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Creator, &new_creator);
+
+        env.events()
+            .publish(("campaign", "creator_transferred"), (creator, new_creator));
+    }
+
+    /// Propose `new_admin` as the next platform admin.
+    ///
+    /// The handover only takes effect once `new_admin` calls `accept_admin`,
+    /// so a mistyped address cannot lock out administration. A new proposal
+    /// replaces any pending one.
+    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if current_admin != stored_admin {
+            panic!("not authorized");
+        }
+
+        current_admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::PendingAdmin, &new_admin);
+
+        env.events()
+            .publish(("campaign", "admin_proposed"), (current_admin, new_admin));
+    }
+
+    /// Accept a pending admin proposal, completing the handover.
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), ContractError> {
+        let pending: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::PendingAdmin)
+            .ok_or(ContractError::NoPendingAdmin)?;
+        if new_admin != pending {
+            return Err(ContractError::NotPendingAdmin);
+        }
+
+        new_admin.require_auth();
+
+        let old_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events()
+            .publish(("campaign", "admin_accepted"), (old_admin, new_admin));
+
+        Ok(())
+    }
+
+    /// Change the minimum contribution while the campaign is active.
+    ///
+    /// Existing contributions are unaffected; only later contributions are
+    /// checked against the new minimum. The new minimum cannot exceed any
+    /// fixed pledge level, or no level could be pledged.
+    pub fn set_min_contribution(
         env: Env,
-        contributor: Address,
-        amount: i128,
-        referral: Option<Address>,
+        creator: Address,
+        new_min: i128,
     ) -> Result<(), ContractError> {
-        // ── Rate limiting: enforce cooldown between contributions ──
-        let now = env.ledger().timestamp();
-        let last_time_key = DataKey::LastContributionTime(contributor.clone());
-        if let Some(last_time) = env.storage().persistent().get::<_, u64>(&last_time_key) {
-            if now < last_time + CONTRIBUTION_COOLDOWN {
-                return Err(ContractError::RateLimitExceeded);
-            }
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
         }
 
+        creator.require_auth();
+
         let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
         if status != Status::Active {
             panic!("campaign is not active");
         }
 
-        let min_contribution: i128 = env
+        if new_min <= 0 {
+            return Err(ContractError::InvalidMinContribution);
+        }
+        if let Some(max) = env
             .storage()
             .instance()
-            .get(&DataKey::MinContribution)
-            .unwrap();
-        if amount < min_contribution {
-            panic!("amount below minimum");
+            .get::<_, i128>(&DataKey::MaxContribution)
+        {
+            if new_min > max {
+                return Err(ContractError::InvalidMinContribution);
+            }
         }
-
-        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
-        if env.ledger().timestamp() > deadline {
-            return Err(ContractError::CampaignEnded);
+        if let Some(levels) = env
+            .storage()
+            .instance()
+            .get::<_, Vec<i128>>(&DataKey::FixedLevels)
+        {
+            if levels.iter().any(|level| level < new_min) {
+                return Err(ContractError::InvalidMinContribution);
+            }
         }
 
-        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let token_client = token::Client::new(&env, &token_address);
-        token_client.transfer(&contributor, &env.current_contract_address(), &amount);
-
-        let contribution_key = DataKey::Contribution(contributor.clone());
-        let previous_amount: i128 = env
+        let old_min: i128 = env
             .storage()
-            .persistent()
-            .get(&contribution_key)
-            .unwrap_or(0);
-
-        env.storage()
-            .persistent()
-            .set(&contribution_key, &(previous_amount + amount));
-        env.storage()
-            .persistent()
-            .extend_ttl(&contribution_key, 100, 100);
-
-        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+            .instance()
+            .get(&DataKey::MinContribution)
+            .unwrap();
         env.storage()
             .instance()
-            .set(&DataKey::TotalRaised, &(total + amount));
+            .set(&DataKey::MinContribution, &new_min);
 
-        let mut contributors: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Contributors)
-            .unwrap_or_else(|| Vec::new(&env));
+        env.events()
+            .publish(("campaign", "min_contribution_updated"), (old_min, new_min));
 
-        if !contributors.contains(&contributor) {
-            contributors.push_back(contributor);
-            env.storage()
-                .persistent()
-                .set(&DataKey::Contributors, &contributors);
-            env.storage()
-                .persistent()
-                .extend_ttl(&DataKey::Contributors, 100, 100);
-        }
+        Ok(())
+    }
 
-        // Emit contribution event
-        env.events().publish(
-            ("campaign", "contributed"),
-            (contributor.clone(), amount),
-        );
+    /// Raise the goal to `new_goal` once the current goal has been met.
+    ///
+    /// The campaign keeps collecting toward the higher target; the goal set
+    /// at initialization stays available through `original_goal`. The new
+    /// goal may not exceed the hard cap, and donation campaigns, which have
+    /// no goal to meet, take no stretch goal.
+    pub fn set_stretch_goal(
+        env: Env,
+        creator: Address,
+        new_goal: i128,
+    ) -> Result<(), ContractError> {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
 
-        // Update referral tally if referral provided
-        if let Some(referrer) = referral {
-            if referrer != contributor {
-                let referral_key = DataKey::ReferralTally(referrer.clone());
-                let current_tally: i128 =
-                    env.storage().persistent().get(&referral_key).unwrap_or(0);
+        creator.require_auth();
 
-                let new_tally = current_tally
-                    .checked_add(amount)
-                    .ok_or(ContractError::Overflow)?;
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+        if is_donation_mode(&env) {
+            return Err(ContractError::DonationMode);
+        }
 
-                env.storage().persistent().set(&referral_key, &new_tally);
-                env.storage()
-                    .persistent()
-                    .extend_ttl(&referral_key, 100, 100);
+        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+        if total < goal {
+            return Err(ContractError::GoalNotReached);
+        }
+        if new_goal <= goal {
+            panic!("stretch goal must exceed the current goal");
+        }
+        let hard_cap: Option<i128> = env.storage().instance().get(&DataKey::HardCap);
+        if hard_cap.is_some_and(|cap| new_goal > cap) {
+            return Err(ContractError::HardCapExceeded);
+        }
 
-                // Emit referral event
-                env.events().publish(
-                    ("campaign", "referral"),
-                    (referrer, contributor, amount),
-                );
-            }
+        if !env.storage().instance().has(&DataKey::OriginalGoal) {
+            env.storage().instance().set(&DataKey::OriginalGoal, &goal);
         }
+        env.storage().instance().set(&DataKey::Goal, &new_goal);
 
-        // Update last contribution time for rate limiting
-        env.storage().persistent().set(&last_time_key, &now);
-        env.storage()
-            .persistent()
-            .extend_ttl(&last_time_key, 100, 100);
+        env.events()
+            .publish(("campaign", "stretch_goal_set"), (goal, new_goal));
 
         Ok(())
     }
 
-    /// Pledge tokens to the campaign without transferring them immediately.
+    /// Split the creator payout between co-creators.
     ///
-    /// The pledger must authorize the call. Pledges are recorded off-chain
-    /// and only collected if the goal is met after the deadline.
-    pub fn pledge(env: Env, pledger: Address, amount: i128) -> Result<(), ContractError> {
-        pledger.require_auth();
+    /// Each entry pairs a recipient with a share in basis points; the shares
+    /// must sum to 10_000. Payouts are split after the platform fee, and an
+    /// empty list sends everything to the creator again.
+    pub fn set_payout_splits(env: Env, creator: Address, splits: Vec<(Address, u32)>) {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
 
-        let min_contribution: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MinContribution)
-            .unwrap();
-        if amount < min_contribution {
-            panic!("amount below minimum");
+        creator.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            panic!("campaign is not active");
         }
 
-        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
-        if env.ledger().timestamp() > deadline {
-            return Err(ContractError::CampaignEnded);
+        if splits.is_empty() {
+            env.storage().instance().remove(&DataKey::PayoutSplits);
+            return;
+        }
+        if splits.len() > MAX_PAYOUT_SPLITS {
+            panic!("too many payout splits");
+        }
+        let total_bps: u64 = splits.iter().map(|(_, bps)| bps as u64).sum();
+        if total_bps != 10_000 {
+            panic!("payout splits must sum to 10000 bps");
         }
 
-        // Update the pledger's running total.
-        let pledge_key = DataKey::Pledge(pledger.clone());
-        let prev: i128 = env.storage().persistent().get(&pledge_key).unwrap_or(0);
         env.storage()
-            .persistent()
-            .set(&pledge_key, &(prev + amount));
-        env.storage().persistent().extend_ttl(&pledge_key, 100, 100);
+            .instance()
+            .set(&DataKey::PayoutSplits, &splits);
+    }
 
-        // Update the global total pledged.
-        let total_pledged: i128 = env
+    /// Restrict contributions to the exact amounts in `levels`.
+    ///
+    /// Each level must be at least the minimum contribution. An empty list
+    /// accepts any amount at or above the minimum again. Levels can only be
+    /// changed while the campaign is active and before the first contribution.
+    pub fn set_fixed_levels(
+        env: Env,
+        creator: Address,
+        levels: Vec<i128>,
+    ) -> Result<(), ContractError> {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
+
+        creator.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        // Backers picked their amounts from the levels already offered.
+        let total_raised: i128 = env
             .storage()
             .instance()
-            .get(&DataKey::TotalPledged)
+            .get(&DataKey::TotalRaised)
             .unwrap_or(0);
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalPledged, &(total_pledged + amount));
-
-        // Track pledger address if new.
-        let mut pledgers: Vec<Address> = env
+        let contributors: Vec<Address> = env
             .storage()
             .persistent()
-            .get(&DataKey::Pledgers)
+            .get(&DataKey::Contributors)
             .unwrap_or_else(|| Vec::new(&env));
-        if !pledgers.contains(&pledger) {
-            pledgers.push_back(pledger.clone());
-            env.storage()
-                .persistent()
-                .set(&DataKey::Pledgers, &pledgers);
-            env.storage()
-                .persistent()
-                .extend_ttl(&DataKey::Pledgers, 100, 100);
+        if total_raised > 0 || !contributors.is_empty() {
+            panic!("pledge levels are locked");
         }
 
-        // Emit pledge event
-        env.events()
-            .publish(("campaign", "pledged"), (pledger, amount));
+        if levels.is_empty() {
+            env.storage().instance().remove(&DataKey::FixedLevels);
+            return Ok(());
+        }
+        let min_contribution: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinContribution)
+            .unwrap();
+        for level in levels.iter() {
+            if level < min_contribution {
+                panic!("pledge level below minimum");
+            }
+        }
 
+        env.storage().instance().set(&DataKey::FixedLevels, &levels);
         Ok(())
     }
 
-    /// Collect all pledges after the deadline when the goal is met.
+    /// Replace this contract's code with the uploaded WASM `new_wasm_hash`.
     ///
-    /// This function transfers tokens from all pledgers to the contract.
-    /// Only callable after the deadline and when the combined total of
-    /// contributions and pledges meets or exceeds the goal.
-    pub fn collect_pledges(env: Env) -> Result<(), ContractError> {
-        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
-        if status != Status::Active {
-            panic!("campaign is not active");
+    /// Only the platform admin may upgrade. Storage is preserved.
+    /// `new_version` is recorded in the version history as given and must be
+    /// above every version already recorded. It is admin-supplied metadata:
+    /// the new code only runs after this call, so its `version()` is not
+    /// checked against it.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>, new_version: u32) {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("not authorized");
         }
 
-        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
-        if env.ledger().timestamp() <= deadline {
-            return Err(ContractError::CampaignStillActive);
+        admin.require_auth();
+
+        let history = Self::version_history(env.clone());
+        if history
+            .last()
+            .is_some_and(|record| new_version <= record.version)
+        {
+            panic!("version must increase");
         }
+        record_version(&env, new_version);
 
-        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
-        let total_raised: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
-        let total_pledged: i128 = env
-            .storage()
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        env.events()
+            .publish(("campaign", "upgraded"), new_wasm_hash);
+    }
+
+    /// Configure tiered NFT rewards.
+    ///
+    /// `thresholds` must be strictly ascending. A contributor's tier is the
+    /// number of thresholds their total meets, so tier 0 is the base reward.
+    /// An empty list restores the single `mint` behavior.
+    pub fn set_nft_tiers(env: Env, creator: Address, thresholds: Vec<i128>) {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
+
+        creator.require_auth();
+
+        let mut previous = 0i128;
+        for threshold in thresholds.iter() {
+            if threshold <= previous {
+                panic!("tier thresholds must be positive and ascending");
+            }
+            previous = threshold;
+        }
+
+        env.storage()
             .instance()
-            .get(&DataKey::TotalPledged)
-            .unwrap_or(0);
+            .set(&DataKey::NFTTiers, &thresholds);
+    }
 
-        // Check if combined total meets the goal
-        if total_raised + total_pledged < goal {
-            return Err(ContractError::GoalNotReached);
+    /// Require contributors to have backed at least `min_contribution` to be
+    /// minted an NFT at withdrawal. Without it any non-zero stake qualifies.
+    pub fn set_nft_min_contribution(env: Env, creator: Address, min_contribution: i128) {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
         }
 
-        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let token_client = token::Client::new(&env, &token_address);
+        creator.require_auth();
 
-        let pledgers: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Pledgers)
-            .unwrap_or_else(|| Vec::new(&env));
+        if min_contribution <= 0 {
+            panic!("nft minimum contribution must be positive");
+        }
 
-        // Collect pledges from all pledgers
-        for pledger in pledgers.iter() {
-            let pledge_key = DataKey::Pledge(pledger.clone());
-            let amount: i128 = env.storage().persistent().get(&pledge_key).unwrap_or(0);
-            if amount > 0 {
-                // Transfer tokens from pledger to contract
-                token_client.transfer(&pledger, &env.current_contract_address(), &amount);
+        env.storage()
+            .instance()
+            .set(&DataKey::NFTMinContribution, &min_contribution);
+    }
 
-                // Clear the pledge
-                env.storage().persistent().set(&pledge_key, &0i128);
-                env.storage().persistent().extend_ttl(&pledge_key, 100, 100);
-            }
+    /// Cap how many contributors `withdraw` mints NFTs for, so large
+    /// campaigns stay within the instruction budget. Contributors past the
+    /// limit mint their own through `claim_nft`.
+    pub fn set_nft_batch_limit(env: Env, creator: Address, limit: u32) {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
         }
 
-        // Update total raised to include collected pledges
+        creator.require_auth();
+
         env.storage()
             .instance()
-            .set(&DataKey::TotalRaised, &(total_raised + total_pledged));
+            .set(&DataKey::NftBatchLimit, &limit);
+    }
 
-        // Reset total pledged
-        env.storage().instance().set(&DataKey::TotalPledged, &0i128);
+    /// Mark or unmark an address as verified (e.g. KYC'd).
+    ///
+    /// Only the platform admin may call this. Contributions made while an
+    /// address is verified count toward `verified_raised`.
+    pub fn set_verified(env: Env, admin: Address, contributor: Address, verified: bool) {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("not authorized");
+        }
+
+        admin.require_auth();
+        track_participant(&env, &contributor);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Verified(contributor.clone()), &verified);
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKey::Verified(contributor.clone()), 100, 100);
 
-        // Emit pledges collected event
         env.events()
-            .publish(("campaign", "pledges_collected"), total_pledged);
+            .publish(("campaign", "verified_set"), (contributor, verified));
+    }
 
-        Ok(())
+    /// Bar `address` from contributing to the campaign.
+    ///
+    /// Only the platform admin may call this. Existing contributions are
+    /// untouched; the block only stops new ones.
+    pub fn block_address(env: Env, admin: Address, address: Address) {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("not authorized");
+        }
+
+        admin.require_auth();
+        track_participant(&env, &address);
+        let blocked_key = DataKey::Blocked(address.clone());
+        env.storage().persistent().set(&blocked_key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&blocked_key, 100, 100);
+
+        env.events()
+            .publish(("campaign", "address_blocked"), address);
     }
 
-    pub fn withdraw(env: Env) -> Result<(), ContractError> {
-        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
-        if status != Status::Active {
-            panic!("campaign is not active");
+    /// Lift a block placed with `block_address`.
+    pub fn unblock_address(env: Env, admin: Address, address: Address) {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("not authorized");
         }
 
+        admin.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Blocked(address.clone()));
+
+        env.events()
+            .publish(("campaign", "address_unblocked"), address);
+    }
+
+    /// Release funds to the creator in `periods` equal tranches, one every
+    /// `interval` seconds, with the first unlocking at the deadline.
+    ///
+    /// Must be configured before the deadline. Once set, `withdraw` is
+    /// disabled in favour of `withdraw_vested`.
+    pub fn set_vesting(env: Env, periods: u32, interval: u64) {
         let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
         creator.require_auth();
 
         let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
-        if env.ledger().timestamp() <= deadline {
-            return Err(ContractError::CampaignStillActive);
+        if env.ledger().timestamp() > deadline {
+            panic!("vesting must be configured before the deadline");
         }
-
+        if periods == 0 || interval == 0 {
+            panic!("vesting periods and interval must be positive");
+        }
+        if is_roadmap_gated(&env) {
+            panic!("vesting and roadmap gating are mutually exclusive");
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Vesting, &Vesting { periods, interval });
+
+        env.events()
+            .publish(("campaign", "vesting_set"), (periods, interval));
+    }
+
+    /// Unlock payouts in proportion to completed roadmap items.
+    ///
+    /// Must be configured before the deadline and cannot be combined with
+    /// vesting. Once set, `withdraw` is disabled in favour of
+    /// `withdraw_milestone`.
+    pub fn set_roadmap_gating(env: Env) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        if env.ledger().timestamp() > deadline {
+            panic!("roadmap gating must be configured before the deadline");
+        }
+        if env.storage().instance().has(&DataKey::Vesting) {
+            panic!("vesting and roadmap gating are mutually exclusive");
+        }
+
+        env.storage().instance().set(&DataKey::RoadmapGated, &true);
+
+        env.events()
+            .publish(("campaign", "roadmap_gating_set"), creator);
+    }
+
+    /// Configure a sponsor to match backer contributions.
+    ///
+    /// Requires both the creator's and the sponsor's authorization. The
+    /// sponsor deposits `cap` tokens up front. Each later contribution is
+    /// matched at `match_bps` out of that deposit until it is exhausted; the
+    /// matched amount counts toward `total_raised` but not toward any backer.
+    /// Matches never push `total_raised` past the hard cap, or past the goal
+    /// under a strict goal.
+    ///
+    /// Whatever is left of the deposit goes back to the sponsor when the
+    /// campaign pays out. On a failed or cancelled campaign the matched
+    /// share is returned to the sponsor as well.
+    pub fn set_matching(
+        env: Env,
+        creator: Address,
+        sponsor: Address,
+        match_bps: u32,
+        cap: i128,
+    ) -> Result<(), ContractError> {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
+
+        creator.require_auth();
+        sponsor.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+        if env.storage().instance().has(&DataKey::Matching) {
+            panic!("matching already configured");
+        }
+        if match_bps == 0 || cap <= 0 {
+            panic!("match rate and cap must be positive");
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        token_client.transfer(&sponsor, &env.current_contract_address(), &cap);
+
+        env.storage().instance().set(&DataKey::SponsorBalance, &cap);
+        env.storage().instance().set(
+            &DataKey::Matching,
+            &Matching {
+                sponsor: sponsor.clone(),
+                match_bps,
+                cap,
+                matched: 0,
+            },
+        );
+
+        env.events()
+            .publish(("campaign", "matching_set"), (sponsor, match_bps, cap));
+
+        Ok(())
+    }
+
+    /// Require the verified portion of contributions to reach `quality_goal`
+    /// before the campaign counts as successful.
+    pub fn set_quality_goal(env: Env, creator: Address, quality_goal: i128) {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
+
+        creator.require_auth();
+
         let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
-        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
-        if total < goal {
-            return Err(ContractError::GoalNotReached);
+        if quality_goal <= 0 || quality_goal > goal {
+            panic!("quality goal must be positive and not exceed the goal");
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::QualityGoal, &quality_goal);
+    }
+
+    /// Contribute tokens to the campaign.
+    ///
+    /// The contributor must authorize the call. Contributions are rejected
+    /// before the start time and after the deadline has passed.
+    ///
+    /// With `anonymous` set, a backer not already listed publicly is kept off
+    /// the `Contributors` list; anonymity then sticks for later contributions.
+    /// The per-address `Contribution` entry is still written, so anonymous
+    /// backers can reclaim funds through `refund_single`.
+    ///
+    /// A `memo` replaces any earlier one from the same contributor; passing
+    /// `None` keeps the previous memo.
+    ///
+    /// In escrow mode the gross amount is parked as pending instead, and the
+    /// returned receipt reflects the ledger before it; see
+    /// `accept_contribution`. Escrowed contributions cannot be anonymous or
+    /// referred.
+    pub fn contribute(
+        env: Env,
+        contributor: Address,
+        amount: i128,
+        referral: Option<Address>,
+        anonymous: bool,
+        tip: i128,
+        memo: Option<String>,
+    ) -> Result<ContributionReceipt, ContractError> {
+        // ── Rate limiting: enforce cooldown between contributions ──
+        let now = env.ledger().timestamp();
+        let last_time_key = DataKey::LastContributionTime(contributor.clone());
+        if let Some(last_time) = env.storage().persistent().get::<_, u64>(&last_time_key) {
+            if now < last_time.saturating_add(contribution_cooldown(&env)) {
+                return Err(ContractError::RateLimitExceeded);
+            }
+        }
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        if !Self::has_started(env.clone()) {
+            return Err(ContractError::CampaignNotStarted);
+        }
+
+        if address_is_blocked(&env, &contributor) {
+            return Err(ContractError::AddressBlocked);
+        }
+
+        let min_contribution: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinContribution)
+            .unwrap();
+        if amount < min_contribution {
+            panic!("amount below minimum");
+        }
+
+        let fixed_levels = Self::fixed_levels(env.clone());
+        if !fixed_levels.is_empty() && !fixed_levels.contains(amount) {
+            return Err(ContractError::InvalidPledgeLevel);
+        }
+
+        if tip < 0 {
+            panic!("tip cannot be negative");
+        }
+
+        if let Some(ref memo) = memo {
+            if memo.len() > MAX_MEMO_LENGTH {
+                panic!("memo too long");
+            }
+        }
+
+        if referral.as_ref() == Some(&contributor) {
+            return Err(ContractError::SelfReferral);
+        }
+
+        let escrow = is_escrow_mode(&env);
+        if escrow && (anonymous || referral.is_some()) {
+            return Err(ContractError::EscrowUnsupported);
+        }
+
+        // ── Entry fee: only the net amount is credited to the campaign ──
+        let gross_amount = amount;
+        let fee = entry_fee(&env, gross_amount);
+        let amount = gross_amount - fee;
+
+        if env.ledger().timestamp() > contribution_deadline(&env) {
+            return Err(ContractError::CampaignEnded);
+        }
+
+        // ── Hard cap: with `RefundExcess` only what fits is taken, provided
+        // that is itself a valid contribution, and the excess never leaves
+        // the wallet; escrow is checked on acceptance ──
+        let room = hard_cap_room(&env);
+        let (gross_amount, fee, amount) = if escrow || amount <= room {
+            (gross_amount, fee, amount)
+        } else {
+            // The cap bounds the credited net amount, so trim the gross to
+            // the largest one whose net still fits.
+            let capped = gross_for_net(&env, room);
+            let capped_fee = entry_fee(&env, capped);
+            if capped - capped_fee > 0
+                && capped >= min_contribution
+                && (fixed_levels.is_empty() || fixed_levels.contains(capped))
+                && refund_policy(&env) == RefundPolicy::RefundExcess
+            {
+                env.events().publish(
+                    ("campaign", "contribution_capped"),
+                    (contributor.clone(), capped, gross_amount - capped),
+                );
+                (capped, capped_fee, capped - capped_fee)
+            } else {
+                return Err(ContractError::HardCapExceeded);
+            }
+        };
+
+        // ── Strict goal: the total may never exceed the goal ──
+        let strict_goal: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::StrictGoal)
+            .unwrap_or(false);
+        if strict_goal && amount > remaining_to_goal(&env) {
+            return Err(ContractError::ContributionExceedsGoal);
+        }
+
+        // ── Per-address cap on the cumulative contribution ──
+        let contribution_key = DataKey::Contribution(contributor.clone());
+        let previous_amount: i128 = env
+            .storage()
+            .persistent()
+            .get(&contribution_key)
+            .unwrap_or(0);
+        // A pending escrow contribution counts as the address's one contribution.
+        if is_single_contribution(&env)
+            && (previous_amount > 0
+                || Self::pending_contribution(env.clone(), contributor.clone()) > 0)
+        {
+            return Err(ContractError::AlreadyContributed);
+        }
+        let new_contribution = previous_amount
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        if let Some(max) = env
+            .storage()
+            .instance()
+            .get::<_, i128>(&DataKey::MaxContribution)
+        {
+            if new_contribution > max {
+                return Err(ContractError::MaxContributionExceeded);
+            }
         }
 
+        // ── Contributor cap: only backers joining the public list count ──
+        if let Some(max) = Self::max_contributors(env.clone()) {
+            let contributors: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Contributors)
+                .unwrap_or_else(|| Vec::new(&env));
+            let joins_list = !anonymous
+                && !contributors.contains(&contributor)
+                && !env
+                    .storage()
+                    .persistent()
+                    .has(&DataKey::Anonymous(contributor.clone()));
+            if joins_list && contributors.len() >= max {
+                return Err(ContractError::ContributorLimitReached);
+            }
+        }
+
+        // ── Periodic cap: limit how much one address sends per window ──
+        let period_usage = match env
+            .storage()
+            .instance()
+            .get::<_, PeriodLimit>(&DataKey::PeriodLimit)
+        {
+            Some(limit) => {
+                let usage_key = DataKey::PeriodUsage(contributor.clone());
+                let usage = match env.storage().persistent().get::<_, PeriodUsage>(&usage_key) {
+                    Some(usage) if now < usage.window_start + limit.period_seconds => usage,
+                    _ => PeriodUsage {
+                        window_start: now,
+                        amount: 0,
+                    },
+                };
+                let used = usage
+                    .amount
+                    .checked_add(amount)
+                    .ok_or(ContractError::Overflow)?;
+                if used > limit.cap {
+                    return Err(ContractError::PeriodCapExceeded);
+                }
+                Some(PeriodUsage {
+                    window_start: usage.window_start,
+                    amount: used,
+                })
+            }
+            None => None,
+        };
+
+        track_participant(&env, &contributor);
+
         let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
         let token_client = token::Client::new(&env, &token_address);
+        transfer_or_fail(
+            &env,
+            &token_client,
+            &contributor,
+            &env.current_contract_address(),
+            gross_amount,
+            "contribute",
+        )?;
+        if fee > 0 && !escrow {
+            transfer_platform_fee(&env, &token_client, fee);
+        }
+
+        // Tips go straight to the creator; they never join the pool, count
+        // toward the goal or get refunded.
+        if tip > 0 {
+            let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+            transfer_or_fail(&env, &token_client, &contributor, &creator, tip, "tip")?;
+
+            let total_tips: i128 = env.storage().instance().get(&DataKey::Tips).unwrap_or(0);
+            let total_tips = total_tips.checked_add(tip).ok_or(ContractError::Overflow)?;
+            env.storage().instance().set(&DataKey::Tips, &total_tips);
+            env.events()
+                .publish(("campaign", "tipped"), (contributor.clone(), tip));
+        }
+
+        if let Some(ref memo) = memo {
+            let memo_key = DataKey::Memo(contributor.clone());
+            env.storage().persistent().set(&memo_key, memo);
+            env.storage().persistent().extend_ttl(&memo_key, 100, 100);
+        }
+
+        // ── Escrow: park the gross amount; the entry fee is charged on accept ──
+        if escrow {
+            let pending_key = DataKey::Pending(contributor.clone());
+            let pending: i128 = env.storage().persistent().get(&pending_key).unwrap_or(0);
+            let pending = pending
+                .checked_add(gross_amount)
+                .ok_or(ContractError::Overflow)?;
+            let pending_total = Self::pending_total(env.clone())
+                .checked_add(gross_amount)
+                .ok_or(ContractError::Overflow)?;
+            env.storage().persistent().set(&pending_key, &pending);
+            env.storage()
+                .persistent()
+                .extend_ttl(&pending_key, 100, 100);
+            env.storage()
+                .instance()
+                .set(&DataKey::PendingTotal, &pending_total);
+
+            env.events().publish(
+                ("campaign", "contribution_pending"),
+                (contributor.clone(), gross_amount, memo),
+            );
+            record_contribution_time(&env, &contributor, now, period_usage);
+
+            let contributors: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Contributors)
+                .unwrap_or_else(|| Vec::new(&env));
+            return Ok(ContributionReceipt {
+                total_contributed: previous_amount,
+                campaign_total: env.storage().instance().get(&DataKey::TotalRaised).unwrap(),
+                rank: contributors
+                    .first_index_of(&contributor)
+                    .map_or(0, |index| index + 1),
+            });
+        }
+
+        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+        let new_total = total.checked_add(amount).ok_or(ContractError::Overflow)?;
+
+        env.storage()
+            .persistent()
+            .set(&contribution_key, &new_contribution);
+        env.storage()
+            .persistent()
+            .extend_ttl(&contribution_key, 100, 100);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalRaised, &new_total);
+        record_contribution_entry(&env, &contributor, amount);
+
+        // Verified contributions also count toward the quality goal.
+        if address_is_verified(&env, &contributor) {
+            let verified_raised: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::VerifiedRaised)
+                .unwrap_or(0);
+            let new_verified_raised = verified_raised
+                .checked_add(amount)
+                .ok_or(ContractError::Overflow)?;
+            env.storage()
+                .instance()
+                .set(&DataKey::VerifiedRaised, &new_verified_raised);
+        }
+
+        let mut contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let anonymous_key = DataKey::Anonymous(contributor.clone());
+        let is_anonymous = !contributors.contains(&contributor)
+            && (anonymous || env.storage().persistent().has(&anonymous_key));
+
+        if is_anonymous {
+            if previous_amount == 0 {
+                let count: u32 = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::AnonymousCount)
+                    .unwrap_or(0);
+                env.storage()
+                    .instance()
+                    .set(&DataKey::AnonymousCount, &(count + 1));
+            }
+            env.storage().persistent().set(&anonymous_key, &true);
+            env.storage()
+                .persistent()
+                .extend_ttl(&anonymous_key, 100, 100);
+
+            let anonymous_total: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::AnonymousTotal)
+                .unwrap_or(0);
+            let new_anonymous_total = anonymous_total
+                .checked_add(amount)
+                .ok_or(ContractError::Overflow)?;
+            env.storage()
+                .instance()
+                .set(&DataKey::AnonymousTotal, &new_anonymous_total);
+        } else if !contributors.contains(&contributor) {
+            contributors.push_back(contributor.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::Contributors, &contributors);
+            env.storage()
+                .persistent()
+                .extend_ttl(&DataKey::Contributors, 100, 100);
+            env.events().publish(
+                ("campaign", "new_contributor"),
+                (contributor.clone(), contributors.len()),
+            );
+        }
+
+        // Emit contribution event
+        env.events().publish(
+            ("campaign", "contributed"),
+            (contributor.clone(), amount, memo),
+        );
+        report_contribution(&env, amount);
+
+        // Credit sponsor matching from the pre-funded deposit, within the
+        // same hard-cap and strict-goal limits as the contribution itself.
+        if let Some(mut matching) = env
+            .storage()
+            .instance()
+            .get::<_, Matching>(&DataKey::Matching)
+        {
+            let balance = sponsor_balance(&env);
+            let mut matched = (amount
+                .checked_mul(matching.match_bps as i128)
+                .ok_or(ContractError::Overflow)?
+                / 10_000)
+                .min(balance)
+                .min(hard_cap_room(&env));
+            if strict_goal {
+                matched = matched.min(remaining_to_goal(&env));
+            }
+            if matched > 0 {
+                let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+                let total = total.checked_add(matched).ok_or(ContractError::Overflow)?;
+
+                matching.matched = matching
+                    .matched
+                    .checked_add(matched)
+                    .ok_or(ContractError::Overflow)?;
+                env.storage().instance().set(&DataKey::Matching, &matching);
+                let credit_key = DataKey::MatchCredit(contributor.clone());
+                let credit: i128 = env.storage().persistent().get(&credit_key).unwrap_or(0);
+                let new_credit = credit.checked_add(matched).ok_or(ContractError::Overflow)?;
+                env.storage().persistent().set(&credit_key, &new_credit);
+                env.storage().persistent().extend_ttl(&credit_key, 100, 100);
+                env.storage()
+                    .instance()
+                    .set(&DataKey::SponsorBalance, &(balance - matched));
+                env.storage().instance().set(&DataKey::TotalRaised, &total);
+
+                env.events().publish(
+                    ("campaign", "matched"),
+                    (matching.sponsor, contributor.clone(), matched),
+                );
+            }
+        }
+
+        emit_milestones(&env);
+        record_goal_reached(&env);
+
+        // Update referral tally if referral provided
+        if let Some(referrer) = referral {
+            track_participant(&env, &referrer);
+            let referral_key = DataKey::ReferralTally(referrer.clone());
+            let current_tally: i128 = env.storage().persistent().get(&referral_key).unwrap_or(0);
+
+            let new_tally = current_tally
+                .checked_add(amount)
+                .ok_or(ContractError::Overflow)?;
+
+            env.storage().persistent().set(&referral_key, &new_tally);
+            env.storage()
+                .persistent()
+                .extend_ttl(&referral_key, 100, 100);
+
+            // Emit referral event
+            env.events().publish(
+                ("campaign", "referral"),
+                (referrer, contributor.clone(), amount),
+            );
+        }
+
+        record_contribution_time(&env, &contributor, now, period_usage);
 
-        let platform_config: Option<PlatformConfig> =
-            env.storage().instance().get(&DataKey::PlatformConfig);
+        let rank = contributors
+            .first_index_of(&contributor)
+            .map_or(0, |index| index + 1);
+        Ok(ContributionReceipt {
+            total_contributed: new_contribution,
+            campaign_total: env.storage().instance().get(&DataKey::TotalRaised).unwrap(),
+            rank,
+        })
+    }
+
+    /// Contribute on behalf of `contributor` through a relayer, e.g. for
+    /// gasless contributions.
+    ///
+    /// The contributor's signed authorization covers `nonce` as well as the
+    /// token transfer, and `nonce` must equal their next value from `nonce`,
+    /// so a relayer cannot replay the same authorization. Otherwise behaves
+    /// like `contribute` with no referral, tip or memo.
+    pub fn contribute_with_auth(
+        env: Env,
+        contributor: Address,
+        amount: i128,
+        nonce: u64,
+    ) -> Result<ContributionReceipt, ContractError> {
+        contributor.require_auth();
+
+        let nonce_key = DataKey::Nonce(contributor.clone());
+        let expected: u64 = env.storage().persistent().get(&nonce_key).unwrap_or(0);
+        if nonce != expected {
+            return Err(ContractError::InvalidNonce);
+        }
+        env.storage().persistent().set(&nonce_key, &(nonce + 1));
+        env.storage().persistent().extend_ttl(&nonce_key, 100, 100);
+
+        Self::contribute(env, contributor, amount, None, false, 0, None)
+    }
+
+    /// Record several contributions funded by a single `sponsor`, e.g. an
+    /// off-chain payment processor settling pledges in bulk.
+    ///
+    /// Each `(contributor, amount)` entry is credited to that contributor as a
+    /// listed contribution, or an anonymous one if the address already backs
+    /// the campaign anonymously, and the sponsor transfers the aggregate once.
+    /// Minimum, per-address maximum, contributor-cap and strict-goal limits
+    /// apply; any invalid entry aborts the whole batch. Rate limits, period
+    /// caps, entry fees, referrals and sponsor matching do not apply to batch
+    /// entries.
+    pub fn contribute_batch(
+        env: Env,
+        sponsor: Address,
+        entries: Vec<(Address, i128)>,
+    ) -> Result<i128, ContractError> {
+        sponsor.require_auth();
+
+        if entries.is_empty() || entries.len() > MAX_BATCH_SIZE {
+            return Err(ContractError::InvalidLimit);
+        }
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        if !Self::has_started(env.clone()) {
+            return Err(ContractError::CampaignNotStarted);
+        }
+        if env.ledger().timestamp() > contribution_deadline(&env) {
+            return Err(ContractError::CampaignEnded);
+        }
+
+        let min_contribution: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinContribution)
+            .unwrap();
+        let max_contribution: Option<i128> =
+            env.storage().instance().get(&DataKey::MaxContribution);
+        let fixed_levels = Self::fixed_levels(env.clone());
+        let max_contributors = Self::max_contributors(env.clone());
+        let single_contribution = is_single_contribution(&env);
+
+        let mut contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut batch_total: i128 = 0;
+        let mut verified_total: i128 = 0;
+        let mut anonymous_total: i128 = 0;
+        let mut anonymous_joined: u32 = 0;
+
+        // Credit every entry first; returning an error reverts all of it.
+        for (contributor, amount) in entries.iter() {
+            if address_is_blocked(&env, &contributor) {
+                return Err(ContractError::AddressBlocked);
+            }
+            if amount < min_contribution {
+                panic!("amount below minimum");
+            }
+            if !fixed_levels.is_empty() && !fixed_levels.contains(amount) {
+                return Err(ContractError::InvalidPledgeLevel);
+            }
+
+            let contribution_key = DataKey::Contribution(contributor.clone());
+            let previous_amount: i128 = env
+                .storage()
+                .persistent()
+                .get(&contribution_key)
+                .unwrap_or(0);
+            if single_contribution && previous_amount > 0 {
+                return Err(ContractError::AlreadyContributed);
+            }
+            let new_amount = previous_amount
+                .checked_add(amount)
+                .ok_or(ContractError::Overflow)?;
+            if let Some(max) = max_contribution {
+                if new_amount > max {
+                    return Err(ContractError::MaxContributionExceeded);
+                }
+            }
+
+            env.storage()
+                .persistent()
+                .set(&contribution_key, &new_amount);
+            env.storage()
+                .persistent()
+                .extend_ttl(&contribution_key, 100, 100);
+
+            record_contribution_entry(&env, &contributor, amount);
+            track_participant(&env, &contributor);
+
+            if address_is_verified(&env, &contributor) {
+                verified_total = verified_total
+                    .checked_add(amount)
+                    .ok_or(ContractError::Overflow)?;
+            }
+            // Backers who chose anonymity stay anonymous, as in `contribute`.
+            if !contributors.contains(&contributor)
+                && env
+                    .storage()
+                    .persistent()
+                    .has(&DataKey::Anonymous(contributor.clone()))
+            {
+                if previous_amount == 0 {
+                    anonymous_joined += 1;
+                }
+                anonymous_total = anonymous_total
+                    .checked_add(amount)
+                    .ok_or(ContractError::Overflow)?;
+            } else if !contributors.contains(&contributor) {
+                if max_contributors.is_some_and(|max| contributors.len() >= max) {
+                    return Err(ContractError::ContributorLimitReached);
+                }
+                contributors.push_back(contributor.clone());
+                env.events().publish(
+                    ("campaign", "new_contributor"),
+                    (contributor.clone(), contributors.len()),
+                );
+            }
+
+            batch_total = batch_total
+                .checked_add(amount)
+                .ok_or(ContractError::Overflow)?;
+
+            env.events()
+                .publish(("campaign", "contributed"), (contributor, amount));
+        }
+
+        let strict_goal: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::StrictGoal)
+            .unwrap_or(false);
+        if strict_goal && batch_total > remaining_to_goal(&env) {
+            return Err(ContractError::ContributionExceedsGoal);
+        }
+        if batch_total > hard_cap_room(&env) {
+            return Err(ContractError::HardCapExceeded);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Contributors, &contributors);
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKey::Contributors, 100, 100);
+
+        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+        let new_total = total
+            .checked_add(batch_total)
+            .ok_or(ContractError::Overflow)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalRaised, &new_total);
+
+        if verified_total > 0 {
+            let verified_raised: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::VerifiedRaised)
+                .unwrap_or(0);
+            let new_verified_raised = verified_raised
+                .checked_add(verified_total)
+                .ok_or(ContractError::Overflow)?;
+            env.storage()
+                .instance()
+                .set(&DataKey::VerifiedRaised, &new_verified_raised);
+        }
+        if anonymous_total > 0 {
+            let count: u32 = env
+                .storage()
+                .instance()
+                .get(&DataKey::AnonymousCount)
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&DataKey::AnonymousCount, &(count + anonymous_joined));
+            let previous_total: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::AnonymousTotal)
+                .unwrap_or(0);
+            let new_anonymous_total = previous_total
+                .checked_add(anonymous_total)
+                .ok_or(ContractError::Overflow)?;
+            env.storage()
+                .instance()
+                .set(&DataKey::AnonymousTotal, &new_anonymous_total);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        token_client.transfer(&sponsor, &env.current_contract_address(), &batch_total);
+
+        env.events().publish(
+            ("campaign", "batch_contributed"),
+            (sponsor, entries.len(), batch_total),
+        );
+        report_contribution(&env, batch_total);
+        emit_milestones(&env);
+        record_goal_reached(&env);
+
+        Ok(batch_total)
+    }
+
+    /// Pull back part or all of a contribution while the campaign is active.
+    ///
+    /// Unlike `refund_single`, this is only available before the deadline.
+    /// A contributor whose balance reaches zero is removed from the
+    /// contributors list. Sponsor matching credited on the withdrawn part
+    /// is reversed in proportion and returned to the matching deposit.
+    /// Backers who flagged the campaign or voted on an open proposal cannot
+    /// reduce.
+    pub fn reduce_contribution(
+        env: Env,
+        contributor: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        contributor.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        if env.ledger().timestamp() > deadline {
+            return Err(ContractError::CampaignEnded);
+        }
+        check_churn_cooldown(&env, &contributor)?;
+        if stake_locked(&env, &contributor) {
+            return Err(ContractError::StakeLocked);
+        }
+
+        let contribution_key = DataKey::Contribution(contributor.clone());
+        let current: i128 = env
+            .storage()
+            .persistent()
+            .get(&contribution_key)
+            .unwrap_or(0);
+        if amount <= 0 || amount > current {
+            return Err(ContractError::InsufficientContribution);
+        }
+
+        let remaining = current - amount;
+        env.storage()
+            .persistent()
+            .set(&contribution_key, &remaining);
+        env.storage()
+            .persistent()
+            .extend_ttl(&contribution_key, 100, 100);
+
+        // The sponsor's match on the withdrawn part goes back into the
+        // matching deposit.
+        let unmatched = unwind_match_credit(&env, &contributor, amount, current);
+        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalRaised, &(total - amount - unmatched));
+
+        record_contribution_entry(&env, &contributor, -amount);
+        release_verified_stake(&env, &contributor, amount);
+        release_anonymous_stake(&env, &contributor, amount, remaining);
+
+        if remaining == 0 {
+            let mut contributors: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Contributors)
+                .unwrap_or_else(|| Vec::new(&env));
+            if let Some(index) = contributors.first_index_of(&contributor) {
+                contributors.remove(index);
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::Contributors, &contributors);
+            }
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        token_client.transfer(&env.current_contract_address(), &contributor, &amount);
+
+        env.events().publish(
+            ("campaign", "contribution_reduced"),
+            (contributor, amount, remaining),
+        );
+        report_contribution(&env, -amount);
+
+        Ok(())
+    }
+
+    /// Reassign `from`'s entire contribution to `to`, e.g. when moving to a
+    /// new wallet.
+    ///
+    /// `to` takes over `from`'s place on the contributors list, or merges
+    /// into its own entry if already listed, so reward eligibility follows
+    /// the funds. `total_raised` is unchanged. Anonymous stakes cannot be
+    /// transferred, and neither can the stake of a backer who flagged the
+    /// campaign or voted on an open proposal. The single-contribution and
+    /// contributor-cap limits apply to `to` as for a new contribution.
+    pub fn transfer_contribution(
+        env: Env,
+        from: Address,
+        to: Address,
+    ) -> Result<(), ContractError> {
+        from.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        if from == to {
+            panic!("cannot transfer a contribution to the same address");
+        }
+        if address_is_blocked(&env, &to) {
+            return Err(ContractError::AddressBlocked);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Anonymous(from.clone()))
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::Anonymous(to.clone()))
+        {
+            panic!("anonymous contributions cannot be transferred");
+        }
+        check_churn_cooldown(&env, &from)?;
+        if stake_locked(&env, &from) {
+            return Err(ContractError::StakeLocked);
+        }
+
+        let from_key = DataKey::Contribution(from.clone());
+        let amount: i128 = env.storage().persistent().get(&from_key).unwrap_or(0);
+        if amount <= 0 {
+            return Err(ContractError::NotContributor);
+        }
+
+        let to_key = DataKey::Contribution(to.clone());
+        let previous_amount: i128 = env.storage().persistent().get(&to_key).unwrap_or(0);
+        if is_single_contribution(&env)
+            && (previous_amount > 0 || Self::pending_contribution(env.clone(), to.clone()) > 0)
+        {
+            return Err(ContractError::AlreadyContributed);
+        }
+        let new_amount = previous_amount
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        if let Some(max) = env
+            .storage()
+            .instance()
+            .get::<_, i128>(&DataKey::MaxContribution)
+        {
+            if new_amount > max {
+                return Err(ContractError::MaxContributionExceeded);
+            }
+        }
+
+        // `to` normally takes over `from`'s list entry; it only adds one
+        // when `from` was not listed, and that entry counts toward the cap.
+        let mut contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+        let joins_list = !contributors.contains(&from) && !contributors.contains(&to);
+        if joins_list
+            && Self::max_contributors(env.clone()).is_some_and(|max| contributors.len() >= max)
+        {
+            return Err(ContractError::ContributorLimitReached);
+        }
+
+        env.storage().persistent().set(&from_key, &0i128);
+        env.storage().persistent().extend_ttl(&from_key, 100, 100);
+        env.storage().persistent().set(&to_key, &new_amount);
+        env.storage().persistent().extend_ttl(&to_key, 100, 100);
+        track_participant(&env, &to);
+        record_contribution_entry(&env, &from, -amount);
+        record_contribution_entry(&env, &to, amount);
+
+        // Any sponsor match follows the funds.
+        let from_credit_key = DataKey::MatchCredit(from.clone());
+        let credit: i128 = env
+            .storage()
+            .persistent()
+            .get(&from_credit_key)
+            .unwrap_or(0);
+        if credit > 0 {
+            env.storage().persistent().remove(&from_credit_key);
+            let to_credit_key = DataKey::MatchCredit(to.clone());
+            let to_credit: i128 = env.storage().persistent().get(&to_credit_key).unwrap_or(0);
+            let new_credit = to_credit
+                .checked_add(credit)
+                .ok_or(ContractError::Overflow)?;
+            env.storage().persistent().set(&to_credit_key, &new_credit);
+            env.storage()
+                .persistent()
+                .extend_ttl(&to_credit_key, 100, 100);
+        }
+
+        // Verified stakes count toward the quality goal only while held by a
+        // verified address.
+        let verified_delta = match (
+            address_is_verified(&env, &from),
+            address_is_verified(&env, &to),
+        ) {
+            (true, false) => -amount,
+            (false, true) => amount,
+            _ => 0,
+        };
+        if verified_delta != 0 {
+            let verified_raised: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::VerifiedRaised)
+                .unwrap_or(0);
+            let new_verified_raised = verified_raised
+                .checked_add(verified_delta)
+                .ok_or(ContractError::Overflow)?;
+            env.storage()
+                .instance()
+                .set(&DataKey::VerifiedRaised, &new_verified_raised);
+        }
+
+        if let Some(index) = contributors.first_index_of(&from) {
+            if contributors.contains(&to) {
+                contributors.remove(index);
+            } else {
+                contributors.set(index, to.clone());
+            }
+        } else if joins_list {
+            contributors.push_back(to.clone());
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Contributors, &contributors);
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKey::Contributors, 100, 100);
+
+        env.events()
+            .publish(("campaign", "contribution_transferred"), (from, to, amount));
+
+        Ok(())
+    }
+
+    /// Credit `contributor`'s pending escrow contribution to the ledger and
+    /// return the net amount credited.
+    ///
+    /// The entry fee is charged now, and the per-address maximum,
+    /// contributor cap and strict goal are checked against the ledger at
+    /// acceptance. Sponsor matching does not apply to escrowed contributions.
+    pub fn accept_contribution(
+        env: Env,
+        creator: Address,
+        contributor: Address,
+    ) -> Result<i128, ContractError> {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
+
+        creator.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        if env.ledger().timestamp() > contribution_deadline(&env) {
+            return Err(ContractError::CampaignEnded);
+        }
+
+        let gross_amount = take_pending_contribution(&env, &contributor)?;
+        let fee = entry_fee(&env, gross_amount);
+        let amount = gross_amount - fee;
+
+        let strict_goal: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::StrictGoal)
+            .unwrap_or(false);
+        if strict_goal && amount > remaining_to_goal(&env) {
+            return Err(ContractError::ContributionExceedsGoal);
+        }
+        if amount > hard_cap_room(&env) {
+            return Err(ContractError::HardCapExceeded);
+        }
+
+        let contribution_key = DataKey::Contribution(contributor.clone());
+        let previous_amount: i128 = env
+            .storage()
+            .persistent()
+            .get(&contribution_key)
+            .unwrap_or(0);
+        let new_contribution = previous_amount
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        if let Some(max) = env
+            .storage()
+            .instance()
+            .get::<_, i128>(&DataKey::MaxContribution)
+        {
+            if new_contribution > max {
+                return Err(ContractError::MaxContributionExceeded);
+            }
+        }
+
+        let mut contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+        let joins_list = !contributors.contains(&contributor)
+            && !env
+                .storage()
+                .persistent()
+                .has(&DataKey::Anonymous(contributor.clone()));
+        if joins_list
+            && Self::max_contributors(env.clone()).is_some_and(|max| contributors.len() >= max)
+        {
+            return Err(ContractError::ContributorLimitReached);
+        }
+
+        if fee > 0 {
+            let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+            let token_client = token::Client::new(&env, &token_address);
+            transfer_platform_fee(&env, &token_client, fee);
+        }
+
+        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+        let new_total = total.checked_add(amount).ok_or(ContractError::Overflow)?;
+
+        env.storage()
+            .persistent()
+            .set(&contribution_key, &new_contribution);
+        env.storage()
+            .persistent()
+            .extend_ttl(&contribution_key, 100, 100);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalRaised, &new_total);
+        record_contribution_entry(&env, &contributor, amount);
+
+        if address_is_verified(&env, &contributor) {
+            let verified_raised: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::VerifiedRaised)
+                .unwrap_or(0);
+            let new_verified_raised = verified_raised
+                .checked_add(amount)
+                .ok_or(ContractError::Overflow)?;
+            env.storage()
+                .instance()
+                .set(&DataKey::VerifiedRaised, &new_verified_raised);
+        }
+
+        if joins_list {
+            contributors.push_back(contributor.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::Contributors, &contributors);
+            env.storage()
+                .persistent()
+                .extend_ttl(&DataKey::Contributors, 100, 100);
+            env.events().publish(
+                ("campaign", "new_contributor"),
+                (contributor.clone(), contributors.len()),
+            );
+        }
+
+        env.events()
+            .publish(("campaign", "contribution_accepted"), (contributor, amount));
+        report_contribution(&env, amount);
+        emit_milestones(&env);
+        record_goal_reached(&env);
+
+        Ok(amount)
+    }
+
+    /// Return `contributor`'s pending escrow contribution in full and return
+    /// the amount refunded.
+    ///
+    /// Allowed in any status, so parked funds never outlive the campaign.
+    pub fn reject_contribution(
+        env: Env,
+        creator: Address,
+        contributor: Address,
+    ) -> Result<i128, ContractError> {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
+
+        creator.require_auth();
+
+        let amount = take_pending_contribution(&env, &contributor)?;
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        transfer_or_fail(
+            &env,
+            &token_client,
+            &env.current_contract_address(),
+            &contributor,
+            amount,
+            "refund",
+        )?;
+
+        env.events()
+            .publish(("campaign", "contribution_rejected"), (contributor, amount));
+
+        Ok(amount)
+    }
+
+    /// Reclaim `contributor`'s pending escrow contribution the creator never
+    /// accepted, and return the amount.
+    ///
+    /// Available once the deadline has passed or the campaign has settled,
+    /// so backers are not left waiting on the creator.
+    pub fn withdraw_pending(env: Env, contributor: Address) -> Result<i128, ContractError> {
+        contributor.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        if status == Status::Active && env.ledger().timestamp() <= deadline {
+            return Err(ContractError::CampaignStillActive);
+        }
+        if Self::pending_contribution(env.clone(), contributor.clone()) <= 0 {
+            return Err(ContractError::NoPendingContribution);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        return_pending(&env, &token_client, &contributor)
+    }
+
+    /// Pledge tokens to the campaign without transferring them immediately.
+    ///
+    /// The pledger must authorize the call. Pledges are recorded off-chain
+    /// and only collected if the goal is met after the deadline.
+    pub fn pledge(env: Env, pledger: Address, amount: i128) -> Result<(), ContractError> {
+        pledger.require_auth();
+
+        let min_contribution: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinContribution)
+            .unwrap();
+        if amount < min_contribution {
+            panic!("amount below minimum");
+        }
+
+        if env.ledger().timestamp() > contribution_deadline(&env) {
+            return Err(ContractError::CampaignEnded);
+        }
+
+        // Update the pledger's running total.
+        let pledge_key = DataKey::Pledge(pledger.clone());
+        let prev: i128 = env.storage().persistent().get(&pledge_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&pledge_key, &(prev + amount));
+        env.storage().persistent().extend_ttl(&pledge_key, 100, 100);
+
+        // Update the global total pledged.
+        let total_pledged: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalPledged)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalPledged, &(total_pledged + amount));
+
+        // Track pledger address if new.
+        let mut pledgers: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pledgers)
+            .unwrap_or_else(|| Vec::new(&env));
+        if !pledgers.contains(&pledger) {
+            pledgers.push_back(pledger.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::Pledgers, &pledgers);
+            env.storage()
+                .persistent()
+                .extend_ttl(&DataKey::Pledgers, 100, 100);
+        }
+
+        // Emit pledge event
+        env.events()
+            .publish(("campaign", "pledged"), (pledger, amount));
+
+        Ok(())
+    }
+
+    /// Collect all pledges after the deadline when the goal is met.
+    ///
+    /// This function transfers tokens from all pledgers to the contract.
+    /// Only callable after the deadline and when the combined total of
+    /// contributions and pledges meets or exceeds the goal.
+    pub fn collect_pledges(env: Env) -> Result<(), ContractError> {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        if env.ledger().timestamp() <= deadline {
+            return Err(ContractError::CampaignStillActive);
+        }
+
+        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+        let total_raised: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+        let total_pledged: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalPledged)
+            .unwrap_or(0);
+
+        // Check if combined total meets the goal
+        if total_raised + total_pledged < goal {
+            return Err(ContractError::GoalNotReached);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+
+        let pledgers: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pledgers)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        // Collect pledges from all pledgers
+        for pledger in pledgers.iter() {
+            let pledge_key = DataKey::Pledge(pledger.clone());
+            let amount: i128 = env.storage().persistent().get(&pledge_key).unwrap_or(0);
+            if amount > 0 {
+                // Transfer tokens from pledger to contract
+                token_client.transfer(&pledger, &env.current_contract_address(), &amount);
+
+                // Clear the pledge
+                env.storage().persistent().set(&pledge_key, &0i128);
+                env.storage().persistent().extend_ttl(&pledge_key, 100, 100);
+            }
+        }
+
+        // Update total raised to include collected pledges
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalRaised, &(total_raised + total_pledged));
+
+        // Reset total pledged
+        env.storage().instance().set(&DataKey::TotalPledged, &0i128);
+
+        // Emit pledges collected event
+        env.events()
+            .publish(("campaign", "pledges_collected"), total_pledged);
+
+        Ok(())
+    }
+
+    /// Commit to contribute `amount` every `interval` seconds, `count` times.
+    ///
+    /// Each instalment is pulled by `execute_pledge` through a token
+    /// allowance the contributor grants to this contract. A new schedule
+    /// replaces any existing one. Escrow campaigns do not take scheduled
+    /// pledges, the amount must be an allowed pledge level, and
+    /// single-contribution campaigns take a single instalment from a backer
+    /// who has not contributed yet.
+    pub fn schedule_pledge(
+        env: Env,
+        contributor: Address,
+        amount: i128,
+        interval: u64,
+        count: u32,
+    ) -> Result<(), ContractError> {
+        contributor.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        let min_contribution: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinContribution)
+            .unwrap();
+        if amount < min_contribution {
+            panic!("amount below minimum");
+        }
+        if interval == 0 || count == 0 {
+            panic!("pledge schedule must have a positive interval and count");
+        }
+        if is_escrow_mode(&env) {
+            return Err(ContractError::EscrowUnsupported);
+        }
+        let fixed_levels = Self::fixed_levels(env.clone());
+        if !fixed_levels.is_empty() && !fixed_levels.contains(amount) {
+            return Err(ContractError::InvalidPledgeLevel);
+        }
+        if is_single_contribution(&env) {
+            if count > 1 {
+                panic!("single-contribution campaigns allow one instalment");
+            }
+            if Self::contribution(env.clone(), contributor.clone()) > 0 {
+                return Err(ContractError::AlreadyContributed);
+            }
+        }
+
+        track_participant(&env, &contributor);
+        let schedule_key = DataKey::ScheduledPledge(contributor.clone());
+        env.storage().persistent().set(
+            &schedule_key,
+            &ScheduledPledge {
+                amount,
+                interval,
+                remaining: count,
+                last_executed: None,
+            },
+        );
+        env.storage()
+            .persistent()
+            .extend_ttl(&schedule_key, 100, 100);
+
+        env.events().publish(
+            ("campaign", "pledge_scheduled"),
+            (contributor, amount, interval, count),
+        );
+
+        Ok(())
+    }
+
+    /// Pull the next instalment of `contributor`'s scheduled pledge.
+    ///
+    /// Anyone may call this, at most once per interval. The instalment is
+    /// credited like a contribution without fee, tip or referral: the start
+    /// time, pledge levels, per-address maximum, single-contribution,
+    /// contributor-cap, strict-goal and hard-cap limits apply, and it is
+    /// logged and counted toward milestones. The schedule is removed after
+    /// its last instalment.
+    pub fn execute_pledge(env: Env, contributor: Address) -> Result<(), ContractError> {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        if !Self::has_started(env.clone()) {
+            return Err(ContractError::CampaignNotStarted);
+        }
+        let now = env.ledger().timestamp();
+        if now > contribution_deadline(&env) {
+            return Err(ContractError::CampaignEnded);
+        }
+
+        if address_is_blocked(&env, &contributor) {
+            return Err(ContractError::AddressBlocked);
+        }
+
+        let schedule_key = DataKey::ScheduledPledge(contributor.clone());
+        let mut schedule: ScheduledPledge = env
+            .storage()
+            .persistent()
+            .get(&schedule_key)
+            .ok_or(ContractError::NoScheduledPledge)?;
+        if let Some(last_executed) = schedule.last_executed {
+            if now < last_executed + schedule.interval {
+                return Err(ContractError::PledgeNotDue);
+            }
+        }
+
+        let amount = schedule.amount;
+        // Levels may have changed since the pledge was scheduled.
+        let fixed_levels = Self::fixed_levels(env.clone());
+        if !fixed_levels.is_empty() && !fixed_levels.contains(amount) {
+            return Err(ContractError::InvalidPledgeLevel);
+        }
+        let strict_goal: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::StrictGoal)
+            .unwrap_or(false);
+        if strict_goal && amount > remaining_to_goal(&env) {
+            return Err(ContractError::ContributionExceedsGoal);
+        }
+        if amount > hard_cap_room(&env) {
+            return Err(ContractError::HardCapExceeded);
+        }
+
+        let contribution_key = DataKey::Contribution(contributor.clone());
+        let previous_amount: i128 = env
+            .storage()
+            .persistent()
+            .get(&contribution_key)
+            .unwrap_or(0);
+        if is_single_contribution(&env) && previous_amount > 0 {
+            return Err(ContractError::AlreadyContributed);
+        }
+        let new_amount = previous_amount
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        if let Some(max) = env
+            .storage()
+            .instance()
+            .get::<_, i128>(&DataKey::MaxContribution)
+        {
+            if new_amount > max {
+                return Err(ContractError::MaxContributionExceeded);
+            }
+        }
+
+        let mut contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+        let is_anonymous = env
+            .storage()
+            .persistent()
+            .has(&DataKey::Anonymous(contributor.clone()));
+        let joins_list = !is_anonymous && !contributors.contains(&contributor);
+        if joins_list
+            && Self::max_contributors(env.clone()).is_some_and(|max| contributors.len() >= max)
+        {
+            return Err(ContractError::ContributorLimitReached);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        token_client.transfer_from(
+            &env.current_contract_address(),
+            &contributor,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        env.storage()
+            .persistent()
+            .set(&contribution_key, &new_amount);
+        env.storage()
+            .persistent()
+            .extend_ttl(&contribution_key, 100, 100);
+
+        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+        let new_total = total.checked_add(amount).ok_or(ContractError::Overflow)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalRaised, &new_total);
+        record_contribution_entry(&env, &contributor, amount);
+
+        if address_is_verified(&env, &contributor) {
+            let verified_raised: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::VerifiedRaised)
+                .unwrap_or(0);
+            let new_verified_raised = verified_raised
+                .checked_add(amount)
+                .ok_or(ContractError::Overflow)?;
+            env.storage()
+                .instance()
+                .set(&DataKey::VerifiedRaised, &new_verified_raised);
+        }
+
+        if is_anonymous {
+            let anonymous_total: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::AnonymousTotal)
+                .unwrap_or(0);
+            let new_anonymous_total = anonymous_total
+                .checked_add(amount)
+                .ok_or(ContractError::Overflow)?;
+            env.storage()
+                .instance()
+                .set(&DataKey::AnonymousTotal, &new_anonymous_total);
+        } else if joins_list {
+            contributors.push_back(contributor.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::Contributors, &contributors);
+            env.storage()
+                .persistent()
+                .extend_ttl(&DataKey::Contributors, 100, 100);
+            env.events().publish(
+                ("campaign", "new_contributor"),
+                (contributor.clone(), contributors.len()),
+            );
+        }
+
+        schedule.remaining -= 1;
+        schedule.last_executed = Some(now);
+        if schedule.remaining == 0 {
+            env.storage().persistent().remove(&schedule_key);
+        } else {
+            env.storage().persistent().set(&schedule_key, &schedule);
+            env.storage()
+                .persistent()
+                .extend_ttl(&schedule_key, 100, 100);
+        }
+
+        env.events().publish(
+            ("campaign", "pledge_executed"),
+            (contributor, amount, schedule.remaining),
+        );
+        report_contribution(&env, amount);
+        emit_milestones(&env);
+        record_goal_reached(&env);
+
+        Ok(())
+    }
+
+    /// Resolve the campaign once the deadline has passed.
+    ///
+    /// Marks the campaign `Successful` when every success condition holds,
+    /// so the creator can `withdraw`, and `Refunded` otherwise, so backers
+    /// can `refund_single`. `withdraw`, `collect_fee` and the refund paths
+    /// all wait for this call. The outcome is fixed from then on, and a
+    /// reference goal stays at the token amount the oracle price gave here.
+    /// Successful vested and roadmap-gated campaigns settle through their
+    /// first tranche instead; failed ones are finalized like any other.
+    pub fn finalize(env: Env, creator: Address) -> Result<Status, ContractError> {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
+        creator.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        if env.ledger().timestamp() <= deadline {
+            return Err(ContractError::CampaignStillActive);
+        }
+
+        // An unreachable oracle counts as a missed goal and pins nothing.
+        if let Ok(goal) = goal_in_token(&env) {
+            env.storage().instance().set(&DataKey::PinnedGoal, &goal);
+        }
+        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+        let outcome = if is_donation_mode(&env) || check_success(&env, total).is_ok() {
+            if env.storage().instance().has(&DataKey::Vesting) {
+                return Err(ContractError::VestingConfigured);
+            }
+            if is_roadmap_gated(&env) {
+                return Err(ContractError::RoadmapGated);
+            }
+            // Pin the fee now so `collect_fee` can run before `withdraw`.
+            env.storage().instance().set(
+                &DataKey::FeeDue,
+                &platform_fee(&env, total - overflow_amount(&env, total)),
+            );
+            Status::Successful
+        } else {
+            Status::Refunded
+        };
+
+        env.storage().instance().set(&DataKey::Finalized, &true);
+        set_status(&env, outcome.clone());
+
+        // Missing the goal is not abuse, so a failed campaign's bond goes
+        // back to the creator too; a successful one's waits for `withdraw`.
+        if outcome == Status::Refunded {
+            let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+            let token_client = token::Client::new(&env, &token_address);
+            release_bond(&env, &token_client, &creator, "bond_returned")?;
+        }
+
+        env.events()
+            .publish(("campaign", "finalized"), (outcome.clone(), total));
+
+        Ok(outcome)
+    }
+
+    pub fn withdraw(env: Env) -> Result<(), ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let total = check_withdrawable(&env)?;
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+
+        // With `overflow_refund`, anything raised past the goal stays in the
+        // contract for backers to reclaim through `claim_overflow`.
+        let overflow = hold_overflow(&env, total);
+
+        // The platform fee stays in the contract for `collect_fee`; the
+        // creator only ever receives the net payout. Held-back overflow is
+        // not charged.
+        let fee = platform_fee(&env, total - overflow);
+        let fee_collected: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::FeeCollected)
+            .unwrap_or(false);
+        if !fee_collected {
+            env.storage().instance().set(&DataKey::FeeDue, &fee);
+        }
+
+        // Settle state before any external token call (checks-effects-interactions).
+        env.storage().instance().set(&DataKey::TotalRaised, &0i128);
+        env.storage().instance().remove(&DataKey::Finalized);
+
+        pay_creator(&env, &token_client, &creator, total - fee - overflow)?;
+        record_withdrawal(&env, total - fee - overflow);
+        release_bond(&env, &token_client, &creator, "bond_returned")?;
+        refund_sponsor(&env, &token_client, false)?;
+
+        // Mint one commemorative NFT per eligible contributor after successful payout.
+        mint_contributor_nfts(&env);
+
+        env.events()
+            .publish(("campaign", "withdrawn"), (creator.clone(), total));
+
+        Ok(())
+    }
+
+    /// Dry-run `withdraw`: returns the error it would fail with right now,
+    /// without moving funds or requiring auth. Once the campaign has settled
+    /// this is `CampaignNotActive`.
+    pub fn can_withdraw(env: Env) -> Result<(), ContractError> {
+        check_withdrawable(&env).map(|_| ())
+    }
+
+    /// Claim a contributor's share of the amount raised past the goal.
+    ///
+    /// Available once the creator has withdrawn, or first drawn a tranche
+    /// from, a campaign with `overflow_refund` that over-collected. Each
+    /// contributor receives the pool in proportion to their contribution
+    /// among backers still to claim, so the last claimer also takes any
+    /// rounding dust. Sponsor matches are paid out to backers the same way.
+    /// Each contributor may claim only once.
+    pub fn claim_overflow(env: Env, contributor: Address) -> Result<i128, ContractError> {
+        contributor.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Successful {
+            return Err(ContractError::CampaignNotSettled);
+        }
+
+        let overflow: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::OverflowPool)
+            .unwrap_or(0);
+        if overflow <= 0 {
+            return Err(ContractError::NoOverflow);
+        }
+
+        let claimed_key = DataKey::OverflowClaimed(contributor.clone());
+        if env.storage().persistent().has(&claimed_key) {
+            return Err(ContractError::OverflowAlreadyClaimed);
+        }
+
+        let contribution: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(contributor.clone()))
+            .unwrap_or(0);
+        if contribution <= 0 {
+            return Err(ContractError::NotContributor);
+        }
+
+        let base: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::OverflowBase)
+            .unwrap();
+        let share = if contribution >= base {
+            overflow
+        } else {
+            overflow
+                .checked_mul(contribution)
+                .ok_or(ContractError::Overflow)?
+                / base
+        };
+
+        env.storage()
+            .instance()
+            .set(&DataKey::OverflowPool, &(overflow - share));
+        env.storage()
+            .instance()
+            .set(&DataKey::OverflowBase, &(base - contribution));
+        env.storage().persistent().set(&claimed_key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&claimed_key, 100, 100);
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        token_client.transfer(&env.current_contract_address(), &contributor, &share);
+
+        env.events()
+            .publish(("campaign", "overflow_claimed"), (contributor, share));
+
+        Ok(share)
+    }
+
+    /// Mint `contributor`'s NFT when `withdraw` stopped at the NFT batch
+    /// limit before reaching them.
+    ///
+    /// Available on a `Successful` campaign to listed contributors whose
+    /// stake meets the NFT minimum, once per address.
+    pub fn claim_nft(env: Env, contributor: Address) -> Result<(), ContractError> {
+        contributor.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Successful {
+            return Err(ContractError::CampaignNotSettled);
+        }
+
+        let nft_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::NFTContract)
+            .ok_or(ContractError::NoNftContract)?;
+        if Self::nft_claimed(env.clone(), contributor.clone()) {
+            return Err(ContractError::NftAlreadyClaimed);
+        }
+
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+        let position = contributors
+            .first_index_of(&contributor)
+            .ok_or(ContractError::NotContributor)?;
+        let amount: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(contributor.clone()))
+            .unwrap_or(0);
+        if amount <= 0 || amount < nft_min_amount(&env) {
+            return Err(ContractError::NotContributor);
+        }
+
+        let nft_client = NftContractClient::new(&env, &nft_contract);
+        mint_contributor_nft(&env, &nft_client, &contributor, position, amount);
+
+        Ok(())
+    }
+
+    /// Transfer the platform fee of a successful campaign to the platform.
+    ///
+    /// Callable by the platform admin once `finalize` has marked the campaign
+    /// `Successful`, either before or after the creator's `withdraw`; the fee
+    /// can only be collected once. Vested and
+    /// roadmap-gated campaigns pay the fee with each tranche instead.
+    pub fn collect_fee(env: Env, admin: Address) -> Result<i128, ContractError> {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("not authorized");
+        }
+
+        admin.require_auth();
+
+        if !env.storage().instance().has(&DataKey::PlatformConfig) {
+            return Err(ContractError::NoPlatformFee);
+        }
+        if env.storage().instance().has(&DataKey::Vesting) {
+            return Err(ContractError::VestingConfigured);
+        }
+        if is_roadmap_gated(&env) {
+            return Err(ContractError::RoadmapGated);
+        }
+        let fee_collected: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::FeeCollected)
+            .unwrap_or(false);
+        if fee_collected {
+            return Err(ContractError::FeeAlreadyCollected);
+        }
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        // `finalize` pins the fee; `withdraw` keeps it once the flag is gone.
+        if status != Status::Successful {
+            require_outcome(
+                &env,
+                &status,
+                Status::Successful,
+                ContractError::GoalNotReached,
+            )?;
+        }
+        let fee: i128 = env.storage().instance().get(&DataKey::FeeDue).unwrap_or(0);
+
+        env.storage().instance().set(&DataKey::FeeCollected, &true);
+        env.storage().instance().set(&DataKey::FeeDue, &fee);
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        transfer_platform_fee(&env, &token_client, fee);
+
+        Ok(fee)
+    }
+
+    /// Withdraw whatever has vested since the deadline and not yet been paid.
+    ///
+    /// The first call settles the campaign: it checks the deadline and goal
+    /// like `withdraw`, pins the vesting total and marks the campaign
+    /// `Successful`. Later calls release further tranches as they vest.
+    pub fn withdraw_vested(env: Env) -> Result<i128, ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Vesting) {
+            return Err(ContractError::VestingNotConfigured);
+        }
+
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        if env.ledger().timestamp() <= deadline {
+            return Err(ContractError::CampaignStillActive);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Active {
+            let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+            check_success(&env, total)?;
+
+            let overflow = hold_overflow(&env, total);
+            env.storage()
+                .instance()
+                .set(&DataKey::VestingTotal, &(total - overflow));
+            env.storage()
+                .instance()
+                .set(&DataKey::TotalRaised, &(total - overflow));
+            set_status(&env, Status::Successful);
+            mint_contributor_nfts(&env);
+            refund_sponsor(&env, &token_client, false)?;
+            release_bond(&env, &token_client, &creator, "bond_returned")?;
+        } else if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        } else if status != Status::Successful {
+            panic!("campaign is not active");
+        }
+
+        let withdrawn: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Withdrawn)
+            .unwrap_or(0);
+        let amount = vested_amount(&env) - withdrawn;
+        if amount <= 0 {
+            return Err(ContractError::NothingToWithdraw);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Withdrawn, &(withdrawn + amount));
+        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalRaised, &(total - amount));
+
+        let creator_payout = pay_platform_fee(&env, &token_client, amount);
+        pay_creator(&env, &token_client, &creator, creator_payout)?;
+        record_withdrawal(&env, creator_payout);
+
+        env.events()
+            .publish(("campaign", "vested_withdrawn"), (creator, amount));
+
+        Ok(amount)
+    }
+
+    /// Withdraw the share of funds unlocked by completed roadmap items and
+    /// not yet paid.
+    ///
+    /// The first call settles the campaign like `withdraw_vested`. With 3 of
+    /// 4 items complete, up to 75% of the pinned total is withdrawable.
+    pub fn withdraw_milestone(env: Env) -> Result<i128, ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        if !is_roadmap_gated(&env) {
+            return Err(ContractError::NotRoadmapGated);
+        }
+
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        if env.ledger().timestamp() <= deadline {
+            return Err(ContractError::CampaignStillActive);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Active {
+            let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+            check_success(&env, total)?;
+
+            let overflow = hold_overflow(&env, total);
+            env.storage()
+                .instance()
+                .set(&DataKey::MilestoneTotal, &(total - overflow));
+            env.storage()
+                .instance()
+                .set(&DataKey::TotalRaised, &(total - overflow));
+            set_status(&env, Status::Successful);
+            mint_contributor_nfts(&env);
+            refund_sponsor(&env, &token_client, false)?;
+            release_bond(&env, &token_client, &creator, "bond_returned")?;
+        } else if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        } else if status != Status::Successful {
+            panic!("campaign is not active");
+        }
+
+        let milestone_total: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MilestoneTotal)
+            .unwrap();
+        let unlocked = milestone_total * Self::roadmap_progress(env.clone()) as i128 / 10_000;
+        let withdrawn: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Withdrawn)
+            .unwrap_or(0);
+        let amount = unlocked - withdrawn;
+        if amount <= 0 {
+            return Err(ContractError::NothingToWithdraw);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Withdrawn, &(withdrawn + amount));
+        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalRaised, &(total - amount));
+
+        let creator_payout = pay_platform_fee(&env, &token_client, amount);
+        pay_creator(&env, &token_client, &creator, creator_payout)?;
+        record_withdrawal(&env, creator_payout);
+
+        env.events()
+            .publish(("campaign", "milestone_withdrawn"), (creator, amount));
+
+        Ok(amount)
+    }
+
+    pub fn refund_single(env: Env, contributor: Address) -> Result<(), ContractError> {
+        contributor.require_auth();
+
+        // Campaigns cancelled by the creator or finalized as failed stay
+        // open for refunds.
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        let cancelled = is_creator_cancelled(&env, &status);
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active && !cancelled && !is_finalized(&env, &status) {
+            panic!("campaign is not active");
+        }
+
+        if !cancelled {
+            if is_donation_mode(&env) {
+                return Err(ContractError::DonationMode);
+            }
+            require_outcome(&env, &status, Status::Refunded, ContractError::GoalReached)?;
+        }
+        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        // The sponsor's deposit, including matches, goes back with the first
+        // refund, so backer refunds alone can bring the total to zero.
+        let total = total - refund_sponsor(&env, &token_client, true)?;
+        // A pending escrow contribution goes back with the refund.
+        return_pending(&env, &token_client, &contributor)?;
+
+        let contribution_key = DataKey::Contribution(contributor.clone());
+        let amount: i128 = env
+            .storage()
+            .persistent()
+            .get(&contribution_key)
+            .unwrap_or(0);
+
+        if amount == 0 {
+            return Ok(());
+        }
+
+        // Settle accounting before the external token call
+        // (checks-effects-interactions).
+        env.storage().persistent().set(&contribution_key, &0i128);
+        env.storage()
+            .persistent()
+            .extend_ttl(&contribution_key, 100, 100);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalRaised, &(total - amount));
+
+        record_contribution_entry(&env, &contributor, -amount);
+        release_verified_stake(&env, &contributor, amount);
+        release_anonymous_stake(&env, &contributor, amount, 0);
+
+        transfer_or_fail(
+            &env,
+            &token_client,
+            &env.current_contract_address(),
+            &contributor,
+            amount,
+            "refund",
+        )?;
+        env.events()
+            .publish(("campaign", "refunded"), (contributor, amount));
+        report_contribution(&env, -amount);
+
+        Ok(())
+    }
+
+    /// Refund every contributor in a single creator-driven transaction.
+    ///
+    /// Valid once `finalize` has marked the campaign `Refunded`. Mirrors
+    /// `refund_single` for each listed contributor and returns every pending
+    /// escrow contribution.
+    pub fn creator_refund_all(env: Env) -> Result<(), ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active && !is_finalized(&env, &status) {
+            panic!("campaign is not active");
+        }
+
+        if is_donation_mode(&env) {
+            return Err(ContractError::DonationMode);
+        }
+        require_outcome(&env, &status, Status::Refunded, ContractError::GoalReached)?;
+
+        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        let total = total - refund_sponsor(&env, &token_client, true)?;
+        refund_pending_contributions(&env);
+
+        let refunded = refund_listed_contributors(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalRaised, &(total - refunded));
+
+        // Anonymous backers are not listed, so they reclaim their funds via
+        // `refund_single`.
+        env.events()
+            .publish(("campaign", "creator_refunded"), (creator, refunded));
+        report_contribution(&env, -refunded);
+
+        Ok(())
+    }
+
+    /// Cancel an active campaign, recording `reason` for backers.
+    ///
+    /// With `auto_refund` every listed contributor is refunded immediately,
+    /// along with every pending escrow contribution. Otherwise, and always
+    /// for anonymous backers, funds are reclaimed through `refund_single` or
+    /// `withdraw_pending`, which stay available on a cancelled campaign.
+    /// Large campaigns can skip the refund loops this way.
+    pub fn cancel(env: Env, reason: String, auto_refund: bool) -> Result<(), ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        if reason.is_empty() {
+            panic!("cancel reason cannot be empty");
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::CancelReason, &reason);
+        set_status(&env, Status::Cancelled);
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        refund_sponsor(&env, &token_client, true)?;
+
+        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+        let refunded = if auto_refund {
+            refund_pending_contributions(&env);
+            refund_listed_contributors(&env)
+        } else {
+            0
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalRaised, &(total - refunded));
+
+        let platform = env
+            .storage()
+            .instance()
+            .get::<_, PlatformConfig>(&DataKey::PlatformConfig)
+            .map(|config| config.address)
+            .unwrap_or_else(|| env.storage().instance().get(&DataKey::Admin).unwrap());
+        release_bond(&env, &token_client, &platform, "bond_forfeited")?;
+
+        env.events()
+            .publish(("campaign", "cancelled"), (creator, reason, refunded));
+        report_contribution(&env, -refunded);
+
+        Ok(())
+    }
+
+    /// Sweep refunds nobody claimed to the creator and cancel the campaign.
+    ///
+    /// Valid on a campaign finalized as failed once `deadline + claim_window`
+    /// has passed;
+    /// until then backers reclaim funds through `refund_single`. Without a
+    /// configured claim window unclaimed refunds never expire. Only the
+    /// stakes backers left unclaimed are swept, and their entries are
+    /// zeroed; sponsor funds and pending escrow contributions keep their own
+    /// return paths, and stray deposits stay put. Returns the swept amount.
+    pub fn sweep_unclaimed(env: Env) -> Result<i128, ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active && !is_finalized(&env, &status) {
+            panic!("campaign is not active");
+        }
+        require_outcome(&env, &status, Status::Refunded, ContractError::GoalReached)?;
+
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        let claim_window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ClaimWindow)
+            .ok_or(ContractError::ClaimWindowOpen)?;
+        if env.ledger().timestamp() <= deadline.saturating_add(claim_window) {
+            return Err(ContractError::ClaimWindowOpen);
+        }
+
+        // The sponsor's deposit goes back to the sponsor first; `finalize`
+        // has already returned any creator bond. Pending escrow
+        // contributions stay for their backers to `withdraw_pending`.
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        refund_sponsor(&env, &token_client, true)?;
+        let swept = take_unclaimed_contributions(&env)?;
+        env.storage().instance().set(&DataKey::TotalRaised, &0i128);
+        env.storage().instance().remove(&DataKey::Finalized);
+        set_status(&env, Status::Cancelled);
+
+        if swept > 0 {
+            token_client.transfer(&env.current_contract_address(), &creator, &swept);
+        }
+
+        env.events()
+            .publish(("campaign", "unclaimed_swept"), (creator, swept));
+
+        Ok(swept)
+    }
+
+    /// Send this contract's full balance of a stray `token` to `to`.
+    ///
+    /// Only the platform admin may sweep, and never the campaign token, which
+    /// backs contributions. Returns the swept amount.
+    pub fn sweep_foreign_token(
+        env: Env,
+        admin: Address,
+        token: Address,
+        to: Address,
+    ) -> Result<i128, ContractError> {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("not authorized");
+        }
+
+        admin.require_auth();
+
+        let campaign_token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        if token == campaign_token {
+            return Err(ContractError::CampaignTokenNotSweepable);
+        }
+
+        let token_client = token::Client::new(&env, &token);
+        let amount = token_client.balance(&env.current_contract_address());
+        if amount > 0 {
+            token_client.transfer(&env.current_contract_address(), &to, &amount);
+        }
+
+        env.events()
+            .publish(("campaign", "foreign_swept"), (token, to, amount));
+
+        Ok(amount)
+    }
+
+    /// Pause an active campaign.
+    ///
+    /// Only the platform admin may pause. Contributions, payouts and refunds
+    /// are rejected with `ContractPaused` until `unpause`; the deadline is
+    /// not extended.
+    pub fn pause(env: Env, admin: Address) {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("not authorized");
+        }
+
+        admin.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        set_status(&env, Status::Paused);
+    }
+
+    /// Resume a campaign paused through `pause`. Only the platform admin may
+    /// unpause.
+    pub fn unpause(env: Env, admin: Address) {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("not authorized");
+        }
+
+        admin.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Paused {
+            panic!("campaign is not paused");
+        }
+
+        set_status(&env, Status::Active);
+    }
+
+    /// Clear all campaign state so this instance can be initialized again.
+    ///
+    /// Only the platform admin may reset, and only once the campaign has
+    /// settled (`Successful`, `Refunded` or `Cancelled`) and the contract
+    /// holds no campaign tokens.
+    pub fn reset(env: Env, platform_admin: Address) -> Result<(), ContractError> {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if platform_admin != stored_admin {
+            panic!("not authorized");
+        }
+
+        platform_admin.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Active || status == Status::Paused {
+            return Err(ContractError::CampaignNotSettled);
+        }
+
+        let total: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalRaised)
+            .unwrap_or(0);
+        if total != 0 || contract_token_balance(&env) != 0 {
+            return Err(ContractError::BalanceNotZero);
+        }
+
+        clear_campaign_state(&env);
+
+        env.events().publish(("campaign", "reset"), platform_admin);
+
+        Ok(())
+    }
+
+    /// Flag the campaign as suspicious.
+    ///
+    /// Only addresses with a non-zero contribution may flag, and each only
+    /// once. When flags reach `FLAG_THRESHOLD_BPS` of the backer count,
+    /// anonymous backers included, a `flag_threshold_reached` event is emitted
+    /// for the platform admin.
+    pub fn flag_campaign(env: Env, contributor: Address) -> Result<(), ContractError> {
+        contributor.require_auth();
+
+        let amount: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(contributor.clone()))
+            .unwrap_or(0);
+        if amount <= 0 {
+            return Err(ContractError::NotContributor);
+        }
+
+        let flag_key = DataKey::Flags(contributor.clone());
+        if env.storage().persistent().has(&flag_key) {
+            return Err(ContractError::AlreadyFlagged);
+        }
+        env.storage().persistent().set(&flag_key, &true);
+        env.storage().persistent().extend_ttl(&flag_key, 100, 100);
+
+        let previous: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::FlagCount)
+            .unwrap_or(0);
+        let flag_count = previous + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::FlagCount, &flag_count);
+
+        env.events()
+            .publish(("campaign", "flagged"), (contributor, flag_count));
+
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+        let anonymous_count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AnonymousCount)
+            .unwrap_or(0);
+        let backer_count = contributors.len() + anonymous_count;
+        let threshold = backer_count as u64 * FLAG_THRESHOLD_BPS as u64;
+        let reached = |count: u32| count as u64 * 10_000 >= threshold;
+        if reached(flag_count) && !reached(previous) {
+            env.events().publish(
+                ("campaign", "flag_threshold_reached"),
+                (flag_count, backer_count),
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn add_roadmap_item(env: Env, date: u64, description: String) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        if date <= env.ledger().timestamp() {
+            panic!("date must be in the future");
+        }
+
+        if description.is_empty() {
+            panic!("description cannot be empty");
+        }
+
+        let mut roadmap: Vec<RoadmapItem> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Roadmap)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        roadmap.push_back(RoadmapItem {
+            date,
+            description: description.clone(),
+        });
+
+        env.storage().instance().set(&DataKey::Roadmap, &roadmap);
+        env.events()
+            .publish(("campaign", "roadmap_item_added"), (date, description));
+    }
+
+    /// Remove roadmap item `index`.
+    ///
+    /// Later items shift down by one, along with their completion flags.
+    /// Proposals refer to items by index, so removal is rejected while any
+    /// proposal is open. Roadmap-gated campaigns lock their roadmap against
+    /// removals once settled.
+    pub fn remove_roadmap_item(
+        env: Env,
+        creator: Address,
+        index: u32,
+    ) -> Result<(), ContractError> {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
+
+        creator.require_auth();
+
+        let mut roadmap: Vec<RoadmapItem> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Roadmap)
+            .unwrap_or_else(|| Vec::new(&env));
+        let item = roadmap
+            .get(index)
+            .ok_or(ContractError::RoadmapItemNotFound)?;
+        // Dropping unfinished items would unlock gated funds early.
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if is_roadmap_gated(&env) && status != Status::Active {
+            panic!("roadmap is locked");
+        }
+        if has_open_proposal(&env) {
+            return Err(ContractError::ProposalOpen);
+        }
+        roadmap.remove(index);
+
+        // Keep completion flags aligned with the shifted items.
+        for i in index..roadmap.len() {
+            let done = roadmap_item_done(&env, i + 1);
+            set_roadmap_item_done(&env, i, done);
+        }
+        set_roadmap_item_done(&env, roadmap.len(), false);
+
+        env.storage().instance().set(&DataKey::Roadmap, &roadmap);
+        env.events().publish(
+            ("campaign", "roadmap_item_removed"),
+            (index, item.date, item.description),
+        );
+
+        Ok(())
+    }
+
+    /// Replace roadmap item `index` in place. The same rules as
+    /// `add_roadmap_item` apply to the new date and description. Edits are
+    /// rejected while a proposal is open so they cannot pre-empt the vote.
+    pub fn edit_roadmap_item(
+        env: Env,
+        creator: Address,
+        index: u32,
+        date: u64,
+        description: String,
+    ) -> Result<(), ContractError> {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
+
+        creator.require_auth();
+
+        if date <= env.ledger().timestamp() {
+            panic!("date must be in the future");
+        }
+
+        if description.is_empty() {
+            panic!("description cannot be empty");
+        }
+
+        let mut roadmap: Vec<RoadmapItem> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Roadmap)
+            .unwrap_or_else(|| Vec::new(&env));
+        if index >= roadmap.len() {
+            return Err(ContractError::RoadmapItemNotFound);
+        }
+        if has_open_proposal(&env) {
+            return Err(ContractError::ProposalOpen);
+        }
+        roadmap.set(
+            index,
+            RoadmapItem {
+                date,
+                description: description.clone(),
+            },
+        );
+
+        env.storage().instance().set(&DataKey::Roadmap, &roadmap);
+        env.events().publish(
+            ("campaign", "roadmap_item_edited"),
+            (index, date, description),
+        );
+
+        Ok(())
+    }
+
+    /// Mark roadmap item `index` as delivered.
+    pub fn complete_roadmap_item(
+        env: Env,
+        creator: Address,
+        index: u32,
+    ) -> Result<(), ContractError> {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
+
+        creator.require_auth();
+
+        let roadmap: Vec<RoadmapItem> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Roadmap)
+            .unwrap_or_else(|| Vec::new(&env));
+        if index >= roadmap.len() {
+            return Err(ContractError::RoadmapItemNotFound);
+        }
+
+        let done_key = DataKey::RoadmapDone(index);
+        env.storage().persistent().set(&done_key, &true);
+        env.storage().persistent().extend_ttl(&done_key, 100, 100);
+
+        env.events()
+            .publish(("campaign", "roadmap_item_completed"), index);
+
+        Ok(())
+    }
+
+    /// Propose replacing the description of roadmap item `index`.
+    ///
+    /// Backers decide through `vote`; the change applies once yes-votes carry
+    /// more than half of `total_raised` as it stood when the proposal was
+    /// made. Returns the new proposal's id.
+    pub fn propose_roadmap_change(
+        env: Env,
+        creator: Address,
+        index: u32,
+        new_description: String,
+    ) -> Result<u32, ContractError> {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
+
+        creator.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        let roadmap: Vec<RoadmapItem> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Roadmap)
+            .unwrap_or_else(|| Vec::new(&env));
+        if index >= roadmap.len() {
+            panic!("roadmap item not found");
+        }
+        if new_description.is_empty() {
+            panic!("description cannot be empty");
+        }
+
+        let proposal_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProposalCount)
+            .unwrap_or(0);
+        let total_raised: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalRaised)
+            .unwrap_or(0);
+        let proposal_key = DataKey::Proposal(proposal_id);
+        env.storage().persistent().set(
+            &proposal_key,
+            &RoadmapProposal {
+                index,
+                description: new_description.clone(),
+                yes_weight: 0,
+                no_weight: 0,
+                total_raised,
+                resolved: false,
+                approved: false,
+            },
+        );
+        env.storage()
+            .persistent()
+            .extend_ttl(&proposal_key, 100, 100);
+        env.storage()
+            .instance()
+            .set(&DataKey::ProposalCount, &(proposal_id + 1));
+
+        env.events().publish(
+            ("campaign", "roadmap_change_proposed"),
+            (proposal_id, index, new_description),
+        );
+
+        Ok(proposal_id)
+    }
+
+    /// Vote on a roadmap change proposal, weighted by the caller's current
+    /// contribution.
+    ///
+    /// The proposal is approved and applied once yes-votes exceed half of
+    /// the `total_raised` recorded on the proposal, and rejected once
+    /// no-votes reach half. Each contributor votes at most once per proposal,
+    /// and only while the campaign is active.
+    pub fn vote(
+        env: Env,
+        contributor: Address,
+        proposal_id: u32,
+        approve: bool,
+    ) -> Result<(), ContractError> {
+        contributor.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        let weight: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(contributor.clone()))
+            .unwrap_or(0);
+        if weight <= 0 {
+            return Err(ContractError::NotContributor);
+        }
+
+        let proposal_key = DataKey::Proposal(proposal_id);
+        let mut proposal: RoadmapProposal = env
+            .storage()
+            .persistent()
+            .get(&proposal_key)
+            .ok_or(ContractError::NoProposal)?;
+        if proposal.resolved {
+            return Err(ContractError::ProposalResolved);
+        }
+
+        let vote_key = DataKey::Vote(proposal_id, contributor.clone());
+        if env.storage().persistent().has(&vote_key) {
+            return Err(ContractError::AlreadyVoted);
+        }
+        env.storage().persistent().set(&vote_key, &approve);
+        env.storage().persistent().extend_ttl(&vote_key, 100, 100);
+
+        if approve {
+            proposal.yes_weight = proposal
+                .yes_weight
+                .checked_add(weight)
+                .ok_or(ContractError::Overflow)?;
+        } else {
+            proposal.no_weight = proposal
+                .no_weight
+                .checked_add(weight)
+                .ok_or(ContractError::Overflow)?;
+        }
+
+        env.events().publish(
+            ("campaign", "voted"),
+            (proposal_id, contributor, approve, weight),
+        );
+
+        let total_raised = proposal.total_raised;
+        if proposal.yes_weight.saturating_mul(2) > total_raised {
+            let mut roadmap: Vec<RoadmapItem> = env
+                .storage()
+                .instance()
+                .get(&DataKey::Roadmap)
+                .unwrap_or_else(|| Vec::new(&env));
+            let mut item = roadmap.get(proposal.index).unwrap();
+            item.description = proposal.description.clone();
+            roadmap.set(proposal.index, item);
+            env.storage().instance().set(&DataKey::Roadmap, &roadmap);
+
+            proposal.resolved = true;
+            proposal.approved = true;
+            env.events().publish(
+                ("campaign", "roadmap_change_applied"),
+                (proposal_id, proposal.index),
+            );
+        } else if proposal.no_weight.saturating_mul(2) >= total_raised {
+            proposal.resolved = true;
+            env.events()
+                .publish(("campaign", "roadmap_change_rejected"), proposal_id);
+        }
+
+        env.storage().persistent().set(&proposal_key, &proposal);
+        env.storage()
+            .persistent()
+            .extend_ttl(&proposal_key, 100, 100);
+
+        Ok(())
+    }
+
+    /// Returns the roadmap change proposal with `proposal_id`, if any.
+    pub fn proposal(env: Env, proposal_id: u32) -> Option<RoadmapProposal> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+    }
+
+    pub fn roadmap(env: Env) -> Vec<RoadmapItem> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Roadmap)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns roadmap items dated at or before `cutoff`, in insertion order.
+    pub fn roadmap_due_before(env: Env, cutoff: u64) -> Vec<RoadmapItem> {
+        let mut due = Vec::new(&env);
+        for item in Self::roadmap(env.clone()).iter() {
+            if item.date <= cutoff {
+                due.push_back(item);
+            }
+        }
+        due
+    }
+
+    /// Returns the share of roadmap items marked complete, in basis points.
+    pub fn roadmap_progress(env: Env) -> u32 {
+        let len = Self::roadmap(env.clone()).len();
+        if len == 0 {
+            return 0;
+        }
+        let done = (0..len).filter(|i| roadmap_item_done(&env, *i)).count() as u32;
+        done * 10_000 / len
+    }
+
+    /// Returns roadmap items dated after the current ledger time, in
+    /// insertion order.
+    pub fn roadmap_upcoming(env: Env) -> Vec<RoadmapItem> {
+        let now = env.ledger().timestamp();
+        let mut upcoming = Vec::new(&env);
+        for item in Self::roadmap(env.clone()).iter() {
+            if item.date > now {
+                upcoming.push_back(item);
+            }
+        }
+        upcoming
+    }
+
+    /// Post a project update for backers. Unlike roadmap items, updates are
+    /// stamped with the current ledger time.
+    pub fn post_update(env: Env, message: String) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        if message.is_empty() {
+            panic!("message cannot be empty");
+        }
+        if message.len() > MAX_UPDATE_LENGTH {
+            panic!("message too long");
+        }
+
+        let id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::UpdateCount)
+            .unwrap_or(0);
+        let update_key = DataKey::Update(id);
+        let timestamp = env.ledger().timestamp();
+        env.storage().persistent().set(
+            &update_key,
+            &Update {
+                timestamp,
+                message: message.clone(),
+            },
+        );
+        env.storage().persistent().extend_ttl(&update_key, 100, 100);
+        env.storage()
+            .instance()
+            .set(&DataKey::UpdateCount, &(id + 1));
+
+        env.events()
+            .publish(("campaign", "update_posted"), (timestamp, message));
+    }
+
+    /// Returns the number of project updates posted.
+    pub fn update_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::UpdateCount)
+            .unwrap_or(0)
+    }
+
+    /// Returns up to `limit` project updates starting at index `start`, in
+    /// posting order.
+    ///
+    /// `limit` is clamped to `MAX_PAGE_SIZE`; an empty list is returned when
+    /// `start` is past the end.
+    pub fn updates(env: Env, start: u32, limit: u32) -> Vec<Update> {
+        let count = Self::update_count(env.clone());
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+        let mut updates = Vec::new(&env);
+        for id in start..end {
+            if let Some(update) = env.storage().persistent().get(&DataKey::Update(id)) {
+                updates.push_back(update);
+            }
+        }
+        updates
+    }
+
+    /// Returns the campaign's current lifecycle status.
+    pub fn status(env: Env) -> Status {
+        env.storage().instance().get(&DataKey::Status).unwrap()
+    }
+
+    /// Returns the total tipped to the creator on top of contributions.
+    pub fn total_tips(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::Tips).unwrap_or(0)
+    }
+
+    pub fn total_raised(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalRaised)
+            .unwrap_or(0)
+    }
+
+    /// Returns the reference-currency goal converted into token base units at
+    /// the oracle's current price, or at the price `finalize` pinned.
+    pub fn goal_in_token(env: Env) -> Result<i128, ContractError> {
+        goal_in_token(&env)
+    }
+
+    /// Returns the reference-currency goal, if the campaign uses one.
+    pub fn reference_goal(env: Env) -> Option<ReferenceGoal> {
+        env.storage().instance().get(&DataKey::ReferenceGoal)
+    }
+
+    pub fn goal(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::Goal).unwrap()
+    }
+
+    /// Returns the cap on listed contributors, if any.
+    pub fn max_contributors(env: Env) -> Option<u32> {
+        env.storage().instance().get(&DataKey::MaxContributors)
+    }
+
+    /// Returns the next nonce `contribute_with_auth` accepts from
+    /// `contributor`.
+    pub fn nonce(env: Env, contributor: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Nonce(contributor))
+            .unwrap_or(0)
+    }
+
+    /// Returns whether contributions have opened.
+    pub fn has_started(env: Env) -> bool {
+        env.ledger().timestamp() >= Self::start_time(env.clone())
+    }
+
+    /// Returns the ledger timestamp at which contributions open.
+    pub fn start_time(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::StartTime)
+            .unwrap_or(0)
+    }
+
+    /// Returns whether `total_raised` has reached the funding goal.
+    ///
+    /// Uses the same comparison as `withdraw` and `refund_single`; an
+    /// unusable oracle price counts as not reached. Other success conditions
+    /// such as the quality goal or minimum contributors are not considered.
+    pub fn goal_reached(env: Env) -> bool {
+        let total: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalRaised)
+            .unwrap_or(0);
+        goal_amount_reached(&env, total).unwrap_or(false)
+    }
+
+    /// Returns the seconds an address must wait between contributions.
+    pub fn contribution_cooldown(env: Env) -> u64 {
+        contribution_cooldown(&env)
+    }
+
+    /// Returns the seconds a contributor must wait after contributing before
+    /// reducing or transferring; 0 when no churn cooldown is configured.
+    pub fn churn_cooldown(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ChurnCooldown)
+            .unwrap_or(0)
+    }
+
+    /// Returns how many seconds remain before `contributor` may contribute
+    /// again; 0 if they can contribute now.
+    pub fn cooldown_remaining(env: Env, contributor: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get::<_, u64>(&DataKey::LastContributionTime(contributor))
+            .map_or(0, |last_time| {
+                last_time
+                    .saturating_add(contribution_cooldown(&env))
+                    .saturating_sub(env.ledger().timestamp())
+            })
+    }
+
+    /// Returns the maximum `total_raised` the campaign accepts, if capped.
+    pub fn hard_cap(env: Env) -> Option<i128> {
+        env.storage().instance().get(&DataKey::HardCap)
+    }
+
+    /// Returns how contributions past the hard cap are handled.
+    pub fn refund_policy(env: Env) -> RefundPolicy {
+        refund_policy(&env)
+    }
+
+    /// Returns whether each address may contribute only once.
+    pub fn single_contribution(env: Env) -> bool {
+        is_single_contribution(&env)
+    }
+
+    /// Returns whether funds raised past the goal are held back for backers.
+    pub fn overflow_refund(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::OverflowRefund)
+            .unwrap_or(false)
+    }
+
+    /// Returns the overflow still held for backers who have not claimed.
+    pub fn overflow_pool(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::OverflowPool)
+            .unwrap_or(0)
+    }
+
+    /// Returns whether contributions wait for creator acceptance.
+    pub fn escrow_mode(env: Env) -> bool {
+        is_escrow_mode(&env)
+    }
+
+    /// Returns the gross amount `contributor` has awaiting acceptance.
+    pub fn pending_contribution(env: Env, contributor: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Pending(contributor))
+            .unwrap_or(0)
+    }
+
+    /// Returns the sum of all pending escrow contributions.
+    pub fn pending_total(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::PendingTotal)
+            .unwrap_or(0)
+    }
+
+    /// Returns the goal set at initialization, before any stretch goal.
+    pub fn original_goal(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::OriginalGoal)
+            .unwrap_or_else(|| Self::goal(env))
+    }
+
+    /// Returns how much is still needed to reach the goal, never negative.
+    pub fn remaining_to_goal(env: Env) -> i128 {
+        remaining_to_goal(&env)
+    }
+
+    /// Returns whether contributions are clamped to the goal.
+    pub fn strict_goal(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::StrictGoal)
+            .unwrap_or(false)
+    }
+
+    /// Returns the contract's token balance minus the recorded `total_raised`,
+    /// any creator bond, pending escrow contributions, the sponsor's unused
+    /// matching deposit and unclaimed overflow.
+    ///
+    /// A positive value means untracked extra funds (tips, dust); a negative
+    /// value means a shortfall and should be treated as an alert.
+    pub fn solvency_delta(env: Env) -> i128 {
+        let total: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalRaised)
+            .unwrap_or(0);
+        contract_token_balance(&env)
+            - total
+            - Self::bond(env.clone())
+            - Self::pending_total(env.clone())
+            - sponsor_balance(&env)
+            - Self::overflow_pool(env)
+    }
+
+    /// Returns the contract's actual balance of the campaign token.
+    pub fn token_balance(env: Env) -> i128 {
+        contract_token_balance(&env)
+    }
+
+    /// Returns stray deposits: `token_balance` beyond `total_raised`, any
+    /// creator bond, pending escrow contributions, unused matching funds and
+    /// unclaimed overflow.
+    /// Same figure as `solvency_delta`, named for reconciliation.
+    pub fn surplus(env: Env) -> i128 {
+        Self::solvency_delta(env)
+    }
+
+    /// Returns the creator bond the contract still holds.
+    pub fn bond(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::Bond).unwrap_or(0)
+    }
+
+    /// Returns the part of the sponsor's matching deposit not yet credited.
+    pub fn sponsor_balance(env: Env) -> i128 {
+        sponsor_balance(&env)
+    }
+
+    /// Returns the total credited from sponsor matching so far.
+    pub fn matched_total(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get::<_, Matching>(&DataKey::Matching)
+            .map(|matching| matching.matched)
+            .unwrap_or(0)
+    }
+
+    /// Recompute the contribution ledger from storage and report whether it
+    /// is consistent.
+    ///
+    /// While backers' funds are still held (`Active` or `Paused`), listed
+    /// contributions plus the anonymous total and sponsor matching must sum
+    /// to `total_raised`; settled campaigns pay `total_raised` out by design
+    /// and skip this check. Every listed contributor must hold a non-zero
+    /// contribution until the deadline, after which refunds may zero entries.
+    pub fn check_invariants(env: Env) -> bool {
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut listed_total: i128 = 0;
+        let mut non_zero: u32 = 0;
+        for contributor in contributors.iter() {
+            let amount: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Contribution(contributor))
+                .unwrap_or(0);
+            if amount < 0 {
+                return false;
+            }
+            if amount > 0 {
+                non_zero += 1;
+            }
+            listed_total += amount;
+        }
+
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        if non_zero != contributors.len() && env.ledger().timestamp() <= deadline {
+            return false;
+        }
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active && status != Status::Paused {
+            return true;
+        }
+
+        let anonymous_total: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AnonymousTotal)
+            .unwrap_or(0);
+        let total_raised: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+        listed_total + anonymous_total + Self::matched_total(env) == total_raised
+    }
+
+    /// Returns the per-address cumulative contribution cap, if configured.
+    pub fn max_contribution(env: Env) -> Option<i128> {
+        env.storage().instance().get(&DataKey::MaxContribution)
+    }
+
+    /// Returns the per-address periodic contribution limit, if configured.
+    pub fn period_limit(env: Env) -> Option<PeriodLimit> {
+        env.storage().instance().get(&DataKey::PeriodLimit)
+    }
+
+    /// Returns which optional features are active so a generic front-end can
+    /// adapt to this campaign's configuration.
+    pub fn feature_flags(env: Env) -> FeatureFlags {
+        let storage = env.storage().instance();
+        FeatureFlags {
+            platform_fee: storage.has(&DataKey::PlatformConfig),
+            bonus_goal: storage.has(&DataKey::BonusGoal),
+            strict_goal: storage.get(&DataKey::StrictGoal).unwrap_or(false),
+            period_limit: storage.has(&DataKey::PeriodLimit),
+            quality_goal: storage.has(&DataKey::QualityGoal),
+            nft_rewards: storage.has(&DataKey::NFTContract),
+            matching: storage.has(&DataKey::Matching),
+            max_contribution: storage.has(&DataKey::MaxContribution),
+            vesting: storage.has(&DataKey::Vesting),
+        }
+    }
+
+    /// Returns when contributions close; the main deadline unless an earlier
+    /// contribution deadline was configured or a post-goal window ends first.
+    pub fn contribution_deadline(env: Env) -> u64 {
+        contribution_deadline(&env)
+    }
+
+    /// Returns when the goal was first reached, if a post-goal window is
+    /// configured and the goal has been reached.
+    pub fn goal_reached_time(env: Env) -> Option<u64> {
+        env.storage().instance().get(&DataKey::GoalReachedTime)
+    }
+
+    /// Returns the creator's reason for cancelling, if cancelled.
+    pub fn cancel_reason(env: Env) -> Option<String> {
+        env.storage().instance().get(&DataKey::CancelReason)
+    }
+
+    /// Returns the refund claim window after the deadline, if configured.
+    pub fn claim_window(env: Env) -> Option<u64> {
+        env.storage().instance().get(&DataKey::ClaimWindow)
+    }
+
+    pub fn deadline(env: Env) -> u64 {
+        env.storage().instance().get(&DataKey::Deadline).unwrap()
+    }
+
+    /// Returns the seconds left until the deadline, or 0 once it has passed.
+    pub fn time_remaining(env: Env) -> u64 {
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        deadline.saturating_sub(env.ledger().timestamp())
+    }
+
+    /// Returns whether the deadline has passed, matching the check `withdraw`
+    /// uses to settle the campaign.
+    pub fn has_ended(env: Env) -> bool {
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        env.ledger().timestamp() > deadline
+    }
+
+    pub fn contribution(env: Env, contributor: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Contribution(contributor))
+            .unwrap_or(0)
+    }
+
+    /// Returns `contributor`'s most recent contributions, oldest first, up to
+    /// `MAX_CONTRIBUTION_LOG` entries. Reductions, refunds and stakes
+    /// transferred away appear as negative entries.
+    pub fn contribution_log(env: Env, contributor: Address) -> Vec<ContributionEntry> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ContributionLog(contributor))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns the sum of `contributor`'s logged contributions made between
+    /// `from` and `to`, inclusive, net of reductions, refunds and stakes
+    /// transferred away.
+    ///
+    /// Only the retained log is searched, so contributions older than the
+    /// last `MAX_CONTRIBUTION_LOG` are not counted.
+    pub fn contributions_in_range(env: Env, contributor: Address, from: u64, to: u64) -> i128 {
+        Self::contribution_log(env, contributor)
+            .iter()
+            .filter(|entry| entry.timestamp >= from && entry.timestamp <= to)
+            .map(|entry| entry.amount)
+            .sum()
+    }
+
+    /// Returns whether `address` currently has a non-zero contribution.
+    ///
+    /// Fully refunded or withdrawn backers are no longer contributors.
+    pub fn is_contributor(env: Env, address: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get::<_, i128>(&DataKey::Contribution(address))
+            .unwrap_or(0)
+            > 0
+    }
+
+    /// Returns the contribution of each address in `addresses`, in the same
+    /// order, with 0 for non-contributors.
+    ///
+    /// At most `MAX_BATCH_SIZE` addresses may be queried at once.
+    pub fn contributions_of(env: Env, addresses: Vec<Address>) -> Result<Vec<i128>, ContractError> {
+        if addresses.len() > MAX_BATCH_SIZE {
+            return Err(ContractError::InvalidLimit);
+        }
+
+        let mut amounts = Vec::new(&env);
+        for address in addresses.iter() {
+            let amount: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Contribution(address))
+                .unwrap_or(0);
+            amounts.push_back(amount);
+        }
+        Ok(amounts)
+    }
+
+    pub fn min_contribution(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinContribution)
+            .unwrap()
+    }
+
+    /// Returns the minimum contribution in whole tokens, rounded down.
+    pub fn min_contribution_whole(env: Env) -> Result<i128, ContractError> {
+        let decimals = Self::token_decimals(env.clone());
+        Ok(Self::min_contribution(env) / decimal_scale(decimals)?)
+    }
+
+    pub fn creator(env: Env) -> Address {
+        env.storage().instance().get(&DataKey::Creator).unwrap()
+    }
+
+    /// Returns the total contributed by verified addresses.
+    pub fn verified_raised(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::VerifiedRaised)
+            .unwrap_or(0)
+    }
+
+    /// Returns the verified-contribution threshold, if one is configured.
+    pub fn quality_goal(env: Env) -> Option<i128> {
+        env.storage().instance().get(&DataKey::QualityGoal)
+    }
+
+    /// Returns whether `contributor` is currently verified.
+    pub fn is_verified(env: Env, contributor: Address) -> bool {
+        address_is_verified(&env, &contributor)
+    }
+
+    /// Returns whether `contributor` can claim, or has already claimed, a
+    /// refund from a failed campaign.
+    pub fn refund_status(env: Env, contributor: Address) -> RefundStatus {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        let refundable = refunds_open(&env);
+
+        let contribution: Option<i128> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(contributor));
+        match contribution {
+            Some(amount) if amount > 0 && refundable => RefundStatus::Pending,
+            Some(0) if refundable || status == Status::Refunded => RefundStatus::Refunded,
+            _ => RefundStatus::NotApplicable,
+        }
+    }
+
+    /// Returns what `refund_single` would transfer to `contributor` right
+    /// now, or 0 if refunds are not open.
+    ///
+    /// Stored contributions are already net of any entry fee and earlier
+    /// partial reductions, so this is the amount actually returned.
+    pub fn refundable_amount(env: Env, contributor: Address) -> i128 {
+        if !refunds_open(&env) {
+            return 0;
+        }
+        env.storage()
+            .persistent()
+            .get(&DataKey::Contribution(contributor))
+            .unwrap_or(0)
+    }
+
+    /// Returns the memo `contributor` left with their latest contribution.
+    pub fn memo_of(env: Env, contributor: Address) -> Option<String> {
+        env.storage().persistent().get(&DataKey::Memo(contributor))
+    }
+
+    /// Returns the total contributed by backers `referrer` referred.
+    pub fn referral_volume(env: Env, referrer: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReferralTally(referrer))
+            .unwrap_or(0)
+    }
+
+    /// Returns whether `address` is barred from contributing.
+    pub fn is_blocked(env: Env, address: Address) -> bool {
+        address_is_blocked(&env, &address)
+    }
+
+    /// Returns the minimum number of contributors required for success.
+    pub fn min_contributors(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinContributors)
+            .unwrap_or(0)
+    }
+
+    /// Returns the platform fee configuration, if the campaign has one.
+    pub fn platform_config(env: Env) -> Option<PlatformConfig> {
+        env.storage().instance().get(&DataKey::PlatformConfig)
+    }
+
+    /// Returns the total platform fees paid out across all withdrawals.
+    pub fn fees_collected(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeesCollected)
+            .unwrap_or(0)
+    }
+
+    /// Returns the total vested to the creator so far, including amounts
+    /// already withdrawn.
+    pub fn vested_amount(env: Env) -> i128 {
+        vested_amount(&env)
+    }
+
+    /// Returns every payout made to the creator, oldest first.
+    pub fn withdrawal_history(env: Env) -> Vec<Withdrawal> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::WithdrawalHistory)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns the total paid to the creator across `withdrawal_history`.
+    pub fn total_withdrawn(env: Env) -> i128 {
+        Self::withdrawal_history(env)
+            .iter()
+            .map(|withdrawal| withdrawal.amount)
+            .sum()
+    }
+
+    /// Returns the vesting schedule, if configured.
+    pub fn vesting(env: Env) -> Option<Vesting> {
+        env.storage().instance().get(&DataKey::Vesting)
+    }
+
+    /// Returns how many contributors have flagged the campaign.
+    pub fn flag_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::FlagCount)
+            .unwrap_or(0)
+    }
+
+    /// Returns `contributor`'s recurring pledge schedule, if any.
+    pub fn scheduled_pledge(env: Env, contributor: Address) -> Option<ScheduledPledge> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ScheduledPledge(contributor))
+    }
+
+    /// Returns whether `contributor` has flagged the campaign.
+    pub fn has_flagged(env: Env, contributor: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Flags(contributor))
+    }
+
+    /// Returns the platform admin.
+    pub fn admin(env: Env) -> Address {
+        env.storage().instance().get(&DataKey::Admin).unwrap()
+    }
+
+    /// Returns the proposed admin awaiting acceptance, if any.
+    pub fn pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
+
+    /// Returns the factory that deployed this campaign, if any.
+    pub fn factory(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Factory)
+    }
+
+    /// Returns the NFT tier thresholds, empty when tiers are not configured.
+    pub fn nft_tiers(env: Env) -> Vec<i128> {
+        env.storage()
+            .instance()
+            .get(&DataKey::NFTTiers)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns the accepted pledge amounts; empty means any amount above the
+    /// minimum.
+    pub fn fixed_levels(env: Env) -> Vec<i128> {
+        env.storage()
+            .instance()
+            .get(&DataKey::FixedLevels)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns the minimum contribution required to receive an NFT, if set.
+    pub fn nft_min_contribution(env: Env) -> Option<i128> {
+        env.storage().instance().get(&DataKey::NFTMinContribution)
+    }
+
+    /// Returns how many contributors `withdraw` mints NFTs for, if capped.
+    pub fn nft_batch_limit(env: Env) -> Option<u32> {
+        env.storage().instance().get(&DataKey::NftBatchLimit)
+    }
+
+    /// Returns whether `contributor`'s NFT has been minted.
+    pub fn nft_claimed(env: Env, contributor: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::NftClaimed(contributor))
+            .unwrap_or(false)
+    }
+
+    pub fn nft_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::NFTContract)
+    }
+
+    pub fn get_campaign_info(env: Env) -> CampaignInfo {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        let token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        let total_raised: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalRaised)
+            .unwrap_or(0);
+
+        CampaignInfo {
+            creator,
+            token,
+            goal,
+            deadline,
+            total_raised,
+        }
+    }
+
+    /// Returns the campaign details, metadata and stats in a single call.
+    pub fn get_campaign_full(env: Env) -> CampaignFull {
+        let info = Self::get_campaign_info(env.clone());
+        let stats = Self::get_stats(env.clone());
+
+        CampaignFull {
+            creator: info.creator,
+            token: info.token,
+            goal: info.goal,
+            deadline: info.deadline,
+            total_raised: info.total_raised,
+            status: Self::status(env.clone()),
+            title: Self::title(env.clone()),
+            description: Self::description(env.clone()),
+            socials: Self::socials(env.clone()),
+            min_contribution: Self::min_contribution(env),
+            contributor_count: stats.contributor_count,
+            progress_bps: stats.progress_bps,
+        }
+    }
+
+    pub fn get_stats(env: Env) -> CampaignStats {
+        let total_raised: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalRaised)
+            .unwrap_or(0);
+        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let progress_bps = if goal > 0 && total_raised >= goal {
+            10_000
+        } else if goal > 0 {
+            // Below the goal; if scaling up first would overflow, the goal is
+            // large enough to scale down instead.
+            let raw = total_raised
+                .checked_mul(10_000)
+                .map_or_else(|| total_raised / (goal / 10_000), |scaled| scaled / goal);
+            raw as u32
+        } else if total_raised > 0 {
+            // Donation mode: any funds raised complete the (zero) goal.
+            10_000
+        } else {
+            0
+        };
+
+        let anonymous_count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AnonymousCount)
+            .unwrap_or(0);
+        let anonymous_total: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AnonymousTotal)
+            .unwrap_or(0);
+
+        let contributor_count = contributors.len() + anonymous_count;
+        let (average_contribution, largest_contribution) = if contributor_count == 0 {
+            (0, 0)
+        } else {
+            let average = total_raised / contributor_count as i128;
+            let mut largest = 0i128;
+            for contributor in contributors.iter() {
+                let amount: i128 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Contribution(contributor))
+                    .unwrap_or(0);
+                if amount > largest {
+                    largest = amount;
+                }
+            }
+            (average, largest)
+        };
+
+        let verified_raised: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::VerifiedRaised)
+            .unwrap_or(0);
+
+        CampaignStats {
+            total_raised,
+            goal,
+            progress_bps,
+            contributor_count,
+            average_contribution,
+            largest_contribution,
+            verified_raised,
+            anonymous_count,
+            anonymous_total,
+        }
+    }
+
+    /// Returns the quadratic-funding weight `(Σ √contribution)²` over the
+    /// listed contributors, in token units (rounded down).
+    ///
+    /// Each square root is taken at `QF_SCALE` precision, so the sum is
+    /// `Σ √c · QF_SCALE` and its square is divided by `QF_SCALE²`. Stakes too
+    /// large to scale fall back to whole-token roots, and a weight past
+    /// `i128::MAX` saturates. Anonymous contributions are not included.
+    pub fn qf_weight(env: Env) -> i128 {
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut root_sum: i128 = 0;
+        for contributor in contributors.iter() {
+            let amount: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Contribution(contributor))
+                .unwrap_or(0);
+            if amount > 0 {
+                let root = amount
+                    .checked_mul(QF_SCALE * QF_SCALE)
+                    .map_or_else(|| isqrt(amount) * QF_SCALE, isqrt);
+                root_sum = root_sum.saturating_add(root);
+            }
+        }
+
+        root_sum.checked_mul(root_sum).map_or_else(
+            || (root_sum / QF_SCALE).saturating_mul(root_sum / QF_SCALE),
+            |square| square / (QF_SCALE * QF_SCALE),
+        )
+    }
+
+    /// Returns the campaign category.
+    pub fn category(env: Env) -> Symbol {
+        env.storage().instance().get(&DataKey::Category).unwrap()
+    }
+
+    pub fn title(env: Env) -> String {
+        env.storage()
+            .instance()
+            .get(&DataKey::Title)
+            .unwrap_or_else(|| String::from_str(&env, ""))
+    }
+
+    pub fn description(env: Env) -> String {
+        env.storage()
+            .instance()
+            .get(&DataKey::Description)
+            .unwrap_or_else(|| String::from_str(&env, ""))
+    }
+
+    pub fn socials(env: Env) -> String {
+        env.storage()
+            .instance()
+            .get(&DataKey::SocialLinks)
+            .unwrap_or_else(|| String::from_str(&env, ""))
+    }
+
+    pub fn version(_env: Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    /// Returns every contract version this instance has run, oldest first.
+    /// Versions recorded by `upgrade` are as the admin supplied them.
+    pub fn version_history(env: Env) -> Vec<VersionRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::VersionHistory)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns the token's decimals as read when the campaign was created.
+    pub fn token_decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TokenDecimals)
+            .unwrap()
+    }
+
+    /// Returns the token contract address used for contributions.
+    pub fn token(env: Env) -> Address {
+        env.storage().instance().get(&DataKey::Token).unwrap()
+    }
+
+    /// Returns every contributor address.
+    ///
+    /// Reads the whole list in one call, which may exceed resource limits for
+    /// very large campaigns; prefer `contributors_page` in that case.
+    pub fn contributors(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns up to `limit` contributors starting at index `start`.
+    ///
+    /// `limit` is clamped to `MAX_PAGE_SIZE`; an empty list is returned when
+    /// `start` is past the end.
+    pub fn contributors_page(env: Env, start: u32, limit: u32) -> Vec<Address> {
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let len = contributors.len();
+        if start >= len {
+            return Vec::new(&env);
+        }
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(len);
+        contributors.slice(start..end)
+    }
+
+    /// Returns every listed contributor paired with their stored
+    /// contribution, including refunded addresses at 0.
+    pub fn all_contributions(env: Env) -> Vec<(Address, i128)> {
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+        with_contributions(&env, contributors)
+    }
+
+    /// Paginated `all_contributions`, with the same bounds as
+    /// `contributors_page`.
+    pub fn all_contributions_page(env: Env, start: u32, limit: u32) -> Vec<(Address, i128)> {
+        let page = Self::contributors_page(env.clone(), start, limit);
+        with_contributions(&env, page)
+    }
+
+    /// Record every listed contributor's current contribution, e.g. for an
+    /// off-chain airdrop, and return the snapshot id.
+    ///
+    /// Snapshots are never changed by later contributions or refunds.
+    pub fn snapshot(env: Env, creator: Address) -> u32 {
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
+        creator.require_auth();
+
+        let id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::SnapshotCount)
+            .unwrap_or(0);
+        let timestamp = env.ledger().timestamp();
+        let snapshot = ContributorSnapshot {
+            timestamp,
+            contributions: Self::all_contributions(env.clone()),
+        };
+
+        let snapshot_key = DataKey::Snapshot(id);
+        env.storage().persistent().set(&snapshot_key, &snapshot);
+        env.storage()
+            .persistent()
+            .extend_ttl(&snapshot_key, 100, 100);
+        env.storage()
+            .instance()
+            .set(&DataKey::SnapshotCount, &(id + 1));
+
+        env.events()
+            .publish(("campaign", "snapshot_taken"), (id, timestamp));
+
+        id
+    }
+
+    /// Returns the contributions recorded by snapshot `id`, or an empty list
+    /// if no such snapshot exists.
+    pub fn get_snapshot(env: Env, id: u32) -> Vec<(Address, i128)> {
+        env.storage()
+            .persistent()
+            .get::<_, ContributorSnapshot>(&DataKey::Snapshot(id))
+            .map(|snapshot| snapshot.contributions)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns the ledger timestamp at which snapshot `id` was taken.
+    pub fn snapshot_timestamp(env: Env, id: u32) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get::<_, ContributorSnapshot>(&DataKey::Snapshot(id))
+            .map(|snapshot| snapshot.timestamp)
+    }
+
+    /// Returns the number of snapshots taken so far.
+    pub fn snapshot_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SnapshotCount)
+            .unwrap_or(0)
+    }
+
+    /// Returns up to `n` listed contributors with the largest contributions,
+    /// sorted descending.
+    ///
+    /// `n` is clamped to `MAX_LEADERBOARD_SIZE`. Ties keep contributors-list
+    /// order; anonymous backers and zero balances are left out.
+    pub fn top_contributors(env: Env, n: u32) -> Vec<(Address, i128)> {
+        let n = n.min(MAX_LEADERBOARD_SIZE);
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        // Insert each contributor into a bounded, descending top list; the
+        // strict comparison keeps earlier contributors ahead on ties.
+        let mut top: Vec<(Address, i128)> = Vec::new(&env);
+        if n == 0 {
+            return top;
+        }
+        for contributor in contributors.iter() {
+            let amount: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Contribution(contributor.clone()))
+                .unwrap_or(0);
+            if amount <= 0 {
+                continue;
+            }
+
+            let mut position = top.len();
+            while position > 0 && top.get(position - 1).unwrap().1 < amount {
+                position -= 1;
+            }
+            if position < n {
+                top.insert(position, (contributor, amount));
+                if top.len() > n {
+                    top.pop_back();
+                }
+            }
+        }
+        top
+    }
+
+    /// Returns the number of unique contributors.
+    pub fn contributor_count(env: Env) -> u32 {
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+        contributors.len()
+    }
+}
+
+fn address_is_verified(env: &Env, contributor: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Verified(contributor.clone()))
+        .unwrap_or(false)
+}
+
+fn address_is_blocked(env: &Env, address: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Blocked(address.clone()))
+        .unwrap_or(false)
+}
+
+fn min_contributors_met(env: &Env) -> bool {
+    let min_contributors: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::MinContributors)
+        .unwrap_or(0);
+    if min_contributors == 0 {
+        return true;
+    }
+
+    let contributors: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Contributors)
+        .unwrap_or_else(|| Vec::new(env));
+    let anonymous_count: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::AnonymousCount)
+        .unwrap_or(0);
+    contributors.len() + anonymous_count >= min_contributors
+}
+
+/// Update the verified total when a verified backer takes back `amount`.
+fn release_verified_stake(env: &Env, contributor: &Address, amount: i128) {
+    if !address_is_verified(env, contributor) {
+        return;
+    }
+    let verified_raised: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::VerifiedRaised)
+        .unwrap_or(0);
+    env.storage().instance().set(
+        &DataKey::VerifiedRaised,
+        &(verified_raised - amount.min(verified_raised)),
+    );
+}
+
+/// Update anonymous totals when an anonymous backer takes back `amount`,
+/// leaving `remaining` contributed.
+fn release_anonymous_stake(env: &Env, contributor: &Address, amount: i128, remaining: i128) {
+    if !env
+        .storage()
+        .persistent()
+        .has(&DataKey::Anonymous(contributor.clone()))
+    {
+        return;
+    }
+
+    let anonymous_total: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::AnonymousTotal)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::AnonymousTotal, &(anonymous_total - amount));
+
+    if remaining == 0 {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AnonymousCount)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::AnonymousCount, &count.saturating_sub(1));
+    }
+}
+
+fn quality_goal_met(env: &Env) -> bool {
+    match env
+        .storage()
+        .instance()
+        .get::<_, i128>(&DataKey::QualityGoal)
+    {
+        Some(quality_goal) => {
+            let verified_raised: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::VerifiedRaised)
+                .unwrap_or(0);
+            verified_raised >= quality_goal
+        }
+        None => true,
+    }
+}
+
+/// Write a new campaign status, emit `status_changed` and notify the
+/// factory, if one is recorded.
+///
+/// Every transition after `initialize` goes through here, so indexers can
+/// follow the status from events alone. The factory callback is
+/// best-effort: a failing or missing factory must never block a campaign
+/// from settling.
+fn set_status(env: &Env, new_status: Status) {
+    let old_status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+    env.storage().instance().set(&DataKey::Status, &new_status);
+    env.events().publish(
+        ("campaign", "status_changed"),
+        (old_status.clone(), new_status.clone()),
+    );
+
+    if let Some(factory) = env
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::Factory)
+    {
+        let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            &factory,
+            &Symbol::new(env, "report_status"),
+            soroban_sdk::vec![
+                env,
+                env.current_contract_address().into_val(env),
+                old_status.into_val(env),
+                new_status.into_val(env),
+            ],
+        );
+    }
+}
+
+/// Report a change in contributions (negative for refunds) to the factory
+/// that deployed this campaign, if any.
+///
+/// Best-effort like `set_status`: a failing factory never blocks backers.
+fn report_contribution(env: &Env, amount: i128) {
+    if let Some(factory) = env
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::Factory)
+    {
+        let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            &factory,
+            &Symbol::new(env, "report_contribution"),
+            soroban_sdk::vec![
+                env,
+                env.current_contract_address().into_val(env),
+                amount.into_val(env),
+            ],
+        );
+    }
+}
+
+/// Run every `withdraw` precondition and return the total to pay out.
+fn check_withdrawable(env: &Env) -> Result<i128, ContractError> {
+    let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+    if status == Status::Paused {
+        return Err(ContractError::ContractPaused);
+    }
+    if status != Status::Active && !is_finalized(env, &status) {
+        return Err(ContractError::CampaignNotActive);
+    }
+
+    // Vested campaigns pay out through `withdraw_vested` instead.
+    if env.storage().instance().has(&DataKey::Vesting) {
+        return Err(ContractError::VestingConfigured);
+    }
+    // Roadmap-gated campaigns pay out through `withdraw_milestone` instead.
+    if is_roadmap_gated(env) {
+        return Err(ContractError::RoadmapGated);
+    }
+
+    require_outcome(
+        env,
+        &status,
+        Status::Successful,
+        ContractError::GoalNotReached,
+    )?;
+
+    let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+    if is_donation_mode(env) && total <= 0 {
+        return Err(ContractError::NothingToWithdraw);
+    }
+    Ok(total)
+}
+
+/// Pair each address with its stored contribution, 0 when none is stored.
+fn with_contributions(env: &Env, addresses: Vec<Address>) -> Vec<(Address, i128)> {
+    let mut ledger = Vec::new(env);
+    for address in addresses.iter() {
+        let amount: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(address.clone()))
+            .unwrap_or(0);
+        ledger.push_back((address, amount));
+    }
+    ledger
+}
+
+fn is_roadmap_gated(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::RoadmapGated)
+        .unwrap_or(false)
+}
+
+fn roadmap_item_done(env: &Env, index: u32) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RoadmapDone(index))
+        .unwrap_or(false)
+}
+
+fn set_roadmap_item_done(env: &Env, index: u32, done: bool) {
+    let done_key = DataKey::RoadmapDone(index);
+    if done {
+        env.storage().persistent().set(&done_key, &true);
+        env.storage().persistent().extend_ttl(&done_key, 100, 100);
+    } else {
+        env.storage().persistent().remove(&done_key);
+    }
+}
+
+/// Seconds an address must wait between contributions.
+fn contribution_cooldown(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ContributionCooldown)
+        .unwrap_or(CONTRIBUTION_COOLDOWN)
+}
+
+/// Whether `address` has flagged the campaign or voted on a proposal that is
+/// still open. Its stake then cannot move, so the same funds cannot be
+/// counted twice from another address.
+fn stake_locked(env: &Env, address: &Address) -> bool {
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::Flags(address.clone()))
+    {
+        return true;
+    }
+    let proposal_count: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ProposalCount)
+        .unwrap_or(0);
+    (0..proposal_count).any(|id| {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Vote(id, address.clone()))
+            && env
+                .storage()
+                .persistent()
+                .get::<_, RoadmapProposal>(&DataKey::Proposal(id))
+                .is_some_and(|proposal| !proposal.resolved)
+    })
+}
+
+/// Whether any roadmap change proposal is still unresolved.
+fn has_open_proposal(env: &Env) -> bool {
+    let proposal_count: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ProposalCount)
+        .unwrap_or(0);
+    (0..proposal_count).any(|id| {
+        env.storage()
+            .persistent()
+            .get::<_, RoadmapProposal>(&DataKey::Proposal(id))
+            .is_some_and(|proposal| !proposal.resolved)
+    })
+}
+
+/// Whether each address may contribute only once.
+fn is_single_contribution(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::SingleContribution)
+        .unwrap_or(false)
+}
+
+/// Whether contributions are parked as pending until the creator accepts them.
+fn is_escrow_mode(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::Escrow)
+        .unwrap_or(false)
+}
+
+/// Remove and return `contributor`'s pending escrow contribution.
+fn take_pending_contribution(env: &Env, contributor: &Address) -> Result<i128, ContractError> {
+    let pending_key = DataKey::Pending(contributor.clone());
+    let amount: i128 = env.storage().persistent().get(&pending_key).unwrap_or(0);
+    if amount <= 0 {
+        return Err(ContractError::NoPendingContribution);
+    }
+
+    env.storage().persistent().remove(&pending_key);
+    let pending_total: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::PendingTotal)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::PendingTotal, &(pending_total - amount));
+    Ok(amount)
+}
+
+/// Reject reducing or transferring a contribution within the churn cooldown
+/// of the address's last contribution.
+fn check_churn_cooldown(env: &Env, contributor: &Address) -> Result<(), ContractError> {
+    let cooldown: u64 = match env.storage().instance().get(&DataKey::ChurnCooldown) {
+        Some(cooldown) => cooldown,
+        None => return Ok(()),
+    };
+    let last_time: Option<u64> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::LastContributionTime(contributor.clone()));
+    match last_time {
+        Some(last_time) if env.ledger().timestamp() < last_time.saturating_add(cooldown) => {
+            Err(ContractError::CooldownActive)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Append a change to `contributor`'s stake to their log, negative when the
+/// stake shrinks, dropping the oldest entry once the log holds
+/// `MAX_CONTRIBUTION_LOG`.
+fn record_contribution_entry(env: &Env, contributor: &Address, amount: i128) {
+    let log_key = DataKey::ContributionLog(contributor.clone());
+    let mut log: Vec<ContributionEntry> = env
+        .storage()
+        .persistent()
+        .get(&log_key)
+        .unwrap_or_else(|| Vec::new(env));
+    if log.len() >= MAX_CONTRIBUTION_LOG {
+        log.pop_front();
+    }
+    log.push_back(ContributionEntry {
+        timestamp: env.ledger().timestamp(),
+        amount,
+    });
+    env.storage().persistent().set(&log_key, &log);
+    env.storage().persistent().extend_ttl(&log_key, 100, 100);
+}
+
+/// Remember that `address` holds per-address state, so `reset` clears it
+/// even when the address never joined the contributors list.
+fn track_participant(env: &Env, address: &Address) {
+    let mut participants: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Participants)
+        .unwrap_or_else(|| Vec::new(env));
+    if participants.contains(address) {
+        return;
+    }
+    participants.push_back(address.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::Participants, &participants);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::Participants, 100, 100);
+}
+
+/// Record the rate-limit timestamp and any period usage for a contribution.
+fn record_contribution_time(
+    env: &Env,
+    contributor: &Address,
+    now: u64,
+    period_usage: Option<PeriodUsage>,
+) {
+    if let Some(usage) = period_usage {
+        let usage_key = DataKey::PeriodUsage(contributor.clone());
+        env.storage().persistent().set(&usage_key, &usage);
+        env.storage().persistent().extend_ttl(&usage_key, 100, 100);
+    }
+
+    // Update last contribution time for rate limiting
+    let last_time_key = DataKey::LastContributionTime(contributor.clone());
+    env.storage().persistent().set(&last_time_key, &now);
+    env.storage()
+        .persistent()
+        .extend_ttl(&last_time_key, 100, 100);
+}
+
+/// Whether the campaign is an open-ended donation drive (`goal == 0`).
+fn is_donation_mode(env: &Env) -> bool {
+    let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+    goal == 0 && !env.storage().instance().has(&DataKey::ReferenceGoal)
+}
+
+/// Check every success condition other than the deadline.
+///
+/// With a reference goal the target is converted at the oracle's current
+/// price, so a stale or invalid price fails the check.
+fn check_success(env: &Env, total: i128) -> Result<(), ContractError> {
+    if !goal_amount_reached(env, total)? {
+        return Err(ContractError::GoalNotReached);
+    }
+    if !quality_goal_met(env) {
+        return Err(ContractError::QualityGoalNotReached);
+    }
+    if !min_contributors_met(env) {
+        return Err(ContractError::NotEnoughContributors);
+    }
+    Ok(())
+}
+
+/// Whether `total` meets the funding goal, converting a reference goal at
+/// the oracle's current price.
+///
+/// The single goal comparison behind `check_success`, and therefore behind
+/// both `withdraw` and `refund_single`.
+fn goal_amount_reached(env: &Env, total: i128) -> Result<bool, ContractError> {
+    let goal: i128 = if env.storage().instance().has(&DataKey::ReferenceGoal) {
+        goal_in_token(env)?
+    } else {
+        env.storage().instance().get(&DataKey::Goal).unwrap()
+    };
+    Ok(total >= goal)
+}
+
+/// Convert the reference goal into token base units at the oracle's price.
+fn goal_in_token(env: &Env) -> Result<i128, ContractError> {
+    if let Some(goal) = env.storage().instance().get(&DataKey::PinnedGoal) {
+        return Ok(goal);
+    }
+    let reference_goal: ReferenceGoal = env
+        .storage()
+        .instance()
+        .get(&DataKey::ReferenceGoal)
+        .ok_or(ContractError::NoReferenceGoal)?;
+    let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+    let quote = PriceOracleClient::new(env, &reference_goal.oracle).price(&token_address);
+
+    if quote.price <= 0 {
+        return Err(ContractError::InvalidPrice);
+    }
+    if quote.timestamp.saturating_add(MAX_PRICE_AGE) < env.ledger().timestamp() {
+        return Err(ContractError::StalePrice);
+    }
+
+    let decimals: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::TokenDecimals)
+        .unwrap();
+    reference_goal
+        .amount
+        .checked_mul(decimal_scale(decimals)?)
+        .map(|scaled| scaled / quote.price)
+        .ok_or(ContractError::Overflow)
+}
+
+/// Base units per whole token for a token with `decimals` decimals.
+fn decimal_scale(decimals: u32) -> Result<i128, ContractError> {
+    10i128.checked_pow(decimals).ok_or(ContractError::Overflow)
+}
+
+/// Platform fee owed on `amount`, or 0 without a platform config.
+///
+/// Refunded and cancelled campaigns never owe the withdrawal fee, so no
+/// refund path can charge backers one even if it shares a payout helper.
+fn platform_fee(env: &Env, amount: i128) -> i128 {
+    let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+    if status == Status::Refunded || status == Status::Cancelled {
+        return 0;
+    }
+
+    let platform_config: Option<PlatformConfig> =
+        env.storage().instance().get(&DataKey::PlatformConfig);
+
+    match platform_config {
+        Some(config) => amount
+            .checked_mul(config.fee_bps as i128)
+            .expect("fee calculation overflow")
+            .checked_div(10_000)
+            .expect("fee division by zero"),
+        None => 0,
+    }
+}
+
+/// Entry fee charged on a contribution of `amount`, or 0 when not configured.
+fn entry_fee(env: &Env, amount: i128) -> i128 {
+    let platform_config: Option<PlatformConfig> =
+        env.storage().instance().get(&DataKey::PlatformConfig);
+
+    match platform_config {
+        Some(config) => amount
+            .checked_mul(config.entry_fee_bps as i128)
+            .expect("fee calculation overflow")
+            .checked_div(10_000)
+            .expect("fee division by zero"),
+        None => 0,
+    }
+}
+
+/// Largest gross contribution whose net amount after the entry fee is at
+/// most `net`.
+fn gross_for_net(env: &Env, net: i128) -> i128 {
+    let entry_fee_bps = env
+        .storage()
+        .instance()
+        .get::<_, PlatformConfig>(&DataKey::PlatformConfig)
+        .map(|config| config.entry_fee_bps as i128)
+        .unwrap_or(0);
+    if net <= 0 || entry_fee_bps == 0 || entry_fee_bps >= 10_000 {
+        return net;
+    }
+
+    // `net * 10_000 / (10_000 - bps)`, split to stay in range. The fee
+    // rounds down, so this never overshoots; step up past any gross amounts
+    // whose rounded fee still leaves the net in range.
+    let divisor = 10_000 - entry_fee_bps;
+    let mut gross = net / divisor * 10_000 + net % divisor * 10_000 / divisor;
+    while gross < i128::MAX && gross + 1 - entry_fee(env, gross + 1) <= net {
+        gross += 1;
+    }
+    gross
+}
+
+/// Send `fee` to the platform address, or divide it between the fee
+/// recipients, and add it to `FeesCollected`.
+fn transfer_platform_fee(env: &Env, token_client: &token::Client, fee: i128) {
+    let config: PlatformConfig = match env.storage().instance().get(&DataKey::PlatformConfig) {
+        Some(config) => config,
+        None => return,
+    };
+
+    match config.fee_recipients {
+        Some(recipients) => {
+            let mut remaining = fee;
+            for (index, (recipient, bps)) in recipients.iter().enumerate() {
+                let share = if index as u32 == recipients.len() - 1 {
+                    remaining
+                } else {
+                    fee * bps as i128 / 10_000
+                };
+                remaining -= share;
+
+                token_client.transfer(&env.current_contract_address(), &recipient, &share);
+                env.events()
+                    .publish(("campaign", "fee_transferred"), (recipient, share));
+            }
+        }
+        None => {
+            token_client.transfer(&env.current_contract_address(), &config.address, &fee);
+            env.events()
+                .publish(("campaign", "fee_transferred"), (&config.address, fee));
+        }
+    }
+
+    let collected: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::FeesCollected)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::FeesCollected, &(collected + fee));
+}
+
+/// Transfer the platform fee on `amount`, if configured, and return the net
+/// amount left for the creator.
+fn pay_platform_fee(env: &Env, token_client: &token::Client, amount: i128) -> i128 {
+    let fee = platform_fee(env, amount);
+    transfer_platform_fee(env, token_client, fee);
+    amount.checked_sub(fee).expect("creator payout underflow")
+}
+
+/// Refund every listed contributor's stake and return the total refunded.
+///
+/// The caller adjusts `TotalRaised`.
+fn refund_listed_contributors(env: &Env) -> i128 {
+    let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+    let token_client = token::Client::new(env, &token_address);
+
+    let contributors: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Contributors)
+        .unwrap_or_else(|| Vec::new(env));
+
+    let mut refunded = 0i128;
+    for contributor in contributors.iter() {
+        let contribution_key = DataKey::Contribution(contributor.clone());
+        let amount: i128 = env
+            .storage()
+            .persistent()
+            .get(&contribution_key)
+            .unwrap_or(0);
+        if amount > 0 {
+            env.storage().persistent().set(&contribution_key, &0i128);
+            env.storage()
+                .persistent()
+                .extend_ttl(&contribution_key, 100, 100);
+            record_contribution_entry(env, &contributor, -amount);
+            release_verified_stake(env, &contributor, amount);
+            token_client.transfer(&env.current_contract_address(), &contributor, &amount);
+            env.events()
+                .publish(("campaign", "refunded"), (contributor.clone(), amount));
+            refunded += amount;
+        }
+    }
+    refunded
+}
+
+/// Return every pending escrow contribution to its backer and return the
+/// total. Escrow backers are tracked as participants when they contribute.
+fn refund_pending_contributions(env: &Env) -> i128 {
+    let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+    let token_client = token::Client::new(env, &token_address);
+
+    let participants: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Participants)
+        .unwrap_or_else(|| Vec::new(env));
+
+    let mut returned = 0i128;
+    for participant in participants.iter() {
+        if let Ok(amount) = take_pending_contribution(env, &participant) {
+            token_client.transfer(&env.current_contract_address(), &participant, &amount);
+            env.events().publish(
+                ("campaign", "pending_returned"),
+                (participant.clone(), amount),
+            );
+            returned += amount;
+        }
+    }
+    returned
+}
+
+/// Return `contributor`'s pending escrow contribution, if any, and return
+/// the amount.
+fn return_pending(
+    env: &Env,
+    token_client: &token::Client,
+    contributor: &Address,
+) -> Result<i128, ContractError> {
+    let amount = match take_pending_contribution(env, contributor) {
+        Ok(amount) => amount,
+        Err(_) => return Ok(0),
+    };
+    transfer_or_fail(
+        env,
+        token_client,
+        &env.current_contract_address(),
+        contributor,
+        amount,
+        "refund",
+    )?;
+    env.events().publish(
+        ("campaign", "pending_returned"),
+        (contributor.clone(), amount),
+    );
+    Ok(amount)
+}
+
+/// Whether `refund_single` currently accepts claims: after a creator cancel,
+/// or once `finalize` has marked the campaign `Refunded`.
+fn refunds_open(env: &Env) -> bool {
+    let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+    is_creator_cancelled(env, &status) || is_finalized_as(env, &status, Status::Refunded)
+}
+
+/// Zero every contribution still held, listed or anonymous, and return their
+/// sum.
+fn take_unclaimed_contributions(env: &Env) -> Result<i128, ContractError> {
+    let mut backers: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Contributors)
+        .unwrap_or_else(|| Vec::new(env));
+    let participants: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Participants)
+        .unwrap_or_else(|| Vec::new(env));
+    for participant in participants.iter() {
+        if !backers.contains(&participant) {
+            backers.push_back(participant);
+        }
+    }
+
+    let mut unclaimed: i128 = 0;
+    for backer in backers.iter() {
+        let contribution_key = DataKey::Contribution(backer.clone());
+        let amount: i128 = env
+            .storage()
+            .persistent()
+            .get(&contribution_key)
+            .unwrap_or(0);
+        if amount > 0 {
+            env.storage().persistent().set(&contribution_key, &0i128);
+            env.storage()
+                .persistent()
+                .extend_ttl(&contribution_key, 100, 100);
+            record_contribution_entry(env, &backer, -amount);
+            unclaimed = unclaimed
+                .checked_add(amount)
+                .ok_or(ContractError::Overflow)?;
+        }
+    }
+    Ok(unclaimed)
+}
+
+/// Whether the campaign was cancelled through `cancel`, as opposed to having
+/// its unclaimed refunds swept.
+fn is_creator_cancelled(env: &Env, status: &Status) -> bool {
+    *status == Status::Cancelled && env.storage().instance().has(&DataKey::CancelReason)
+}
+
+/// Whether `finalize` resolved the campaign to `outcome`, and for a
+/// successful outcome the creator has not yet withdrawn.
+fn is_finalized_as(env: &Env, status: &Status, outcome: Status) -> bool {
+    *status == outcome && is_finalized(env, status)
+}
+
+/// Whether `finalize` has fixed the outcome and nothing has settled it since.
+fn is_finalized(env: &Env, status: &Status) -> bool {
+    *status != Status::Active && env.storage().instance().has(&DataKey::Finalized)
+}
+
+/// Require `finalize` to have marked the campaign `outcome`.
+///
+/// Before the deadline this is `CampaignStillActive`, and past it, until
+/// `finalize` runs, `CampaignNotSettled`. The opposite outcome is `wrong`.
+fn require_outcome(
+    env: &Env,
+    status: &Status,
+    outcome: Status,
+    wrong: ContractError,
+) -> Result<(), ContractError> {
+    if *status == Status::Active {
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        if env.ledger().timestamp() <= deadline {
+            return Err(ContractError::CampaignStillActive);
+        }
+        return Err(ContractError::CampaignNotSettled);
+    }
+    if !is_finalized_as(env, status, outcome) {
+        return Err(wrong);
+    }
+    Ok(())
+}
+
+/// Send `amount` to the creator, or divide it between the payout splits.
+///
+/// The last recipient receives any rounding remainder.
+fn pay_creator(
+    env: &Env,
+    token_client: &token::Client,
+    creator: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    let splits: Vec<(Address, u32)> = match env.storage().instance().get(&DataKey::PayoutSplits) {
+        Some(splits) => splits,
+        None => {
+            return transfer_or_fail(
+                env,
+                token_client,
+                &env.current_contract_address(),
+                creator,
+                amount,
+                "payout",
+            );
+        }
+    };
+
+    let mut remaining = amount;
+    for (index, (recipient, bps)) in splits.iter().enumerate() {
+        let share = if index as u32 == splits.len() - 1 {
+            remaining
+        } else {
+            amount * bps as i128 / 10_000
+        };
+        remaining -= share;
+
+        transfer_or_fail(
+            env,
+            token_client,
+            &env.current_contract_address(),
+            &recipient,
+            share,
+            "payout",
+        )?;
+        env.events()
+            .publish(("campaign", "split_paid"), (recipient, share));
+    }
+    Ok(())
+}
+
+/// Append a creator payout of `amount` to the withdrawal history.
+fn record_withdrawal(env: &Env, amount: i128) {
+    let mut history: Vec<Withdrawal> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::WithdrawalHistory)
+        .unwrap_or_else(|| Vec::new(env));
+    history.push_back(Withdrawal {
+        timestamp: env.ledger().timestamp(),
+        amount,
+    });
+    env.storage()
+        .persistent()
+        .set(&DataKey::WithdrawalHistory, &history);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::WithdrawalHistory, 100, 100);
+}
+
+/// Append `version` to the version history unless it is already the latest
+/// entry, as when a reset campaign is initialized again.
+fn record_version(env: &Env, version: u32) {
+    let mut history: Vec<VersionRecord> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::VersionHistory)
+        .unwrap_or_else(|| Vec::new(env));
+    if history
+        .last()
+        .is_some_and(|record| record.version == version)
+    {
+        return;
+    }
+    history.push_back(VersionRecord {
+        version,
+        timestamp: env.ledger().timestamp(),
+    });
+    env.storage()
+        .persistent()
+        .set(&DataKey::VersionHistory, &history);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::VersionHistory, 100, 100);
+}
+
+/// Send any held creator bond to `to` and announce it as `event`.
+fn release_bond(
+    env: &Env,
+    token_client: &token::Client,
+    to: &Address,
+    event: &str,
+) -> Result<(), ContractError> {
+    let bond: i128 = env.storage().instance().get(&DataKey::Bond).unwrap_or(0);
+    if bond <= 0 {
+        return Ok(());
+    }
 
-        let creator_payout = if let Some(config) = platform_config {
-            let fee = total
-                .checked_mul(config.fee_bps as i128)
-                .expect("fee calculation overflow")
-                .checked_div(10_000)
-                .expect("fee division by zero");
+    env.storage().instance().remove(&DataKey::Bond);
+    transfer_or_fail(
+        env,
+        token_client,
+        &env.current_contract_address(),
+        to,
+        bond,
+        "bond",
+    )?;
+    env.events()
+        .publish(("campaign", Symbol::new(env, event)), (to.clone(), bond));
+    Ok(())
+}
 
-            token_client.transfer(&env.current_contract_address(), &config.address, &fee);
-            env.events()
-                .publish(("campaign", "fee_transferred"), (&config.address, fee));
-            total.checked_sub(fee).expect("creator payout underflow")
-        } else {
-            total
-        };
+/// Transfer `amount` through the token's fallible entrypoint so a rejected
+/// transfer surfaces as `TransferFailed` rather than an opaque host error.
+///
+/// `direction` names the flow in the `transfer_failed` event.
+fn transfer_or_fail(
+    env: &Env,
+    token_client: &token::Client,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+    direction: &str,
+) -> Result<(), ContractError> {
+    match token_client.try_transfer(from, to, &amount) {
+        Ok(Ok(())) => Ok(()),
+        _ => {
+            env.events().publish(
+                ("campaign", "transfer_failed"),
+                (Symbol::new(env, direction), amount),
+            );
+            Err(ContractError::TransferFailed)
+        }
+    }
+}
 
-        token_client.transfer(&env.current_contract_address(), &creator, &creator_payout);
+/// Mint one commemorative NFT per contributor whose stake meets the NFT
+/// minimum contribution (any non-zero stake when unset), plus a bonus NFT for
+/// qualifying early backers.
+///
+/// Stops after `NftBatchLimit` contributors, if set; the rest mint their own
+/// through `claim_nft`.
+fn mint_contributor_nfts(env: &Env) {
+    if let Some(nft_contract) = env
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::NFTContract)
+    {
+        let nft_client = NftContractClient::new(env, &nft_contract);
+        let min_amount = nft_min_amount(env);
+        let batch_limit: Option<u32> = env.storage().instance().get(&DataKey::NftBatchLimit);
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(env));
 
-        env.storage().instance().set(&DataKey::TotalRaised, &0i128);
-        env.storage()
-            .instance()
-            .set(&DataKey::Status, &Status::Successful);
+        let mut minted = 0u32;
+        for (position, contributor) in contributors.iter().enumerate() {
+            if batch_limit.is_some_and(|limit| minted >= limit) {
+                break;
+            }
 
-        // Mint one commemorative NFT per eligible contributor after successful payout.
-        if let Some(nft_contract) = env
-            .storage()
-            .instance()
-            .get::<_, Address>(&DataKey::NFTContract)
-        {
-            let nft_client = NftContractClient::new(&env, &nft_contract);
-            let contributors: Vec<Address> = env
+            let amount: i128 = env
                 .storage()
                 .persistent()
-                .get(&DataKey::Contributors)
-                .unwrap_or_else(|| Vec::new(&env));
+                .get(&DataKey::Contribution(contributor.clone()))
+                .unwrap_or(0);
 
-            for contributor in contributors.iter() {
-                let amount: i128 = env
+            // Only mint for contributors whose stake meets the threshold and
+            // who have not already claimed.
+            if amount <= 0
+                || amount < min_amount
+                || env
                     .storage()
                     .persistent()
-                    .get(&DataKey::Contribution(contributor.clone()))
-                    .unwrap_or(0);
-
-                // Only mint for contributors with a non-zero stake.
-                if amount > 0 {
-                    let token_id = nft_client.mint(&contributor);
-                    env.events().publish(
-                        (
-                            Symbol::new(&env, "campaign"),
-                            Symbol::new(&env, "nft_minted"),
-                        ),
-                        (contributor, token_id),
-                    );
-                }
+                    .has(&DataKey::NftClaimed(contributor.clone()))
+            {
+                continue;
             }
-        }
-
-        env.events()
-            .publish(("campaign", "withdrawn"), (creator.clone(), total));
-
-        Ok(())
-    }
 
-    pub fn refund_single(env: Env, contributor: Address) -> Result<(), ContractError> {
-        contributor.require_auth();
-
-        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
-        if status != Status::Active {
-            panic!("campaign is not active");
+            mint_contributor_nft(env, &nft_client, &contributor, position as u32, amount);
+            minted += 1;
         }
+    }
+}
 
-        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
-        if env.ledger().timestamp() <= deadline {
-            return Err(ContractError::CampaignStillActive);
-        }
+/// The smallest stake that earns an NFT; any non-zero stake when unset.
+fn nft_min_amount(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::NFTMinContribution)
+        .unwrap_or(1)
+}
 
-        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
-        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
-        if total >= goal {
-            return Err(ContractError::GoalReached);
-        }
+/// Mint `contributor`'s NFT, tiered by `amount` when tiers are set, plus the
+/// early-backer bonus for their `position` on the contributors list, and
+/// mark it claimed.
+fn mint_contributor_nft(
+    env: &Env,
+    nft_client: &NftContractClient,
+    contributor: &Address,
+    position: u32,
+    amount: i128,
+) {
+    let tiers: Vec<i128> = env
+        .storage()
+        .instance()
+        .get(&DataKey::NFTTiers)
+        .unwrap_or_else(|| Vec::new(env));
+    let early_backer_count: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::EarlyBackerCount)
+        .unwrap_or(0);
 
-        let contribution_key = DataKey::Contribution(contributor.clone());
-        let amount: i128 = env
-            .storage()
-            .persistent()
-            .get(&contribution_key)
-            .unwrap_or(0);
+    let claimed_key = DataKey::NftClaimed(contributor.clone());
+    env.storage().persistent().set(&claimed_key, &true);
+    env.storage()
+        .persistent()
+        .extend_ttl(&claimed_key, 100, 100);
 
-        if amount == 0 {
-            return Ok(());
-        }
+    if tiers.is_empty() {
+        let token_id = nft_client.mint(contributor);
+        env.events().publish(
+            (Symbol::new(env, "campaign"), Symbol::new(env, "nft_minted")),
+            (contributor.clone(), token_id),
+        );
+    } else {
+        let tier = tiers
+            .iter()
+            .filter(|threshold| amount >= *threshold)
+            .count() as u32;
+        let token_id = nft_client.mint_tier(contributor, &tier);
+        env.events().publish(
+            (Symbol::new(env, "campaign"), Symbol::new(env, "nft_minted")),
+            (contributor.clone(), token_id, tier),
+        );
+    }
 
-        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let token_client = token::Client::new(&env, &token_address);
-        token_client.transfer(&env.current_contract_address(), &contributor, &amount);
+    // Contributors keeps insertion order, so its head is the earliest.
+    if position < early_backer_count {
+        let token_id = nft_client.mint(contributor);
+        env.events().publish(
+            (
+                Symbol::new(env, "campaign"),
+                Symbol::new(env, "early_bonus_minted"),
+            ),
+            (contributor.clone(), token_id),
+        );
+    }
+}
 
-        env.storage().persistent().set(&contribution_key, &0i128);
-        env.storage()
-            .persistent()
-            .extend_ttl(&contribution_key, 100, 100);
+/// Amount vested so far: one tranche unlocks at the deadline and another
+/// after each further `interval`, until all `periods` have vested.
+fn vested_amount(env: &Env) -> i128 {
+    let vesting: Vesting = match env.storage().instance().get(&DataKey::Vesting) {
+        Some(vesting) => vesting,
+        None => return 0,
+    };
 
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalRaised, &(total - amount));
+    let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+    let now = env.ledger().timestamp();
+    if now <= deadline {
+        return 0;
+    }
 
-        if total - amount == 0 {
-            env.storage()
+    let total: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::VestingTotal)
+        .unwrap_or_else(|| {
+            let total: i128 = env
+                .storage()
                 .instance()
-                .set(&DataKey::Status, &Status::Refunded);
-        }
+                .get(&DataKey::TotalRaised)
+                .unwrap_or(0);
+            total - overflow_amount(env, total)
+        });
 
-        Ok(())
+    let elapsed_periods = ((now - deadline) / vesting.interval + 1).min(vesting.periods as u64);
+    if elapsed_periods == vesting.periods as u64 {
+        total
+    } else {
+        total * elapsed_periods as i128 / vesting.periods as i128
     }
+}
 
-    pub fn add_roadmap_item(env: Env, date: u64, description: String) {
-        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
-        creator.require_auth();
-
-        if date <= env.ledger().timestamp() {
-            panic!("date must be in the future");
-        }
+/// Integer square root, rounded down (Newton's method).
+fn isqrt(n: i128) -> i128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    // `(x + 1) / 2` without overflowing at `i128::MAX`.
+    let mut y = x / 2 + x % 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
 
-        if description.is_empty() {
-            panic!("description cannot be empty");
-        }
+fn contract_token_balance(env: &Env) -> i128 {
+    let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+    token::Client::new(env, &token_address).balance(&env.current_contract_address())
+}
 
-        let mut roadmap: Vec<RoadmapItem> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Roadmap)
-            .unwrap_or_else(|| Vec::new(&env));
+fn contribution_deadline(env: &Env) -> u64 {
+    let deadline: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ContributionDeadline)
+        .unwrap_or_else(|| env.storage().instance().get(&DataKey::Deadline).unwrap());
 
-        roadmap.push_back(RoadmapItem {
-            date,
-            description: description.clone(),
-        });
+    let window: Option<u64> = env.storage().instance().get(&DataKey::PostGoalWindow);
+    let reached_at: Option<u64> = env.storage().instance().get(&DataKey::GoalReachedTime);
+    match (window, reached_at) {
+        (Some(window), Some(reached_at)) => deadline.min(reached_at.saturating_add(window)),
+        _ => deadline,
+    }
+}
 
-        env.storage().instance().set(&DataKey::Roadmap, &roadmap);
-        env.events()
-            .publish(("campaign", "roadmap_item_added"), (date, description));
+/// Start the post-goal window the first time the total reaches the goal.
+///
+/// Later dips below the goal, e.g. from `reduce_contribution`, leave the
+/// window as it is.
+fn record_goal_reached(env: &Env) {
+    if !env.storage().instance().has(&DataKey::PostGoalWindow)
+        || env.storage().instance().has(&DataKey::GoalReachedTime)
+    {
+        return;
     }
 
-    pub fn roadmap(env: Env) -> Vec<RoadmapItem> {
+    let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+    if goal_amount_reached(env, total).unwrap_or(false) {
+        let now = env.ledger().timestamp();
         env.storage()
             .instance()
-            .get(&DataKey::Roadmap)
-            .unwrap_or_else(|| Vec::new(&env))
+            .set(&DataKey::GoalReachedTime, &now);
+        env.events()
+            .publish(("campaign", "post_goal_window_started"), now);
     }
+}
 
-    pub fn total_raised(env: Env) -> i128 {
+/// Emit a `milestone` event for each goal quartile the total has newly
+/// reached. Bit `i` of `MilestonesHit` marks `25 * (i + 1)` percent.
+fn emit_milestones(env: &Env) {
+    let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+    let total: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::TotalRaised)
+        .unwrap_or(0);
+    let hit: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::MilestonesHit)
+        .unwrap_or(0);
+
+    let mut updated = hit;
+    for bit in 0..4u32 {
+        let percent = 25 * (bit + 1);
+        // A total too large to scale is past every milestone.
+        let reached = total
+            .checked_mul(100)
+            .map_or(true, |scaled| scaled >= goal * percent as i128);
+        if updated & (1 << bit) == 0 && reached {
+            updated |= 1 << bit;
+            env.events().publish(("campaign", "milestone"), percent);
+        }
+    }
+    if updated != hit {
         env.storage()
             .instance()
-            .get(&DataKey::TotalRaised)
-            .unwrap_or(0)
+            .set(&DataKey::MilestonesHit, &updated);
     }
+}
 
-    pub fn goal(env: Env) -> i128 {
-        env.storage().instance().get(&DataKey::Goal).unwrap()
-    }
+/// How much more `total_raised` may grow before reaching the hard cap;
+/// unbounded for a donation campaign created without one.
+fn hard_cap_room(env: &Env) -> i128 {
+    let hard_cap: i128 = match env.storage().instance().get(&DataKey::HardCap) {
+        Some(hard_cap) => hard_cap,
+        None => return i128::MAX,
+    };
+    let total: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::TotalRaised)
+        .unwrap_or(0);
+    hard_cap.saturating_sub(total)
+}
 
-    pub fn deadline(env: Env) -> u64 {
-        env.storage().instance().get(&DataKey::Deadline).unwrap()
+/// Amount raised past the goal that `overflow_refund` holds back from a
+/// payout of `total`; 0 when the option is off or for donations, which have
+/// no goal to exceed.
+fn overflow_amount(env: &Env, total: i128) -> i128 {
+    let enabled: bool = env
+        .storage()
+        .instance()
+        .get(&DataKey::OverflowRefund)
+        .unwrap_or(false);
+    let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+    if enabled && !is_donation_mode(env) && total > goal {
+        total - goal
+    } else {
+        0
     }
+}
 
-    pub fn contribution(env: Env, contributor: Address) -> i128 {
+/// Set aside the overflow of `total` for `claim_overflow` and return it.
+///
+/// Shares are split over backer stakes, which exclude sponsor matches, so
+/// the matched part of the overflow is not left unclaimable.
+fn hold_overflow(env: &Env, total: i128) -> i128 {
+    let overflow = overflow_amount(env, total);
+    if overflow > 0 {
+        let matched = env
+            .storage()
+            .instance()
+            .get::<_, Matching>(&DataKey::Matching)
+            .map_or(0, |matching| matching.matched);
         env.storage()
-            .persistent()
-            .get(&DataKey::Contribution(contributor))
-            .unwrap_or(0)
-    }
-
-    pub fn min_contribution(env: Env) -> i128 {
+            .instance()
+            .set(&DataKey::OverflowPool, &overflow);
         env.storage()
             .instance()
-            .get(&DataKey::MinContribution)
-            .unwrap()
+            .set(&DataKey::OverflowBase, &(total - matched));
     }
+    overflow
+}
 
-    pub fn creator(env: Env) -> Address {
-        env.storage().instance().get(&DataKey::Creator).unwrap()
-    }
+/// Part of the sponsor's matching deposit not yet credited as matches.
+fn sponsor_balance(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::SponsorBalance)
+        .unwrap_or(0)
+}
 
-    pub fn nft_contract(env: Env) -> Option<Address> {
-        env.storage().instance().get(&DataKey::NFTContract)
+/// Move the sponsor match credited on `amount` of `contributor`'s `current`
+/// stake back into the matching deposit. Returns the amount unwound, which
+/// the caller takes out of `total_raised`.
+fn unwind_match_credit(env: &Env, contributor: &Address, amount: i128, current: i128) -> i128 {
+    let credit_key = DataKey::MatchCredit(contributor.clone());
+    let credit: i128 = env.storage().persistent().get(&credit_key).unwrap_or(0);
+    if credit <= 0 {
+        return 0;
     }
+    let mut matching: Matching = match env.storage().instance().get(&DataKey::Matching) {
+        Some(matching) => matching,
+        None => return 0,
+    };
 
-    pub fn get_campaign_info(env: Env) -> CampaignInfo {
-        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
-        let token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
-        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
-        let total_raised: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalRaised)
-            .unwrap_or(0);
+    // Integer division rounds down; withdrawing the whole stake unwinds the
+    // rest so no credit is left behind.
+    let unwound = if amount == current {
+        credit
+    } else {
+        credit * amount / current
+    }
+    .min(matching.matched);
+    env.storage()
+        .persistent()
+        .set(&credit_key, &(credit - unwound));
+    matching.matched -= unwound;
+    env.storage().instance().set(&DataKey::Matching, &matching);
+    env.storage()
+        .instance()
+        .set(&DataKey::SponsorBalance, &(sponsor_balance(env) + unwound));
+    env.events().publish(
+        ("campaign", "match_unwound"),
+        (matching.sponsor, contributor.clone(), unwound),
+    );
+    unwound
+}
 
-        CampaignInfo {
-            creator,
-            token,
-            goal,
-            deadline,
-            total_raised,
-        }
+/// Return the sponsor's unused matching deposit and, with `include_matched`
+/// (failed or cancelled campaigns), the matched share as well, which then
+/// leaves `total_raised`. Returns the matched amount removed.
+fn refund_sponsor(
+    env: &Env,
+    token_client: &token::Client,
+    include_matched: bool,
+) -> Result<i128, ContractError> {
+    let mut matching: Matching = match env.storage().instance().get(&DataKey::Matching) {
+        Some(matching) => matching,
+        None => return Ok(0),
+    };
+    let matched = if include_matched { matching.matched } else { 0 };
+    let amount = sponsor_balance(env)
+        .checked_add(matched)
+        .ok_or(ContractError::Overflow)?;
+    if amount <= 0 {
+        return Ok(0);
     }
 
-    pub fn get_stats(env: Env) -> CampaignStats {
-        let total_raised: i128 = env
-            .storage()
+    env.storage().instance().remove(&DataKey::SponsorBalance);
+    if matched > 0 {
+        matching.matched = 0;
+        env.storage().instance().set(&DataKey::Matching, &matching);
+        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+        env.storage()
             .instance()
-            .get(&DataKey::TotalRaised)
-            .unwrap_or(0);
-        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
-        let contributors: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Contributors)
-            .unwrap_or_else(|| Vec::new(&env));
+            .set(&DataKey::TotalRaised, &(total - matched));
+    }
 
-        let progress_bps = if goal > 0 {
-            let raw = (total_raised * 10_000) / goal;
-            if raw > 10_000 {
-                10_000
-            } else {
-                raw as u32
-            }
-        } else {
-            0
-        };
+    transfer_or_fail(
+        env,
+        token_client,
+        &env.current_contract_address(),
+        &matching.sponsor,
+        amount,
+        "refund",
+    )?;
+    env.events()
+        .publish(("campaign", "sponsor_refunded"), (matching.sponsor, amount));
+    Ok(matched)
+}
 
-        let contributor_count = contributors.len();
-        let (average_contribution, largest_contribution) = if contributor_count == 0 {
-            (0, 0)
-        } else {
-            let average = total_raised / contributor_count as i128;
-            let mut largest = 0i128;
-            for contributor in contributors.iter() {
-                let amount: i128 = env
-                    .storage()
-                    .persistent()
-                    .get(&DataKey::Contribution(contributor))
-                    .unwrap_or(0);
-                if amount > largest {
-                    largest = amount;
-                }
-            }
-            (average, largest)
-        };
+fn refund_policy(env: &Env) -> RefundPolicy {
+    env.storage()
+        .instance()
+        .get(&DataKey::RefundPolicy)
+        .unwrap_or_default()
+}
 
-        CampaignStats {
-            total_raised,
-            goal,
-            progress_bps,
-            contributor_count,
-            average_contribution,
-            largest_contribution,
+fn remaining_to_goal(env: &Env) -> i128 {
+    let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+    let total: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::TotalRaised)
+        .unwrap_or(0);
+    if total >= goal {
+        0
+    } else {
+        goal - total
+    }
+}
+
+fn clear_campaign_state(env: &Env) {
+    let proposal_count: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ProposalCount)
+        .unwrap_or(0);
+
+    // Participants include anonymous backers, referrers and addresses the
+    // admin verified or blocked; listed contributors are covered either way.
+    let mut addresses: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Contributors)
+        .unwrap_or_else(|| Vec::new(env));
+    let participants: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Participants)
+        .unwrap_or_else(|| Vec::new(env));
+    for participant in participants.iter() {
+        if !addresses.contains(&participant) {
+            addresses.push_back(participant);
         }
     }
+    for address in addresses.iter() {
+        clear_address_state(env, &address, proposal_count);
+    }
 
-    pub fn title(env: Env) -> String {
-        env.storage()
-            .instance()
-            .get(&DataKey::Title)
-            .unwrap_or_else(|| String::from_str(&env, ""))
+    for id in 0..proposal_count {
+        env.storage().persistent().remove(&DataKey::Proposal(id));
     }
 
-    pub fn description(env: Env) -> String {
-        env.storage()
-            .instance()
-            .get(&DataKey::Description)
-            .unwrap_or_else(|| String::from_str(&env, ""))
+    let update_count: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::UpdateCount)
+        .unwrap_or(0);
+    for id in 0..update_count {
+        env.storage().persistent().remove(&DataKey::Update(id));
     }
 
-    pub fn socials(env: Env) -> String {
+    let pledgers: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Pledgers)
+        .unwrap_or_else(|| Vec::new(env));
+    for pledger in pledgers.iter() {
+        env.storage().persistent().remove(&DataKey::Pledge(pledger));
+    }
+
+    let roadmap: Vec<RoadmapItem> = env
+        .storage()
+        .instance()
+        .get(&DataKey::Roadmap)
+        .unwrap_or_else(|| Vec::new(env));
+    for index in 0..roadmap.len() {
         env.storage()
-            .instance()
-            .get(&DataKey::SocialLinks)
-            .unwrap_or_else(|| String::from_str(&env, ""))
+            .persistent()
+            .remove(&DataKey::RoadmapDone(index));
     }
 
-    pub fn version(_env: Env) -> u32 {
-        CONTRACT_VERSION
+    let snapshot_count: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::SnapshotCount)
+        .unwrap_or(0);
+    for id in 0..snapshot_count {
+        env.storage().persistent().remove(&DataKey::Snapshot(id));
     }
 
-    /// Returns the token contract address used for contributions.
-    pub fn token(env: Env) -> Address {
-        env.storage().instance().get(&DataKey::Token).unwrap()
+    env.storage().persistent().remove(&DataKey::Contributors);
+    env.storage().persistent().remove(&DataKey::Participants);
+    env.storage().persistent().remove(&DataKey::Pledgers);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::WithdrawalHistory);
+
+    let instance_keys = [
+        DataKey::Creator,
+        DataKey::Token,
+        DataKey::Goal,
+        DataKey::Deadline,
+        DataKey::TotalRaised,
+        DataKey::Status,
+        DataKey::MinContribution,
+        DataKey::Roadmap,
+        DataKey::Admin,
+        DataKey::Title,
+        DataKey::Description,
+        DataKey::SocialLinks,
+        DataKey::PlatformConfig,
+        DataKey::RewardTiers,
+        DataKey::TotalPledged,
+        DataKey::StretchGoals,
+        DataKey::BonusGoal,
+        DataKey::BonusGoalDescription,
+        DataKey::BonusGoalReachedEmitted,
+        DataKey::HardCap,
+        DataKey::NFTContract,
+        DataKey::EarlyBackerCount,
+        DataKey::MilestonesHit,
+        DataKey::Bond,
+        DataKey::OriginalGoal,
+        DataKey::MaxContributors,
+        DataKey::RoadmapGated,
+        DataKey::MilestoneTotal,
+        DataKey::SnapshotCount,
+        DataKey::Escrow,
+        DataKey::PendingTotal,
+        DataKey::ContributionCooldown,
+        DataKey::StartTime,
+        DataKey::PostGoalWindow,
+        DataKey::GoalReachedTime,
+        DataKey::Finalized,
+        DataKey::ChurnCooldown,
+        DataKey::NftBatchLimit,
+        DataKey::RefundPolicy,
+        DataKey::SingleContribution,
+        DataKey::OverflowRefund,
+        DataKey::PinnedGoal,
+        DataKey::SponsorBalance,
+        DataKey::VerifiedRaised,
+        DataKey::QualityGoal,
+        DataKey::StrictGoal,
+        DataKey::PeriodLimit,
+        DataKey::Matching,
+        DataKey::Factory,
+        DataKey::PendingAdmin,
+        DataKey::MaxContribution,
+        DataKey::FlagCount,
+        DataKey::Vesting,
+        DataKey::VestingTotal,
+        DataKey::Withdrawn,
+        DataKey::NFTTiers,
+        DataKey::Category,
+        DataKey::FeesCollected,
+        DataKey::MinContributors,
+        DataKey::AnonymousCount,
+        DataKey::AnonymousTotal,
+        DataKey::FeeCollected,
+        DataKey::FeeDue,
+        DataKey::ContributionDeadline,
+        DataKey::OverflowPool,
+        DataKey::OverflowBase,
+        DataKey::UpdateCount,
+        DataKey::Tips,
+        DataKey::NFTMinContribution,
+        DataKey::ProposalCount,
+        DataKey::ClaimWindow,
+        DataKey::TokenDecimals,
+        DataKey::ReferenceGoal,
+        DataKey::PayoutSplits,
+        DataKey::CancelReason,
+        DataKey::FixedLevels,
+        DataKey::Initialized,
+    ];
+    for key in instance_keys.iter() {
+        env.storage().instance().remove(key);
     }
+}
 
-    /// Returns the number of unique contributors.
-    pub fn contributor_count(env: Env) -> u32 {
-        let contributors: Vec<Address> = env
-            .storage()
+/// Remove every per-address entry `address` may hold, including its votes
+/// on the first `proposal_count` proposals.
+fn clear_address_state(env: &Env, address: &Address, proposal_count: u32) {
+    let keys = [
+        DataKey::Contribution(address.clone()),
+        DataKey::LastContributionTime(address.clone()),
+        DataKey::PeriodUsage(address.clone()),
+        DataKey::Flags(address.clone()),
+        DataKey::Memo(address.clone()),
+        DataKey::OverflowClaimed(address.clone()),
+        DataKey::NftClaimed(address.clone()),
+        DataKey::ContributionLog(address.clone()),
+        DataKey::Anonymous(address.clone()),
+        DataKey::Verified(address.clone()),
+        DataKey::ReferralTally(address.clone()),
+        DataKey::Blocked(address.clone()),
+        DataKey::Pending(address.clone()),
+        DataKey::Nonce(address.clone()),
+        DataKey::ScheduledPledge(address.clone()),
+        DataKey::MatchCredit(address.clone()),
+    ];
+    for key in keys.iter() {
+        env.storage().persistent().remove(key);
+    }
+    for id in 0..proposal_count {
+        env.storage()
             .persistent()
-            .get(&DataKey::Contributors)
-            .unwrap_or_else(|| Vec::new(&env));
-        contributors.len()
+            .remove(&DataKey::Vote(id, address.clone()));
     }
 }
@@ -1,10 +1,28 @@
 use soroban_sdk::{
-    contract, contractimpl, contracttype,
+    contract, contractimpl, contracttype, symbol_short,
     testutils::{Address as _, Ledger},
-    token, Address, Env, Vec,
+    token, Address, Env, String, Vec,
 };
 
-use crate::{ContractError, CrowdfundContract, CrowdfundContractClient};
+use crate::{
+    CampaignOptions, ContractError, ContributionEntry, ContributionReceipt, CrowdfundContract,
+    CrowdfundContractClient, PeriodLimit, PlatformConfig, PriceData, ReferenceGoal, RefundPolicy,
+    RefundStatus, Status, VersionRecord, Withdrawal,
+};
+
+/// This contract as built, and a later release to upgrade it to. Both are
+/// built by `cargo build --release --target wasm32-unknown-unknown`.
+mod crowdfund_wasm {
+    soroban_sdk::contractimport!(
+        file = "../../target/wasm32-unknown-unknown/release/crowdfund.wasm"
+    );
+}
+
+mod upgraded_wasm {
+    soroban_sdk::contractimport!(
+        file = "../../target/wasm32-unknown-unknown/release/upgrade_fixture.wasm"
+    );
+}
 
 #[derive(Clone)]
 #[contracttype]
@@ -68,6 +86,73 @@ fn setup_env() -> (
     (env, client, creator, token_address, token_admin_client)
 }
 
+/// An initialized campaign together with the handles most tests need.
+struct Campaign {
+    env: Env,
+    client: CrowdfundContractClient<'static>,
+    admin: Address,
+    creator: Address,
+    token_address: Address,
+    token_admin_client: token::StellarAssetClient<'static>,
+    goal: i128,
+    deadline: u64,
+}
+
+/// Initialize a campaign with a 1_000_000 goal, a one-hour deadline and a
+/// 1_000 minimum contribution.
+fn setup_campaign() -> Campaign {
+    setup_campaign_with(CampaignOptions::default())
+}
+
+/// Like [`setup_campaign`], but with custom optional settings.
+fn setup_campaign_with(options: CampaignOptions) -> Campaign {
+    setup_campaign_full(|_| None, options)
+}
+
+/// Like [`setup_campaign_with`], but also records the factory returned by
+/// `factory`, which runs against the campaign's environment.
+fn setup_campaign_full(
+    factory: impl FnOnce(&Env) -> Option<Address>,
+    options: CampaignOptions,
+) -> Campaign {
+    let (env, client, creator, token_address, token_admin_client) = setup_env();
+    let factory = factory(&env);
+
+    let admin = Address::generate(&env);
+    let goal: i128 = 1_000_000;
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &admin,
+        &creator,
+        &token_address,
+        &goal,
+        &deadline,
+        &1_000,
+        &symbol_short!("tech"),
+        &None,
+        &factory,
+        &Some(options),
+    );
+
+    Campaign {
+        env,
+        client,
+        admin,
+        creator,
+        token_address,
+        token_admin_client,
+        goal,
+        deadline,
+    }
+}
+
+/// Generate an address funded with `amount` campaign tokens.
+fn funded_backer(c: &Campaign, amount: i128) -> Address {
+    let backer = Address::generate(&c.env);
+    c.token_admin_client.mint(&backer, &amount);
+    backer
+}
+
 #[test]
 fn test_withdraw_mints_nft_for_each_contributor() {
     let (env, client, creator, token_address, token_admin_client) = setup_env();
@@ -89,7 +174,7 @@ fn test_withdraw_mints_nft_for_each_contributor() {
 
     let nft_id = env.register(MockNftContract, ());
     let nft_client = MockNftContractClient::new(&env, &nft_id);
-    client.set_nft_contract(&creator, &nft_id);
+    client.set_nft_contract(&creator, &nft_id, &0);
 
     let alice = Address::generate(&env);
     let bob = Address::generate(&env);
@@ -100,6 +185,7 @@ fn test_withdraw_mints_nft_for_each_contributor() {
     client.contribute(&bob, &200_000, None);
 
     env.ledger().set_timestamp(deadline + 1);
+    client.finalize(&creator);
     client.withdraw();
 
     let minted = nft_client.minted();
@@ -136,7 +222,7 @@ fn test_withdraw_skips_nft_mint_when_contract_not_set() {
     token_admin_client.mint(&contributor, &goal);
     client.contribute(&contributor, &goal);
 
-    client.contribute(&contributor, &500_000, &None);
+    client.contribute(&contributor, &500_000, &None, &false);
 
     assert_eq!(nft_client.minted().len(), 0);
 }
@@ -164,8 +250,8 @@ fn test_set_nft_contract_rejects_non_creator() {
     mint_to(&env, &token_address, &token_admin, &alice, 600_000);
     mint_to(&env, &token_address, &token_admin, &bob, 400_000);
 
-    client.contribute(&alice, &300_000, &None);
-    client.contribute(&bob, &200_000, &None);
+    client.contribute(&alice, &300_000, &None, &false);
+    client.contribute(&bob, &200_000, &None, &false);
 
     assert_eq!(client.total_raised(), 500_000);
     assert_eq!(client.contribution(&alice), 300_000);
@@ -207,7 +293,7 @@ fn test_contribute_after_deadline_panics() {
     let non_creator = Address::generate(&env);
     let nft_id = env.register(MockNftContract, ());
 
-    let result = client.try_set_nft_contract(&non_creator, &nft_id);
+    let result = client.try_set_nft_contract(&non_creator, &nft_id, &0);
     assert!(result.is_err());
 }
 
@@ -232,12 +318,13 @@ fn test_withdraw_successful_campaign_updates_status_and_balance() {
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-    client.contribute(&contributor, &1_000_000, &None);
+    client.contribute(&contributor, &1_000_000, &None, &false);
 
     let token_client = token::Client::new(&env, &token_address);
     let creator_before = token_client.balance(&creator);
 
     env.ledger().set_timestamp(deadline + 1);
+    client.finalize(&creator);
     client.withdraw();
 
     assert_eq!(client.total_raised(), 0);
@@ -254,7 +341,7 @@ fn test_contribute_after_deadline_returns_error() {
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-    client.contribute(&contributor, &1_000_000, &None);
+    client.contribute(&contributor, &1_000_000, &None, &false);
 
     let result = client.try_withdraw();
 
@@ -285,7 +372,7 @@ fn test_withdraw_goal_not_reached_panics() {
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 500_000);
-    client.contribute(&contributor, &500_000, &None);
+    client.contribute(&contributor, &500_000, &None, &false);
 
     env.ledger().set_timestamp(deadline + 1);
 
@@ -321,8 +408,8 @@ fn test_contributor_count_zero_before_contributions() {
     mint_to(&env, &token_address, &token_admin, &alice, 300_000);
     mint_to(&env, &token_address, &token_admin, &bob, 200_000);
 
-    client.contribute(&alice, &300_000, &None);
-    client.contribute(&bob, &200_000, &None);
+    client.contribute(&alice, &300_000, &None, &false);
+    client.contribute(&bob, &200_000, &None, &false);
 
     // Move past deadline — goal not met.
     env.ledger().set_timestamp(deadline + 1);
@@ -352,9 +439,10 @@ fn test_contributor_count_one_after_single_contribution() {
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-    client.contribute(&contributor, &1_000_000, &None);
+    client.contribute(&contributor, &1_000_000, &None, &false);
 
     env.ledger().set_timestamp(deadline + 1);
+    client.finalize(&creator);
 
     let result = client.try_refund_single(&contributor);
 
@@ -456,7 +544,7 @@ fn test_bug_condition_exploration_all_error_conditions_panic() {
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, 500_000);
-        let result = client.try_contribute(&contributor, &500_000, &None);
+        let result = client.try_contribute(&contributor, &500_000, &None, &false);
 
         assert!(result.is_err());
         assert_eq!(result.unwrap_err().unwrap(), ContractError::CampaignEnded);
@@ -480,7 +568,7 @@ fn test_bug_condition_exploration_all_error_conditions_panic() {
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-        client.contribute(&contributor, &1_000_000, &None);
+        client.contribute(&contributor, &1_000_000, &None, &false);
 
         let result = client.try_withdraw();
 
@@ -509,9 +597,10 @@ fn test_bug_condition_exploration_all_error_conditions_panic() {
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, 500_000);
-        client.contribute(&contributor, &500_000, &None);
+        client.contribute(&contributor, &500_000, &None, &false);
 
         env.ledger().set_timestamp(deadline + 1);
+        client.finalize(&creator);
         let result = client.try_withdraw();
 
         assert!(result.is_err());
@@ -565,9 +654,10 @@ fn test_bug_condition_exploration_all_error_conditions_panic() {
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-        client.contribute(&contributor, &1_000_000, &None);
+        client.contribute(&contributor, &1_000_000, &None, &false);
 
         env.ledger().set_timestamp(deadline + 1);
+        client.finalize(&creator);
         let result = client.try_refund_single(&contributor);
 
         assert!(result.is_err());
@@ -673,7 +763,7 @@ proptest! {
         // Ensure contribution is less than goal
         let contribution = contribution_amount.min(goal - 1);
 
-    client.contribute(&contributor, &50_000, &None);
+    client.contribute(&contributor, &50_000, &None, &false);
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, contribution);
@@ -748,8 +838,7 @@ proptest! {
 }
 
 #[test]
-#[should_panic(expected = "campaign is not active")]
-fn test_double_withdraw_panics() {
+fn test_double_withdraw_fails() {
     let (env, client, platform_admin, creator, token_address, token_admin) = setup_env();
 
     let deadline = env.ledger().timestamp() + 3600;
@@ -771,9 +860,14 @@ fn test_double_withdraw_panics() {
     client.contribute(&contributor, &1_000_000);
 
     env.ledger().set_timestamp(deadline + 1);
+    client.finalize(&creator);
 
     client.withdraw();
-    client.withdraw(); // should panic — status is Successful
+    let result = client.try_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignNotActive
+    );
 }
 
 #[test]
@@ -799,6 +893,7 @@ fn test_contributor_count_multiple_contributors() {
     client.contribute(&alice, &500_000);
 
     env.ledger().set_timestamp(deadline + 1);
+    client.finalize(&creator);
 
     client.refund_single(&alice);
     // Second refund should succeed but do nothing (amount is 0)
@@ -841,8 +936,8 @@ proptest! {
 
         client.initialize(&creator, &token_address, &goal, &deadline, &1_000, &default_title(&env), &default_description(&env), &None);
 
-    client.contribute(&alice, &300_000, &None);
-    client.contribute(&bob, &200_000, &None);
+    client.contribute(&alice, &300_000, &None, &false);
+    client.contribute(&bob, &200_000, &None, &false);
 
         // Test 3.2: Valid contribution before deadline works correctly
         client.contribute(&contributor, &contribution_amount);
@@ -869,7 +964,7 @@ proptest! {
         // Move past deadline
         env.ledger().set_timestamp(deadline + 1);
 
-    client.contribute(&contributor, &10_000, &None);
+    client.contribute(&contributor, &10_000, &None, &false);
 
     assert_eq!(client.total_raised(), 10_000);
     assert_eq!(client.contribution(&contributor), 10_000);
@@ -897,7 +992,7 @@ proptest! {
         // Ensure contribution is less than goal
         let contribution = contribution_amount.min(goal - 1);
 
-    client.contribute(&contributor, &50_000, &None);
+    client.contribute(&contributor, &50_000, &None, &false);
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, contribution);
@@ -990,7 +1085,7 @@ fn test_double_withdraw_panics() {
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 50_000);
-    client.contribute(&contributor, &50_000, &None);
+    client.contribute(&contributor, &50_000, &None, &false);
 
     let tier = client.get_user_tier(&contributor);
     assert!(tier.is_some());
@@ -1015,7 +1110,7 @@ fn test_get_user_tier_gold_level() {
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 600_000);
-    client.contribute(&contributor, &600_000, &None);
+    client.contribute(&contributor, &600_000, &None, &false);
 
     let tier = client.get_user_tier(&contributor);
     assert!(tier.is_some());
@@ -1069,7 +1164,7 @@ fn test_get_user_tier_no_tiers_defined_returns_none() {
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 500_000);
-    client.contribute(&contributor, &500_000, &None);
+    client.contribute(&contributor, &500_000, &None, &false);
 
     let tier = client.get_user_tier(&contributor);
     assert!(tier.is_none());
@@ -1103,7 +1198,7 @@ fn test_get_user_tier_highest_qualifying_tier_returned() {
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-    client.contribute(&contributor, &1_000_000, &None);
+    client.contribute(&contributor, &1_000_000, &None, &false);
 
     let tier = client.get_user_tier(&contributor);
     assert!(tier.is_some());
@@ -1520,7 +1615,7 @@ fn test_get_campaign_info_after_goal_reached() {
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-    client.contribute(&contributor, &1_000_000, &None);
+    client.contribute(&contributor, &1_000_000, &None, &false);
 
     let info = client.get_campaign_info();
 
@@ -1657,7 +1752,7 @@ fn test_bonus_goal_progress_tracked_separately_from_primary() {
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 500_000);
-    client.contribute(&contributor, &500_000, &None);
+    client.contribute(&contributor, &500_000, &None, &false);
 
     let primary_progress_bps = (client.total_raised() * 10_000) / client.goal();
     assert_eq!(primary_progress_bps, 5_000);
@@ -1688,7 +1783,7 @@ fn test_bonus_goal_reached_returns_false_below_threshold() {
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 1_500_000);
-    client.contribute(&contributor, &1_500_000, &None);
+    client.contribute(&contributor, &1_500_000, &None, &false);
 
     assert!(!client.bonus_goal_reached());
 }
@@ -1715,10 +1810,10 @@ fn test_bonus_goal_reached_returns_true_at_and_above_threshold() {
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 2_100_000);
-    client.contribute(&contributor, &2_000_000, &None);
+    client.contribute(&contributor, &2_000_000, &None, &false);
     assert!(client.bonus_goal_reached());
 
-    client.contribute(&contributor, &100_000, &None);
+    client.contribute(&contributor, &100_000, &None, &false);
     assert!(client.bonus_goal_reached());
     assert_eq!(client.bonus_goal_progress_bps(), 10_000);
 }
@@ -1765,9 +1860,9 @@ proptest! {
         mint_to(&env, &token_address, &admin, &bob, amount2);
         mint_to(&env, &token_address, &admin, &charlie, amount3);
 
-        client.contribute(&alice, &amount1, &None);
-        client.contribute(&bob, &amount2, &None);
-        client.contribute(&charlie, &amount3, &None);
+        client.contribute(&alice, &amount1, &None, &false);
+        client.contribute(&bob, &amount2, &None, &false);
+        client.contribute(&charlie, &amount3, &None, &false);
 
         let expected_total = amount1 + amount2 + amount3;
         let actual_total = client.total_raised();
@@ -1814,7 +1909,7 @@ proptest! {
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, safe_contribution);
-        client.contribute(&contributor, &safe_contribution, &None);
+        client.contribute(&contributor, &safe_contribution, &None, &false);
 
         // Move past deadline (goal not met)
         env.ledger().set_timestamp(deadline + 1);
@@ -1869,7 +1964,7 @@ proptest! {
 
         // Attempt to contribute zero or negative amount
         // This should fail due to minimum contribution check
-        let result = client.try_contribute(&contributor, &negative_amount, &None);
+        let result = client.try_contribute(&contributor, &negative_amount, &None, &false);
 
         // **INVARIANT**: Contribution <= 0 must fail
         prop_assert!(
@@ -1964,9 +2059,9 @@ proptest! {
         mint_to(&env, &token_address, &admin, &contributor2, amount2);
         mint_to(&env, &token_address, &admin, &contributor3, amount3);
 
-        client.contribute(&contributor1, &amount1, &None);
-        client.contribute(&contributor2, &amount2, &None);
-        client.contribute(&contributor3, &amount3, &None);
+        client.contribute(&contributor1, &amount1, &None, &false);
+        client.contribute(&contributor2, &amount2, &None, &false);
+        client.contribute(&contributor3, &amount3, &None, &false);
 
         // **INVARIANT**: total_raised must equal sum of all contributions
         prop_assert_eq!(client.total_raised(), expected_total);
@@ -2005,7 +2100,7 @@ proptest! {
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, goal);
-        client.contribute(&contributor, &goal, &None);
+        client.contribute(&contributor, &goal, &None, &false);
 
         // Move past deadline
         env.ledger().set_timestamp(deadline + 1);
@@ -2063,16 +2158,16 @@ proptest! {
         mint_to(&env, &token_address, &admin, &contributor, total_needed);
 
         // First contribution
-        client.contribute(&contributor, &amount1, &None);
+        client.contribute(&contributor, &amount1, &None, &false);
         prop_assert_eq!(client.contribution(&contributor), amount1);
 
         // Second contribution
-        client.contribute(&contributor, &amount2, &None);
+        client.contribute(&contributor, &amount2, &None, &false);
         let expected_after_2 = amount1.saturating_add(amount2);
         prop_assert_eq!(client.contribution(&contributor), expected_after_2);
 
         // Third contribution
-        client.contribute(&contributor, &amount3, &None);
+        client.contribute(&contributor, &amount3, &None, &false);
         let expected_total = amount1.saturating_add(amount2).saturating_add(amount3);
         prop_assert_eq!(client.contribution(&contributor), expected_total);
 
@@ -2111,7 +2206,7 @@ proptest! {
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, safe_contribution);
-        client.contribute(&contributor, &safe_contribution, &None);
+        client.contribute(&contributor, &safe_contribution, &None, &false);
 
         // Verify total_raised is set
         prop_assert_eq!(client.total_raised(), safe_contribution);
@@ -2158,7 +2253,7 @@ proptest! {
         mint_to(&env, &token_address, &admin, &contributor, amount_to_contribute);
 
         // Attempt to contribute below minimum
-        let result = client.try_contribute(&contributor, &amount_to_contribute, &None);
+        let result = client.try_contribute(&contributor, &amount_to_contribute, &None, &false);
 
         // **INVARIANT**: Contribution below minimum must fail
         prop_assert!(
@@ -2196,7 +2291,7 @@ fn test_add_to_whitelist_non_creator_panics() {
     // Mock authorization for non-creator
     env.mock_all_auths();
 
-    let result = client.try_contribute(&contributor, &5_000, &None);
+    let result = client.try_contribute(&contributor, &5_000, &None, &false);
 
     client.add_to_whitelist(&soroban_sdk::vec![&env, alice]);
 }
@@ -2251,7 +2346,7 @@ fn test_full_withdrawal_removes_contributor() {
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 500_000);
-    client.contribute(&contributor, &500_000, &None);
+    client.contribute(&contributor, &500_000, &None, &false);
 
     let stats = client.get_stats();
     assert_eq!(stats.contributor_count, 1);
@@ -2700,6 +2795,7 @@ fn test_withdraw_with_multisig_creator() {
 
     // Fast forward past deadline
     env.ledger().set_timestamp(deadline + 1);
+    client.finalize(&multisig_creator);
 
     // Withdraw should succeed with multisig creator
     // In a real scenario, this would require M-of-N signatures
@@ -2940,8 +3036,5896 @@ fn test_all_admin_functions_with_dao_creator() {
 
     // Fast forward past deadline
     env.ledger().set_timestamp(new_deadline + 1);
+    client.finalize(&dao_creator);
 
     // Test withdraw
     let result = client.try_withdraw();
     assert!(result.is_ok());
 }
+
+// ── Verified Contribution Tests ────────────────────────────────────────────
+
+#[test]
+fn test_verified_contributions_tracked_separately() {
+    let c = setup_campaign();
+
+    let alice = funded_backer(&c, 300_000);
+    let bob = funded_backer(&c, 200_000);
+    c.client.set_verified(&c.admin, &alice, &true);
+
+    c.client
+        .contribute(&alice, &300_000, &None, &false, &0, &None);
+    c.client
+        .contribute(&bob, &200_000, &None, &false, &0, &None);
+
+    assert!(c.client.is_verified(&alice));
+    assert!(!c.client.is_verified(&bob));
+    assert_eq!(c.client.total_raised(), 500_000);
+    assert_eq!(c.client.verified_raised(), 300_000);
+    assert_eq!(c.client.get_stats().verified_raised, 300_000);
+}
+
+#[test]
+fn test_withdraw_requires_quality_goal() {
+    let c = setup_campaign();
+    c.client.set_quality_goal(&c.creator, &600_000);
+
+    let alice = funded_backer(&c, 500_000);
+    let bob = funded_backer(&c, 500_000);
+    c.client.set_verified(&c.admin, &alice, &true);
+    c.client
+        .contribute(&alice, &500_000, &None, &false, &0, &None);
+    c.client
+        .contribute(&bob, &500_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    assert_eq!(
+        c.client.try_can_withdraw().unwrap_err().unwrap(),
+        ContractError::CampaignNotSettled
+    );
+    assert!(c.client.finalize(&c.creator) == Status::Refunded);
+
+    let result = c.client.try_withdraw();
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::GoalNotReached);
+
+    // The campaign failed on quality, so backers can reclaim their funds.
+    c.client.refund_single(&bob);
+    assert_eq!(c.client.contribution(&bob), 0);
+}
+
+#[test]
+fn test_refunds_release_verified_stake() {
+    let c = setup_campaign();
+
+    let alice = funded_backer(&c, 300_000);
+    let bob = funded_backer(&c, 200_000);
+    c.client.set_verified(&c.admin, &alice, &true);
+    c.client.set_verified(&c.admin, &bob, &true);
+    c.client
+        .contribute(&alice, &300_000, &None, &false, &0, &None);
+    c.client
+        .contribute(&bob, &200_000, &None, &false, &0, &None);
+    assert_eq!(c.client.verified_raised(), 500_000);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    assert!(c.client.finalize(&c.creator) == Status::Refunded);
+    c.client.refund_single(&alice);
+    assert_eq!(c.client.verified_raised(), 200_000);
+
+    c.client.creator_refund_all();
+    assert_eq!(c.client.verified_raised(), 0);
+}
+
+#[test]
+fn test_cancel_with_refunds_releases_verified_stake() {
+    let c = setup_campaign();
+
+    let alice = funded_backer(&c, 300_000);
+    c.client.set_verified(&c.admin, &alice, &true);
+    c.client
+        .contribute(&alice, &300_000, &None, &false, &0, &None);
+
+    c.client.cancel(&String::from_str(&c.env, ""), &true);
+    assert_eq!(c.client.verified_raised(), 0);
+}
+
+#[test]
+#[should_panic(expected = "not authorized")]
+fn test_set_verified_rejects_non_admin() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 1_000);
+    c.client.set_verified(&c.creator, &alice, &true);
+}
+
+// ── Reset Tests ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_reset_after_successful_cycle_allows_reinitialize() {
+    let c = setup_campaign();
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    c.client.reset(&c.admin);
+    assert_eq!(c.client.contribution(&alice), 0);
+    assert_eq!(c.client.contributor_count(), 0);
+
+    let new_deadline = c.env.ledger().timestamp() + 3600;
+    c.client.initialize(
+        &c.admin,
+        &c.creator,
+        &c.token_address,
+        &500_000,
+        &new_deadline,
+        &1_000,
+        &symbol_short!("art"),
+        &None,
+        &None,
+        &None,
+    );
+    assert_eq!(c.client.category(), symbol_short!("art"));
+    assert_eq!(c.client.goal(), 500_000);
+    assert_eq!(c.client.total_raised(), 0);
+
+    let bob = funded_backer(&c, 10_000);
+    c.client.contribute(&bob, &10_000, &None, &false, &0, &None);
+    assert_eq!(c.client.total_raised(), 10_000);
+}
+
+#[test]
+fn test_reset_clears_anonymous_and_admin_state() {
+    let c = setup_campaign();
+
+    let alice = funded_backer(&c, c.goal);
+    let carol = Address::generate(&c.env);
+    c.client.set_verified(&c.admin, &alice, &true);
+    c.client.block_address(&c.admin, &carol);
+    c.client
+        .contribute(&alice, &c.goal, &None, &true, &0, &None);
+    assert_eq!(c.client.get_stats().anonymous_count, 1);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    c.client.reset(&c.admin);
+    let new_deadline = c.env.ledger().timestamp() + 3600;
+    c.client.initialize(
+        &c.admin,
+        &c.creator,
+        &c.token_address,
+        &500_000,
+        &new_deadline,
+        &1_000,
+        &symbol_short!("art"),
+        &None,
+        &None,
+        &None,
+    );
+
+    // No phantom stake survives to be refunded, voted or flagged with.
+    assert_eq!(c.client.contribution(&alice), 0);
+    assert!(!c.client.is_verified(&alice));
+    assert!(!c.client.is_blocked(&carol));
+    assert!(c.client.contribution_log(&alice).is_empty());
+    let result = c.client.try_flag_campaign(&alice);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::NotContributor);
+
+    // Anonymity does not carry over either.
+    c.token_admin_client.mint(&alice, &10_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    assert_eq!(c.client.contributors(), soroban_sdk::vec![&c.env, alice]);
+    assert_eq!(c.client.get_stats().anonymous_count, 0);
+}
+
+#[test]
+fn test_reset_rejected_while_active() {
+    let c = setup_campaign();
+
+    let result = c.client.try_reset(&c.admin);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignNotSettled
+    );
+}
+
+// ── Strict Goal Tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_strict_goal_rejects_contribution_past_goal() {
+    let c = setup_campaign_with(CampaignOptions {
+        strict_goal: true,
+        ..Default::default()
+    });
+
+    let alice = funded_backer(&c, 800_000);
+    let bob = funded_backer(&c, 300_000);
+    c.client
+        .contribute(&alice, &800_000, &None, &false, &0, &None);
+    assert_eq!(c.client.remaining_to_goal(), 200_000);
+
+    let result = c
+        .client
+        .try_contribute(&bob, &300_000, &None, &false, &0, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::ContributionExceedsGoal
+    );
+
+    c.client
+        .contribute(&bob, &200_000, &None, &false, &0, &None);
+    assert_eq!(c.client.total_raised(), c.goal);
+    assert_eq!(c.client.remaining_to_goal(), 0);
+}
+
+#[test]
+fn test_non_strict_goal_allows_over_collection() {
+    let c = setup_campaign();
+
+    let alice = funded_backer(&c, 1_200_000);
+    c.client
+        .contribute(&alice, &1_200_000, &None, &false, &0, &None);
+
+    assert!(!c.client.strict_goal());
+    assert_eq!(c.client.total_raised(), 1_200_000);
+    assert_eq!(c.client.remaining_to_goal(), 0);
+}
+
+// ── Creator Refund Tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_creator_refund_all_returns_funds_to_every_backer() {
+    let c = setup_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    let alice = funded_backer(&c, 300_000);
+    let bob = funded_backer(&c, 200_000);
+    c.client
+        .contribute(&alice, &300_000, &None, &false, &0, &None);
+    c.client
+        .contribute(&bob, &200_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.creator_refund_all();
+
+    assert_eq!(token_client.balance(&alice), 300_000);
+    assert_eq!(token_client.balance(&bob), 200_000);
+    assert_eq!(c.client.contribution(&alice), 0);
+    assert_eq!(c.client.total_raised(), 0);
+}
+
+#[test]
+fn test_creator_refund_all_rejected_when_goal_met() {
+    let c = setup_campaign();
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    let result = c.client.try_creator_refund_all();
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::GoalReached);
+}
+
+// ── Solvency Tests ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_solvency_delta_zero_after_clean_contribution() {
+    let c = setup_campaign();
+
+    let alice = funded_backer(&c, 250_000);
+    c.client
+        .contribute(&alice, &250_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.solvency_delta(), 0);
+}
+
+#[test]
+fn test_solvency_delta_positive_after_direct_tip() {
+    let c = setup_campaign();
+
+    let alice = funded_backer(&c, 250_000);
+    c.client
+        .contribute(&alice, &250_000, &None, &false, &0, &None);
+    c.token_admin_client.mint(&c.client.address, &7_500);
+
+    assert_eq!(c.client.solvency_delta(), 7_500);
+}
+
+#[test]
+fn test_surplus_reports_stray_deposit_above_token_balance() {
+    let c = setup_campaign();
+
+    let alice = funded_backer(&c, 250_000);
+    c.client
+        .contribute(&alice, &250_000, &None, &false, &0, &None);
+    assert_eq!(c.client.surplus(), 0);
+
+    c.token_admin_client.mint(&c.client.address, &7_500);
+
+    assert_eq!(c.client.token_balance(), 257_500);
+    assert_eq!(c.client.total_raised(), 250_000);
+    assert_eq!(c.client.surplus(), 7_500);
+}
+
+// ── Contributors Pagination Tests ──────────────────────────────────────────
+
+#[test]
+fn test_contributors_page_returns_bounded_slices() {
+    let c = setup_campaign();
+
+    let mut backers = Vec::new(&c.env);
+    for _ in 0..5 {
+        let backer = funded_backer(&c, 1_000);
+        c.client
+            .contribute(&backer, &1_000, &None, &false, &0, &None);
+        backers.push_back(backer);
+    }
+
+    let first = c.client.contributors_page(&0, &2);
+    assert_eq!(first.len(), 2);
+    assert_eq!(first.get(0).unwrap(), backers.get(0).unwrap());
+
+    let last = c.client.contributors_page(&4, &10);
+    assert_eq!(last.len(), 1);
+    assert_eq!(last.get(0).unwrap(), backers.get(4).unwrap());
+
+    assert_eq!(c.client.contributors_page(&5, &10).len(), 0);
+    assert_eq!(c.client.contributors(), backers);
+}
+
+#[test]
+fn test_contributors_page_clamps_limit() {
+    let c = setup_campaign();
+
+    for _ in 0..3 {
+        let backer = funded_backer(&c, 1_000);
+        c.client
+            .contribute(&backer, &1_000, &None, &false, &0, &None);
+    }
+
+    assert_eq!(c.client.contributors_page(&0, &u32::MAX).len(), 3);
+}
+
+// ── Periodic Cap Tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_period_cap_enforced_and_resets_after_period() {
+    let c = setup_campaign_with(CampaignOptions {
+        period_limit: Some(PeriodLimit {
+            cap: 10_000,
+            period_seconds: 1_000,
+        }),
+        ..Default::default()
+    });
+
+    let alice = funded_backer(&c, 20_000);
+    c.client
+        .contribute(&alice, &6_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(100);
+    c.client
+        .contribute(&alice, &4_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(200);
+    let result = c
+        .client
+        .try_contribute(&alice, &1_000, &None, &false, &0, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::PeriodCapExceeded
+    );
+
+    c.env.ledger().set_timestamp(1_001);
+    c.client
+        .contribute(&alice, &1_000, &None, &false, &0, &None);
+    assert_eq!(c.client.contribution(&alice), 11_000);
+}
+
+// ── Feature Flag Tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_feature_flags_reflect_configuration() {
+    let c = setup_campaign_with(CampaignOptions {
+        bonus_goal: Some(2_000_000),
+        strict_goal: true,
+        period_limit: Some(PeriodLimit {
+            cap: 50_000,
+            period_seconds: 86_400,
+        }),
+        ..Default::default()
+    });
+
+    let flags = c.client.feature_flags();
+    assert!(flags.bonus_goal);
+    assert!(flags.strict_goal);
+    assert!(flags.period_limit);
+    assert!(!flags.platform_fee);
+    assert!(!flags.quality_goal);
+    assert!(!flags.nft_rewards);
+}
+
+#[test]
+fn test_feature_flags_all_off_by_default() {
+    let c = setup_campaign();
+
+    let flags = c.client.feature_flags();
+    assert!(!flags.bonus_goal);
+    assert!(!flags.strict_goal);
+    assert!(!flags.period_limit);
+}
+
+// ── Matching Funds Tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_matching_credits_total_but_not_backer() {
+    let c = setup_campaign();
+
+    let sponsor = funded_backer(&c, 150_000);
+    c.client
+        .set_matching(&c.creator, &sponsor, &5_000, &150_000);
+
+    let alice = funded_backer(&c, 200_000);
+    c.client
+        .contribute(&alice, &200_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.matched_total(), 100_000);
+    assert_eq!(c.client.contribution(&alice), 200_000);
+    assert_eq!(c.client.total_raised(), 300_000);
+}
+
+#[test]
+fn test_matching_stops_at_cap() {
+    let c = setup_campaign();
+
+    let sponsor = funded_backer(&c, 50_000);
+    c.client
+        .set_matching(&c.creator, &sponsor, &10_000, &50_000);
+
+    let alice = funded_backer(&c, 40_000);
+    let bob = funded_backer(&c, 40_000);
+    c.client
+        .contribute(&alice, &40_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &40_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.matched_total(), 50_000);
+    assert_eq!(c.client.total_raised(), 130_000);
+}
+
+#[test]
+#[should_panic(expected = "not authorized")]
+fn test_matching_rejects_non_creator() {
+    let c = setup_campaign();
+
+    let sponsor = funded_backer(&c, 50_000);
+    c.client.set_matching(&sponsor, &sponsor, &10_000, &50_000);
+}
+
+#[test]
+fn test_matching_returns_unused_deposit_on_withdraw() {
+    let c = setup_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    let sponsor = funded_backer(&c, 200_000);
+    c.client
+        .set_matching(&c.creator, &sponsor, &2_500, &200_000);
+    let alice = funded_backer(&c, 800_000);
+    c.client
+        .contribute(&alice, &800_000, &None, &false, &0, &None);
+    assert_eq!(c.client.matched_total(), 200_000);
+
+    let bob = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&bob, &100_000, &None, &false, &0, &None);
+    assert_eq!(c.client.sponsor_balance(), 0);
+    assert_eq!(c.client.solvency_delta(), 0);
+
+    let creator_before = token_client.balance(&c.creator);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+    assert_eq!(token_client.balance(&sponsor), 0);
+    assert_eq!(token_client.balance(&c.creator), creator_before + 1_100_000);
+}
+
+#[test]
+fn test_matching_unused_deposit_goes_back_to_sponsor() {
+    let c = setup_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    let sponsor = funded_backer(&c, 300_000);
+    c.client
+        .set_matching(&c.creator, &sponsor, &2_500, &300_000);
+    let alice = funded_backer(&c, 1_000_000);
+    c.client
+        .contribute(&alice, &1_000_000, &None, &false, &0, &None);
+    assert_eq!(c.client.sponsor_balance(), 50_000);
+    assert_eq!(c.client.solvency_delta(), 0);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+    assert_eq!(token_client.balance(&sponsor), 50_000);
+    assert_eq!(c.client.sponsor_balance(), 0);
+}
+
+#[test]
+fn test_matching_refunded_to_sponsor_on_failure() {
+    let c = setup_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    let sponsor = funded_backer(&c, 100_000);
+    c.client
+        .set_matching(&c.creator, &sponsor, &5_000, &100_000);
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    assert_eq!(c.client.total_raised(), 150_000);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.refund_single(&alice);
+
+    assert_eq!(token_client.balance(&alice), 100_000);
+    assert_eq!(token_client.balance(&sponsor), 100_000);
+    assert_eq!(c.client.matched_total(), 0);
+    assert_eq!(c.client.total_raised(), 0);
+    assert!(c.client.status() == Status::Refunded);
+}
+
+#[test]
+fn test_matching_refunded_to_sponsor_on_cancel() {
+    let c = setup_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    let sponsor = funded_backer(&c, 100_000);
+    c.client
+        .set_matching(&c.creator, &sponsor, &5_000, &100_000);
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+
+    c.client
+        .cancel(&String::from_str(&c.env, "Venue cancelled"), &true);
+
+    assert_eq!(token_client.balance(&alice), 100_000);
+    assert_eq!(token_client.balance(&sponsor), 100_000);
+    assert_eq!(c.client.total_raised(), 0);
+}
+
+#[test]
+fn test_matching_clamped_by_strict_goal() {
+    let c = setup_campaign_with(CampaignOptions {
+        strict_goal: true,
+        ..Default::default()
+    });
+
+    let sponsor = funded_backer(&c, 500_000);
+    c.client
+        .set_matching(&c.creator, &sponsor, &10_000, &500_000);
+    let alice = funded_backer(&c, 600_000);
+    c.client
+        .contribute(&alice, &600_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.matched_total(), 400_000);
+    assert_eq!(c.client.total_raised(), c.goal);
+    assert_eq!(c.client.sponsor_balance(), 100_000);
+}
+
+#[test]
+fn test_matching_clamped_by_hard_cap() {
+    let c = setup_campaign_with(CampaignOptions {
+        hard_cap: Some(1_200_000),
+        ..Default::default()
+    });
+
+    let sponsor = funded_backer(&c, 500_000);
+    c.client
+        .set_matching(&c.creator, &sponsor, &10_000, &500_000);
+    let alice = funded_backer(&c, 1_000_000);
+    c.client
+        .contribute(&alice, &1_000_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.matched_total(), 200_000);
+    assert_eq!(c.client.total_raised(), 1_200_000);
+}
+
+// ── Creator Transfer Tests ─────────────────────────────────────────────────
+
+#[test]
+fn test_transfer_creator_moves_withdraw_rights() {
+    use soroban_sdk::testutils::{MockAuth, MockAuthInvoke};
+    use soroban_sdk::IntoVal;
+
+    let c = setup_campaign();
+    let new_creator = Address::generate(&c.env);
+    c.client.transfer_creator(&new_creator);
+    assert_eq!(c.client.creator(), new_creator);
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&new_creator);
+
+    c.env.mock_auths(&[MockAuth {
+        address: &c.creator,
+        invoke: &MockAuthInvoke {
+            contract: &c.client.address,
+            fn_name: "withdraw",
+            args: ().into_val(&c.env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(c.client.try_withdraw().is_err());
+
+    c.env.mock_auths(&[MockAuth {
+        address: &new_creator,
+        invoke: &MockAuthInvoke {
+            contract: &c.client.address,
+            fn_name: "withdraw",
+            args: ().into_val(&c.env),
+            sub_invokes: &[],
+        },
+    }]);
+    c.client.withdraw();
+
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    assert_eq!(token_client.balance(&new_creator), c.goal);
+}
+
+// ── Factory Status Callback Tests ──────────────────────────────────────────
+
+#[contract]
+struct MockFactory;
+
+#[contractimpl]
+impl MockFactory {
+    pub fn report_status(env: Env, campaign: Address, old: Status, new: Status) {
+        campaign.require_auth();
+        let mut reports: Vec<(Status, Status)> = env
+            .storage()
+            .instance()
+            .get(&1u32)
+            .unwrap_or_else(|| Vec::new(&env));
+        reports.push_back((old, new));
+        env.storage().instance().set(&1u32, &reports);
+    }
+
+    pub fn reports(env: Env) -> Vec<(Status, Status)> {
+        env.storage()
+            .instance()
+            .get(&1u32)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn report_contribution(env: Env, campaign: Address, amount: i128) {
+        campaign.require_auth();
+        let total: i128 = env.storage().instance().get(&2u32).unwrap_or(0);
+        env.storage().instance().set(&2u32, &(total + amount));
+    }
+
+    pub fn reported_raised(env: Env) -> i128 {
+        env.storage().instance().get(&2u32).unwrap_or(0)
+    }
+}
+
+#[test]
+fn test_contributions_and_refunds_reported_to_factory() {
+    let c = setup_campaign_full(
+        |env| Some(env.register(MockFactory, ())),
+        CampaignOptions::default(),
+    );
+    let factory = MockFactoryClient::new(&c.env, &c.client.factory().unwrap());
+
+    let alice = funded_backer(&c, 100_000);
+    let bob = funded_backer(&c, 50_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &50_000, &None, &false, &0, &None);
+    assert_eq!(factory.reported_raised(), 150_000);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.refund_single(&alice);
+    assert_eq!(factory.reported_raised(), 50_000);
+}
+
+#[test]
+fn test_failing_factory_does_not_block_withdraw() {
+    let c = setup_campaign_full(
+        |env| Some(Address::generate(env)),
+        CampaignOptions::default(),
+    );
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+
+    assert!(c.client.try_withdraw().is_ok());
+}
+
+// ── Admin Handover Tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_admin_handover_requires_acceptance() {
+    let c = setup_campaign();
+    let new_admin = Address::generate(&c.env);
+
+    c.client.propose_admin(&c.admin, &new_admin);
+    assert_eq!(c.client.admin(), c.admin);
+    assert_eq!(c.client.pending_admin(), Some(new_admin.clone()));
+
+    c.client.accept_admin(&new_admin);
+    assert_eq!(c.client.admin(), new_admin);
+    assert_eq!(c.client.pending_admin(), None);
+}
+
+#[test]
+fn test_accept_admin_rejects_wrong_or_missing_proposal() {
+    let c = setup_campaign();
+    let new_admin = Address::generate(&c.env);
+    let stranger = Address::generate(&c.env);
+
+    let result = c.client.try_accept_admin(&new_admin);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::NoPendingAdmin);
+
+    c.client.propose_admin(&c.admin, &new_admin);
+    let result = c.client.try_accept_admin(&stranger);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::NotPendingAdmin);
+}
+
+// ── Max Contribution Tests ─────────────────────────────────────────────────
+
+#[test]
+fn test_max_contribution_sums_repeat_contributions() {
+    let c = setup_campaign_with(CampaignOptions {
+        max_contribution: Some(100_000),
+        ..Default::default()
+    });
+    assert_eq!(c.client.max_contribution(), Some(100_000));
+
+    let alice = funded_backer(&c, 200_000);
+    c.client
+        .contribute(&alice, &60_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(100);
+    let result = c
+        .client
+        .try_contribute(&alice, &50_000, &None, &false, &0, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::MaxContributionExceeded
+    );
+
+    c.client
+        .contribute(&alice, &40_000, &None, &false, &0, &None);
+    assert_eq!(c.client.contribution(&alice), 100_000);
+}
+
+#[test]
+fn test_max_contribution_unset_by_default() {
+    let c = setup_campaign();
+    assert_eq!(c.client.max_contribution(), None);
+}
+
+// ── Flagging Tests ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_flag_campaign_once_per_contributor() {
+    let c = setup_campaign();
+
+    let alice = funded_backer(&c, 10_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+
+    c.client.flag_campaign(&alice);
+    assert!(c.client.has_flagged(&alice));
+    assert_eq!(c.client.flag_count(), 1);
+
+    let result = c.client.try_flag_campaign(&alice);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::AlreadyFlagged);
+    assert_eq!(c.client.flag_count(), 1);
+}
+
+#[test]
+fn test_flag_campaign_rejects_non_contributor() {
+    let c = setup_campaign();
+    let stranger = Address::generate(&c.env);
+
+    let result = c.client.try_flag_campaign(&stranger);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::NotContributor);
+    assert!(!c.client.has_flagged(&stranger));
+}
+
+#[test]
+fn test_flag_threshold_counts_anonymous_backers() {
+    let c = setup_campaign();
+    let threshold_reached = soroban_sdk::Symbol::new(&c.env, "flag_threshold_reached");
+
+    let mut backers = Vec::new(&c.env);
+    for _ in 0..4 {
+        let backer = funded_backer(&c, 10_000);
+        c.client
+            .contribute(&backer, &10_000, &None, &true, &0, &None);
+        backers.push_back(backer);
+    }
+
+    // One flag out of four anonymous backers is exactly the 25% threshold.
+    c.client.flag_campaign(&backers.get(0).unwrap());
+    assert!(campaign_event_names(&c).contains(&threshold_reached));
+
+    c.client.flag_campaign(&backers.get(1).unwrap());
+    assert!(!campaign_event_names(&c).contains(&threshold_reached));
+}
+
+// ── Vesting Tests ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_withdraw_vested_releases_tranches_over_time() {
+    let c = setup_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    c.client.set_vesting(&4, &1_000);
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    assert_eq!(c.client.vested_amount(), 250_000);
+    assert_eq!(c.client.withdraw_vested(), 250_000);
+    assert_eq!(token_client.balance(&c.creator), 10_000_000 + 250_000);
+
+    let result = c.client.try_withdraw_vested();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::NothingToWithdraw
+    );
+
+    c.env.ledger().set_timestamp(c.deadline + 2_001);
+    assert_eq!(c.client.withdraw_vested(), 500_000);
+
+    c.env.ledger().set_timestamp(c.deadline + 10_000);
+    assert_eq!(c.client.withdraw_vested(), 250_000);
+    assert_eq!(c.client.vested_amount(), c.goal);
+    assert_eq!(c.client.total_raised(), 0);
+}
+
+#[test]
+fn test_lump_sum_withdraw_disabled_when_vesting() {
+    let c = setup_campaign();
+    c.client.set_vesting(&2, &1_000);
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+
+    let result = c.client.try_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::VestingConfigured
+    );
+}
+
+// ── Batch Contribution View Tests ──────────────────────────────────────────
+
+#[test]
+fn test_contributions_of_preserves_input_order() {
+    let c = setup_campaign();
+
+    let alice = funded_backer(&c, 30_000);
+    let bob = funded_backer(&c, 20_000);
+    let stranger = Address::generate(&c.env);
+    c.client
+        .contribute(&alice, &30_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &20_000, &None, &false, &0, &None);
+
+    let addresses = soroban_sdk::vec![&c.env, bob, stranger, alice];
+    let amounts = c.client.contributions_of(&addresses);
+    assert_eq!(amounts, soroban_sdk::vec![&c.env, 20_000, 0, 30_000]);
+}
+
+#[test]
+fn test_contributions_of_rejects_oversized_input() {
+    let c = setup_campaign();
+
+    let mut addresses = Vec::new(&c.env);
+    for _ in 0..51 {
+        addresses.push_back(Address::generate(&c.env));
+    }
+
+    let result = c.client.try_contributions_of(&addresses);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::InvalidLimit);
+}
+
+// ── NFT Tier Tests ─────────────────────────────────────────────────────────
+
+#[contract]
+struct MockTieredNft;
+
+#[contractimpl]
+impl MockTieredNft {
+    pub fn mint(env: Env, to: Address) -> u128 {
+        Self::mint_tier(env, to, 0)
+    }
+
+    pub fn mint_tier(env: Env, to: Address, tier: u32) -> u128 {
+        let mut minted: Vec<(Address, u32)> = env
+            .storage()
+            .instance()
+            .get(&1u32)
+            .unwrap_or_else(|| Vec::new(&env));
+        minted.push_back((to, tier));
+        env.storage().instance().set(&1u32, &minted);
+        minted.len() as u128
+    }
+
+    pub fn minted(env: Env) -> Vec<(Address, u32)> {
+        env.storage()
+            .instance()
+            .get(&1u32)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+}
+
+#[test]
+fn test_withdraw_mints_tier_matching_contribution() {
+    let c = setup_campaign();
+    let nft_id = c.env.register(MockTieredNft, ());
+    let nft_client = MockTieredNftClient::new(&c.env, &nft_id);
+    c.client.set_nft_contract(&c.creator, &nft_id, &0);
+    c.client
+        .set_nft_tiers(&c.creator, &soroban_sdk::vec![&c.env, 100_000, 500_000]);
+
+    let small = funded_backer(&c, 50_000);
+    let medium = funded_backer(&c, 150_000);
+    let large = funded_backer(&c, 800_000);
+    c.client
+        .contribute(&small, &50_000, &None, &false, &0, &None);
+    c.client
+        .contribute(&medium, &150_000, &None, &false, &0, &None);
+    c.client
+        .contribute(&large, &800_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    let minted = nft_client.minted();
+    assert_eq!(minted.len(), 3);
+    assert_eq!(minted.get(0).unwrap(), (small, 0));
+    assert_eq!(minted.get(1).unwrap(), (medium, 1));
+    assert_eq!(minted.get(2).unwrap(), (large, 2));
+}
+
+#[test]
+fn test_withdraw_mints_nft_only_above_min_contribution() {
+    let c = setup_campaign();
+    let nft_id = c.env.register(MockTieredNft, ());
+    let nft_client = MockTieredNftClient::new(&c.env, &nft_id);
+    c.client.set_nft_contract(&c.creator, &nft_id, &0);
+    c.client.set_nft_min_contribution(&c.creator, &100_000);
+    assert_eq!(c.client.nft_min_contribution(), Some(100_000));
+
+    let small = funded_backer(&c, 50_000);
+    let exact = funded_backer(&c, 100_000);
+    let large = funded_backer(&c, 850_000);
+    c.client
+        .contribute(&small, &50_000, &None, &false, &0, &None);
+    c.client
+        .contribute(&exact, &100_000, &None, &false, &0, &None);
+    c.client
+        .contribute(&large, &850_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    let minted = nft_client.minted();
+    assert_eq!(minted.len(), 2);
+    assert_eq!(minted.get(0).unwrap(), (exact, 0));
+    assert_eq!(minted.get(1).unwrap(), (large, 0));
+}
+
+#[test]
+#[should_panic(expected = "nft minimum contribution must be positive")]
+fn test_set_nft_min_contribution_rejects_zero() {
+    let c = setup_campaign();
+    c.client.set_nft_min_contribution(&c.creator, &0);
+}
+
+// ── Reentrancy Tests ───────────────────────────────────────────────────────
+
+/// Token that tries to re-enter `refund_single` whenever the campaign pays out.
+#[contract]
+struct ReentrantToken;
+
+#[contractimpl]
+impl ReentrantToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage().persistent().set(&to, &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().persistent().get(&id).unwrap_or(0)
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        7
+    }
+
+    pub fn set_campaign(env: Env, campaign: Address) {
+        env.storage().instance().set(&1u32, &campaign);
+    }
+
+    pub fn reentered(env: Env) -> u32 {
+        env.storage().instance().get(&2u32).unwrap_or(0)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        let from_balance = Self::balance(env.clone(), from.clone());
+        env.storage()
+            .persistent()
+            .set(&from, &(from_balance - amount));
+        let to_balance = Self::balance(env.clone(), to.clone());
+        env.storage().persistent().set(&to, &(to_balance + amount));
+
+        let campaign: Option<Address> = env.storage().instance().get(&1u32);
+        if campaign == Some(from.clone()) {
+            let client = CrowdfundContractClient::new(&env, &from);
+            if client.try_refund_single(&to).is_ok() {
+                let count = Self::reentered(env.clone()) + 1;
+                env.storage().instance().set(&2u32, &count);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_refund_single_cannot_be_reentered_by_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CrowdfundContract, ());
+    let client = CrowdfundContractClient::new(&env, &contract_id);
+    let token_id = env.register(ReentrantToken, ());
+    let token = ReentrantTokenClient::new(&env, &token_id);
+    token.set_campaign(&contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &admin,
+        &creator,
+        &token_id,
+        &1_000_000,
+        &deadline,
+        &1_000,
+        &symbol_short!("tech"),
+        &None,
+        &None,
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token.mint(&alice, &100_000);
+    token.mint(&bob, &100_000);
+    client.contribute(&alice, &100_000, &None, &false, &0, &None);
+    client.contribute(&bob, &100_000, &None, &false, &0, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.finalize(&creator);
+    client.refund_single(&alice);
+
+    // The nested refund failed, so alice was paid once and bob's stake is
+    // untouched.
+    assert_eq!(token.reentered(), 0);
+    assert_eq!(token.balance(&alice), 100_000);
+    assert_eq!(token.balance(&contract_id), 100_000);
+    assert_eq!(client.contribution(&alice), 0);
+    assert_eq!(client.contribution(&bob), 100_000);
+    assert_eq!(client.total_raised(), 100_000);
+}
+
+// ── Category Tests ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_category_stored_at_initialize() {
+    let c = setup_campaign();
+    assert_eq!(c.client.category(), symbol_short!("tech"));
+}
+
+// ── Reduce Contribution Tests ──────────────────────────────────────────────
+
+#[test]
+fn test_reduce_contribution_partial_and_full() {
+    let c = setup_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+
+    c.client.reduce_contribution(&alice, &40_000);
+    assert_eq!(c.client.contribution(&alice), 60_000);
+    assert_eq!(c.client.total_raised(), 60_000);
+    assert_eq!(token_client.balance(&alice), 40_000);
+    assert_eq!(c.client.contributor_count(), 1);
+
+    c.client.reduce_contribution(&alice, &60_000);
+    assert_eq!(c.client.contribution(&alice), 0);
+    assert_eq!(c.client.contributor_count(), 0);
+    assert_eq!(token_client.balance(&alice), 100_000);
+}
+
+#[test]
+fn test_reduce_contribution_rejects_excess_and_after_deadline() {
+    let c = setup_campaign();
+
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+
+    let result = c.client.try_reduce_contribution(&alice, &100_001);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::InsufficientContribution
+    );
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    let result = c.client.try_reduce_contribution(&alice, &1_000);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::CampaignEnded);
+}
+
+#[test]
+fn test_reduce_contribution_unwinds_sponsor_match() {
+    let c = setup_campaign();
+
+    let sponsor = funded_backer(&c, 100_000);
+    c.client
+        .set_matching(&c.creator, &sponsor, &5_000, &100_000);
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    assert_eq!(c.client.total_raised(), 150_000);
+
+    c.client.reduce_contribution(&alice, &40_000);
+    assert_eq!(c.client.matched_total(), 30_000);
+    assert_eq!(c.client.sponsor_balance(), 70_000);
+    assert_eq!(c.client.total_raised(), 90_000);
+
+    c.client.reduce_contribution(&alice, &60_000);
+    assert_eq!(c.client.matched_total(), 0);
+    assert_eq!(c.client.sponsor_balance(), 100_000);
+    assert_eq!(c.client.total_raised(), 0);
+    assert_eq!(c.client.solvency_delta(), 0);
+}
+
+// ── Fee Accounting Tests ───────────────────────────────────────────────────
+
+/// Initialize a campaign that charges a 5% platform fee, returning the
+/// campaign and the platform fee address.
+fn setup_campaign_with_fee() -> (Campaign, Address) {
+    setup_campaign_with_platform_fees(500, 0)
+}
+
+/// Like [`setup_campaign_with_fee`], but with custom withdrawal and entry fee
+/// rates.
+fn setup_campaign_with_platform_fees(fee_bps: u32, entry_fee_bps: u32) -> (Campaign, Address) {
+    let mut platform = None;
+    let campaign = setup_campaign_with_platform_config(|env| {
+        let address = Address::generate(env);
+        platform = Some(address.clone());
+        PlatformConfig {
+            address,
+            fee_bps,
+            entry_fee_bps,
+            fee_recipients: None,
+        }
+    });
+    (campaign, platform.unwrap())
+}
+
+/// Initialize a campaign with the platform config built by `config`, which
+/// runs against the campaign's environment.
+fn setup_campaign_with_platform_config(config: impl FnOnce(&Env) -> PlatformConfig) -> Campaign {
+    let (env, client, creator, token_address, token_admin_client) = setup_env();
+    let config = config(&env);
+
+    let admin = Address::generate(&env);
+    let goal: i128 = 1_000_000;
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &admin,
+        &creator,
+        &token_address,
+        &goal,
+        &deadline,
+        &1_000,
+        &symbol_short!("tech"),
+        &Some(config),
+        &None,
+        &None,
+    );
+
+    Campaign {
+        env,
+        client,
+        admin,
+        creator,
+        token_address,
+        token_admin_client,
+        goal,
+        deadline,
+    }
+}
+
+#[test]
+fn test_entry_fee_credits_net_contribution() {
+    let (c, platform) = setup_campaign_with_platform_fees(0, 200);
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.contribution(&alice), 98_000);
+    assert_eq!(c.client.total_raised(), 98_000);
+    assert_eq!(token_client.balance(&platform), 2_000);
+    assert_eq!(c.client.fees_collected(), 2_000);
+    assert_eq!(token_client.balance(&c.client.address), 98_000);
+}
+
+#[test]
+fn test_refund_returns_net_of_entry_fee() {
+    let (c, _platform) = setup_campaign_with_platform_fees(0, 200);
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.refund_single(&alice);
+
+    assert_eq!(token_client.balance(&alice), 98_000);
+    assert_eq!(token_client.balance(&c.client.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "platform fee and entry fee are mutually exclusive")]
+fn test_initialize_rejects_both_fee_kinds() {
+    setup_campaign_with_platform_fees(500, 200);
+}
+
+#[test]
+fn test_platform_config_returns_stored_config() {
+    let (c, platform) = setup_campaign_with_fee();
+
+    let config = c.client.platform_config().unwrap();
+    assert_eq!(config.address, platform);
+    assert_eq!(config.fee_bps, 500);
+    assert_eq!(config.entry_fee_bps, 0);
+}
+
+#[test]
+fn test_platform_config_none_without_platform_fee() {
+    let c = setup_campaign();
+
+    assert!(c.client.platform_config().is_none());
+}
+
+#[test]
+fn test_fees_collected_recorded_on_fee_collection() {
+    let (c, platform) = setup_campaign_with_fee();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    assert_eq!(c.client.fees_collected(), 0);
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+    c.client.collect_fee(&c.admin);
+
+    assert_eq!(c.client.fees_collected(), 50_000);
+    assert_eq!(token_client.balance(&platform), 50_000);
+}
+
+#[test]
+fn test_fees_collected_sums_vested_withdrawals() {
+    let (c, _platform) = setup_campaign_with_fee();
+    c.client.set_vesting(&2, &1_000);
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.withdraw_vested();
+    assert_eq!(c.client.fees_collected(), 25_000);
+
+    c.env.ledger().set_timestamp(c.deadline + 1_001);
+    c.client.withdraw_vested();
+    assert_eq!(c.client.fees_collected(), 50_000);
+}
+
+// ── Minimum Contributors Tests ─────────────────────────────────────────────
+
+#[test]
+fn test_withdraw_requires_min_contributors() {
+    let c = setup_campaign_with(CampaignOptions {
+        min_contributors: 3,
+        ..Default::default()
+    });
+    assert_eq!(c.client.min_contributors(), 3);
+
+    let whale = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&whale, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    assert!(c.client.finalize(&c.creator) == Status::Refunded);
+
+    let result = c.client.try_withdraw();
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::GoalNotReached);
+
+    // Too few backers makes the campaign refundable despite meeting the goal.
+    c.client.refund_single(&whale);
+    assert_eq!(c.client.contribution(&whale), 0);
+}
+
+#[test]
+fn test_min_contributors_defaults_to_zero() {
+    let c = setup_campaign();
+    assert_eq!(c.client.min_contributors(), 0);
+}
+
+// ── Anonymous Contribution Tests ───────────────────────────────────────────
+
+#[test]
+fn test_anonymous_contribution_hidden_from_list_but_counted() {
+    let c = setup_campaign();
+
+    let alice = funded_backer(&c, 100_000);
+    let bob = funded_backer(&c, 50_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &50_000, &None, &true, &0, &None);
+
+    let contributors = c.client.contributors();
+    assert_eq!(contributors.len(), 1);
+    assert!(!contributors.contains(&bob));
+    assert_eq!(c.client.contribution(&bob), 50_000);
+
+    let stats = c.client.get_stats();
+    assert_eq!(stats.total_raised, 150_000);
+    assert_eq!(stats.contributor_count, 2);
+    assert_eq!(stats.anonymous_count, 1);
+    assert_eq!(stats.anonymous_total, 50_000);
+}
+
+#[test]
+fn test_anonymous_backer_can_still_refund() {
+    let c = setup_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    let bob = funded_backer(&c, 50_000);
+    c.client.contribute(&bob, &50_000, &None, &true, &0, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.refund_single(&bob);
+
+    assert_eq!(token_client.balance(&bob), 50_000);
+    assert_eq!(c.client.get_stats().anonymous_total, 0);
+}
+
+// ── Separate Fee Collection Tests ──────────────────────────────────────────
+
+#[test]
+fn test_collect_fee_before_withdraw() {
+    let (c, platform) = setup_campaign_with_fee();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+
+    assert_eq!(c.client.collect_fee(&c.admin), 50_000);
+    let result = c.client.try_collect_fee(&c.admin);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::FeeAlreadyCollected
+    );
+
+    c.client.withdraw();
+    assert_eq!(token_client.balance(&platform), 50_000);
+    assert_eq!(token_client.balance(&c.creator), 10_000_000 + 950_000);
+    assert_eq!(token_client.balance(&c.client.address), 0);
+}
+
+#[test]
+fn test_withdraw_before_collect_fee() {
+    let (c, platform) = setup_campaign_with_fee();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+
+    c.client.withdraw();
+    assert_eq!(token_client.balance(&c.creator), 10_000_000 + 950_000);
+    assert_eq!(token_client.balance(&platform), 0);
+
+    assert_eq!(c.client.collect_fee(&c.admin), 50_000);
+    assert_eq!(token_client.balance(&platform), 50_000);
+    assert_eq!(token_client.balance(&c.client.address), 0);
+
+    let result = c.client.try_collect_fee(&c.admin);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::FeeAlreadyCollected
+    );
+}
+
+// ── Contributor Query Tests ────────────────────────────────────────────────
+
+#[test]
+fn test_is_contributor_tracks_current_stake() {
+    let c = setup_campaign();
+
+    let alice = funded_backer(&c, 10_000);
+    assert!(!c.client.is_contributor(&alice));
+
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    assert!(c.client.is_contributor(&alice));
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.refund_single(&alice);
+    assert!(!c.client.is_contributor(&alice));
+}
+
+// ── Contribution Deadline Tests ────────────────────────────────────────────
+
+#[test]
+fn test_contribution_deadline_closes_contributions_early() {
+    let c = setup_campaign_with(CampaignOptions {
+        contribution_deadline: Some(1_800),
+        ..Default::default()
+    });
+    assert_eq!(c.client.contribution_deadline(), 1_800);
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(1_801);
+    let bob = funded_backer(&c, 10_000);
+    let result = c
+        .client
+        .try_contribute(&bob, &10_000, &None, &false, &0, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::CampaignEnded);
+
+    // Withdrawal still waits for the main deadline.
+    let result = c.client.try_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignStillActive
+    );
+}
+
+#[test]
+fn test_contribution_deadline_defaults_to_deadline() {
+    let c = setup_campaign();
+    assert_eq!(c.client.contribution_deadline(), c.deadline);
+}
+
+// ── Overflow Claim Tests ───────────────────────────────────────────────────
+
+/// Initialize a campaign that holds back funds raised past the goal.
+fn setup_overflow_campaign() -> Campaign {
+    setup_campaign_with(CampaignOptions {
+        overflow_refund: true,
+        ..CampaignOptions::default()
+    })
+}
+
+#[test]
+fn test_claim_overflow_returns_proportional_share() {
+    let c = setup_overflow_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    let alice = funded_backer(&c, 900_000);
+    let bob = funded_backer(&c, 600_000);
+    c.client
+        .contribute(&alice, &900_000, &None, &false, &0, &None);
+    c.client
+        .contribute(&bob, &600_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+    assert_eq!(token_client.balance(&c.creator), 10_000_000 + c.goal);
+
+    // 500_000 overflow split 900:600 of 1_500_000.
+    assert_eq!(c.client.claim_overflow(&alice), 300_000);
+    assert_eq!(c.client.claim_overflow(&bob), 200_000);
+    assert_eq!(token_client.balance(&alice), 300_000);
+    assert_eq!(token_client.balance(&bob), 200_000);
+
+    let result = c.client.try_claim_overflow(&alice);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::OverflowAlreadyClaimed
+    );
+}
+
+#[test]
+fn test_claim_overflow_without_overflow_fails() {
+    let c = setup_overflow_campaign();
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+
+    let result = c.client.try_claim_overflow(&alice);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignNotSettled
+    );
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    let result = c.client.try_claim_overflow(&alice);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::NoOverflow);
+}
+
+#[test]
+fn test_overflow_paid_to_creator_by_default() {
+    let c = setup_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 1_500_000);
+    c.client
+        .contribute(&alice, &1_500_000, &None, &false, &0, &None);
+    assert!(!c.client.overflow_refund());
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+    assert_eq!(token_client.balance(&c.creator), 10_000_000 + 1_500_000);
+
+    let result = c.client.try_claim_overflow(&alice);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::NoOverflow);
+}
+
+#[test]
+fn test_claim_overflow_last_claimer_takes_dust() {
+    let c = setup_overflow_campaign();
+    let backers = [
+        funded_backer(&c, 400_000),
+        funded_backer(&c, 400_000),
+        funded_backer(&c, 400_000),
+    ];
+    for backer in backers.iter() {
+        c.client
+            .contribute(backer, &400_000, &None, &false, &0, &None);
+    }
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+    assert_eq!(c.client.overflow_pool(), 200_000);
+
+    assert_eq!(c.client.claim_overflow(&backers[0]), 66_666);
+    assert_eq!(c.client.claim_overflow(&backers[1]), 66_667);
+    assert_eq!(c.client.claim_overflow(&backers[2]), 66_667);
+    assert_eq!(c.client.overflow_pool(), 0);
+    assert_eq!(c.client.solvency_delta(), 0);
+}
+
+#[test]
+fn test_overflow_is_not_charged_the_platform_fee() {
+    let (env, client, creator, token_address, token_admin_client) = setup_env();
+    let platform = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &Address::generate(&env),
+        &creator,
+        &token_address,
+        &1_000_000,
+        &deadline,
+        &1_000,
+        &symbol_short!("tech"),
+        &Some(PlatformConfig {
+            address: platform.clone(),
+            fee_bps: 500,
+            entry_fee_bps: 0,
+            fee_recipients: None,
+        }),
+        &None,
+        &Some(CampaignOptions {
+            overflow_refund: true,
+            ..CampaignOptions::default()
+        }),
+    );
+    let alice = Address::generate(&env);
+    token_admin_client.mint(&alice, &1_500_000);
+    client.contribute(&alice, &1_500_000, &None, &false, &0, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.finalize(&creator);
+    client.withdraw();
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&creator), 10_000_000 + 950_000);
+
+    assert_eq!(client.collect_fee(&client.admin()), 50_000);
+    assert_eq!(client.claim_overflow(&alice), 500_000);
+    assert_eq!(client.solvency_delta(), 0);
+}
+
+#[test]
+fn test_overflow_share_includes_sponsor_match() {
+    let c = setup_overflow_campaign();
+    let sponsor = funded_backer(&c, 200_000);
+    c.client
+        .set_matching(&c.creator, &sponsor, &10_000, &200_000);
+    let alice = funded_backer(&c, 1_000_000);
+    c.client
+        .contribute(&alice, &1_000_000, &None, &false, &0, &None);
+    assert_eq!(c.client.total_raised(), 1_200_000);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+    assert_eq!(c.client.claim_overflow(&alice), 200_000);
+    assert_eq!(c.client.overflow_pool(), 0);
+}
+
+#[test]
+fn test_overflow_held_back_from_vested_payout() {
+    let c = setup_overflow_campaign();
+    c.client.set_vesting(&2, &1_000);
+    let alice = funded_backer(&c, 1_500_000);
+    c.client
+        .contribute(&alice, &1_500_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    assert_eq!(c.client.withdraw_vested(), 500_000);
+    assert_eq!(c.client.claim_overflow(&alice), 500_000);
+
+    c.env.ledger().set_timestamp(c.deadline + 1_001);
+    assert_eq!(c.client.withdraw_vested(), 500_000);
+    assert_eq!(c.client.total_raised(), 0);
+    assert_eq!(c.client.solvency_delta(), 0);
+}
+
+// ── Project Update Tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_post_update_appends_to_log() {
+    let c = setup_campaign();
+    c.env.ledger().set_timestamp(500);
+    c.client
+        .post_update(&String::from_str(&c.env, "Prototype shipped"));
+
+    let updates = c.client.updates(&0, &10);
+    assert_eq!(updates.len(), 1);
+    let update = updates.get(0).unwrap();
+    assert_eq!(update.timestamp, 500);
+    assert_eq!(
+        update.message,
+        String::from_str(&c.env, "Prototype shipped")
+    );
+}
+
+#[test]
+fn test_updates_are_paged_in_posting_order() {
+    let c = setup_campaign();
+    let first = String::from_str(&c.env, "Prototype shipped");
+    let second = String::from_str(&c.env, "Tooling ordered");
+    let third = String::from_str(&c.env, "Beta testers wanted");
+    c.client.post_update(&first);
+    c.client.post_update(&second);
+    c.client.post_update(&third);
+
+    assert_eq!(c.client.update_count(), 3);
+    let page = c.client.updates(&1, &5);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().message, second);
+    assert_eq!(page.get(1).unwrap().message, third);
+    assert_eq!(c.client.updates(&3, &5).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "message cannot be empty")]
+fn test_post_update_rejects_empty_message() {
+    let c = setup_campaign();
+    c.client.post_update(&String::from_str(&c.env, ""));
+}
+
+// ── Batch Contribution Tests ───────────────────────────────────────────────
+
+#[test]
+fn test_contribute_batch_credits_each_entry() {
+    let c = setup_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let processor = funded_backer(&c, 30_000);
+    let alice = Address::generate(&c.env);
+    let bob = Address::generate(&c.env);
+
+    let entries = soroban_sdk::vec![
+        &c.env,
+        (alice.clone(), 10_000i128),
+        (bob.clone(), 20_000i128),
+    ];
+    assert_eq!(c.client.contribute_batch(&processor, &entries), 30_000);
+
+    assert_eq!(c.client.contribution(&alice), 10_000);
+    assert_eq!(c.client.contribution(&bob), 20_000);
+    assert_eq!(c.client.total_raised(), 30_000);
+    assert_eq!(
+        c.client.contributors(),
+        soroban_sdk::vec![&c.env, alice, bob]
+    );
+    assert_eq!(token_client.balance(&processor), 0);
+}
+
+#[test]
+fn test_contribute_batch_rolls_back_on_invalid_entry() {
+    let c = setup_campaign_with(CampaignOptions {
+        max_contribution: Some(15_000),
+        ..Default::default()
+    });
+    let processor = funded_backer(&c, 30_000);
+    let alice = Address::generate(&c.env);
+    let bob = Address::generate(&c.env);
+
+    let entries = soroban_sdk::vec![
+        &c.env,
+        (alice.clone(), 10_000i128),
+        (bob.clone(), 20_000i128),
+    ];
+    let result = c.client.try_contribute_batch(&processor, &entries);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::MaxContributionExceeded
+    );
+
+    assert_eq!(c.client.contribution(&alice), 0);
+    assert_eq!(c.client.total_raised(), 0);
+    assert_eq!(c.client.contributors().len(), 0);
+}
+
+#[test]
+fn test_contribute_batch_keeps_anonymous_backer_anonymous() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &true, &0, &None);
+
+    let processor = funded_backer(&c, 5_000);
+    let entries = soroban_sdk::vec![&c.env, (alice.clone(), 5_000i128)];
+    c.client.contribute_batch(&processor, &entries);
+
+    assert_eq!(c.client.contribution(&alice), 15_000);
+    assert_eq!(c.client.contributors().len(), 0);
+    assert_eq!(c.client.get_stats().anonymous_total, 15_000);
+    assert!(c.client.check_invariants());
+}
+
+// ── Creator Tip Tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_tip_goes_to_creator_and_not_to_pool() {
+    let c = setup_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 105_000);
+
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &5_000, &None);
+
+    assert_eq!(c.client.total_raised(), 100_000);
+    assert_eq!(c.client.contribution(&alice), 100_000);
+    assert_eq!(c.client.total_tips(), 5_000);
+    assert_eq!(token_client.balance(&c.creator), 10_000_000 + 5_000);
+}
+
+#[test]
+fn test_tip_is_not_refunded_when_campaign_fails() {
+    let c = setup_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 105_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &5_000, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.refund_single(&alice);
+
+    assert_eq!(token_client.balance(&alice), 100_000);
+    assert_eq!(token_client.balance(&c.creator), 10_000_000 + 5_000);
+    assert_eq!(c.client.total_tips(), 5_000);
+}
+
+// ── Quadratic Funding Weight Tests ─────────────────────────────────────────
+
+#[test]
+fn test_qf_weight_with_perfect_squares() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+    let bob = funded_backer(&c, 40_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &40_000, &None, &false, &0, &None);
+
+    // (√10_000 + √40_000)² = (100 + 200)² = 90_000
+    assert_eq!(c.client.qf_weight(), 90_000);
+}
+
+#[test]
+fn test_qf_weight_favours_many_small_contributions() {
+    let c = setup_campaign();
+    for _ in 0..4 {
+        let backer = funded_backer(&c, 10_000);
+        c.client
+            .contribute(&backer, &10_000, &None, &false, &0, &None);
+    }
+    // (4 × 100)² = 160_000, versus 40_000 from a single backer.
+    assert_eq!(c.client.qf_weight(), 160_000);
+}
+
+#[test]
+fn test_qf_weight_rounds_down_irrational_roots() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 20_000);
+    c.client
+        .contribute(&alice, &20_000, &None, &false, &0, &None);
+
+    // √20_000 ≈ 141.421 at QF_SCALE precision; 141_421² / 10⁶ = 19_999.
+    assert_eq!(c.client.qf_weight(), 19_999);
+}
+
+#[test]
+fn test_qf_weight_empty_campaign() {
+    let c = setup_campaign();
+    assert_eq!(c.client.qf_weight(), 0);
+}
+
+/// Store `amounts` as listed contributions without moving tokens.
+fn seed_contributions(c: &Campaign, amounts: &[i128]) {
+    c.env.as_contract(&c.client.address, || {
+        let mut contributors = Vec::new(&c.env);
+        for amount in amounts {
+            let backer = Address::generate(&c.env);
+            c.env
+                .storage()
+                .persistent()
+                .set(&crate::DataKey::Contribution(backer.clone()), amount);
+            contributors.push_back(backer);
+        }
+        c.env
+            .storage()
+            .persistent()
+            .set(&crate::DataKey::Contributors, &contributors);
+    });
+}
+
+#[test]
+fn test_qf_weight_handles_stakes_too_large_to_scale() {
+    let c = setup_campaign();
+    let amount = i128::MAX / 2;
+    seed_contributions(&c, &[amount]);
+
+    let root = crate::isqrt(amount);
+    assert_eq!(c.client.qf_weight(), root * root);
+}
+
+#[test]
+fn test_qf_weight_saturates_past_i128_max() {
+    let c = setup_campaign();
+    seed_contributions(&c, &[i128::MAX, i128::MAX]);
+    assert_eq!(c.client.qf_weight(), i128::MAX);
+}
+
+// ── Full Campaign View Tests ───────────────────────────────────────────────
+
+#[test]
+fn test_get_campaign_full_bundles_details_and_stats() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 250_000);
+    c.client
+        .contribute(&alice, &250_000, &None, &false, &0, &None);
+
+    let full = c.client.get_campaign_full();
+    assert_eq!(full.creator, c.creator);
+    assert_eq!(full.token, c.token_address);
+    assert_eq!(full.goal, c.goal);
+    assert_eq!(full.deadline, c.deadline);
+    assert_eq!(full.total_raised, 250_000);
+    assert!(full.status == Status::Active);
+    assert_eq!(full.title, String::from_str(&c.env, ""));
+    assert_eq!(full.min_contribution, 1_000);
+    assert_eq!(full.contributor_count, 1);
+    assert_eq!(full.progress_bps, 2_500);
+}
+
+// ── Status View Tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_status_tracks_campaign_lifecycle() {
+    let c = setup_campaign();
+    assert!(c.client.status() == Status::Active);
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    assert!(c.client.status() == Status::Successful);
+}
+
+#[test]
+fn test_paused_campaign_rejects_contributions() {
+    let c = setup_campaign();
+    c.client.pause(&c.admin);
+    assert!(c.client.status() == Status::Paused);
+
+    let alice = funded_backer(&c, 10_000);
+    let result = c
+        .client
+        .try_contribute(&alice, &10_000, &None, &false, &0, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::ContractPaused);
+}
+
+#[test]
+fn test_unpause_restores_contributions() {
+    let c = setup_campaign();
+    c.client.pause(&c.admin);
+    c.client.unpause(&c.admin);
+    assert!(c.client.status() == Status::Active);
+
+    let alice = funded_backer(&c, 10_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    assert_eq!(c.client.total_raised(), 10_000);
+}
+
+#[test]
+fn test_paused_campaign_rejects_matching_and_scheduled_pledges() {
+    let c = setup_campaign();
+    let sponsor = funded_backer(&c, 100_000);
+    let alice = funded_backer(&c, 30_000);
+    c.client.pause(&c.admin);
+
+    let result = c
+        .client
+        .try_set_matching(&c.creator, &sponsor, &5_000, &100_000);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::ContractPaused);
+    let result = c.client.try_schedule_pledge(&alice, &10_000, &60, &3);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::ContractPaused);
+}
+
+#[test]
+#[should_panic(expected = "not authorized")]
+fn test_pause_rejects_non_admin() {
+    let c = setup_campaign();
+    c.client.pause(&c.creator);
+}
+
+#[test]
+#[should_panic(expected = "campaign is not paused")]
+fn test_unpause_rejects_active_campaign() {
+    let c = setup_campaign();
+    c.client.unpause(&c.admin);
+}
+
+// ── Initialization Guard Tests ─────────────────────────────────────────────
+
+#[test]
+fn test_second_initialize_fails_without_mutating_state() {
+    let c = setup_campaign();
+    let other_creator = Address::generate(&c.env);
+    let other_admin = Address::generate(&c.env);
+
+    let result = c.client.try_initialize(
+        &other_admin,
+        &other_creator,
+        &c.token_address,
+        &(c.goal * 3),
+        &(c.deadline + 7_200),
+        &5_000,
+        &symbol_short!("art"),
+        &None,
+        &None,
+        &Some(CampaignOptions {
+            bonus_goal: Some(c.goal * 4),
+            ..CampaignOptions::default()
+        }),
+    );
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::AlreadyInitialized
+    );
+
+    assert_eq!(c.client.creator(), c.creator);
+    assert_eq!(c.client.admin(), c.admin);
+    assert_eq!(c.client.goal(), c.goal);
+    assert_eq!(c.client.deadline(), c.deadline);
+    assert_eq!(c.client.min_contribution(), 1_000);
+    assert_eq!(c.client.category(), symbol_short!("tech"));
+    assert!(c.client.status() == Status::Active);
+}
+
+#[test]
+fn test_initialize_rejected_when_creator_cleared_but_initialized() {
+    let c = setup_campaign();
+
+    // Simulate an upgraded contract that lost its creator entry.
+    c.env.as_contract(&c.client.address, || {
+        c.env.storage().instance().remove(&DataKey::Creator);
+    });
+
+    let result = c.client.try_initialize(
+        &c.admin,
+        &c.creator,
+        &c.token_address,
+        &c.goal,
+        &c.deadline,
+        &1_000,
+        &symbol_short!("tech"),
+        &None,
+        &None,
+        &None,
+    );
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::AlreadyInitialized
+    );
+}
+
+// ── Scheduled Pledge Tests ─────────────────────────────────────────────────
+
+/// Fund `backer`, schedule `count` instalments of `amount` and approve the
+/// campaign to pull all of them.
+fn schedule_funded_pledge(c: &Campaign, amount: i128, interval: u64, count: u32) -> Address {
+    let total = amount * count as i128;
+    let backer = funded_backer(c, total);
+    token::Client::new(&c.env, &c.token_address).approve(
+        &backer,
+        &c.client.address,
+        &total,
+        &(c.env.ledger().sequence() + 1_000),
+    );
+    c.client
+        .schedule_pledge(&backer, &amount, &interval, &count);
+    backer
+}
+
+#[test]
+fn test_execute_pledge_credits_each_instalment() {
+    let c = setup_campaign();
+    let backer = schedule_funded_pledge(&c, 10_000, 600, 2);
+
+    c.client.execute_pledge(&backer);
+    assert_eq!(c.client.contribution(&backer), 10_000);
+    assert_eq!(c.client.total_raised(), 10_000);
+    assert!(c.client.is_contributor(&backer));
+    assert_eq!(c.client.scheduled_pledge(&backer).unwrap().remaining, 1);
+
+    c.env
+        .ledger()
+        .set_timestamp(c.env.ledger().timestamp() + 600);
+    c.client.execute_pledge(&backer);
+    assert_eq!(c.client.contribution(&backer), 20_000);
+    assert!(c.client.scheduled_pledge(&backer).is_none());
+
+    let result = c.client.try_execute_pledge(&backer);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::NoScheduledPledge
+    );
+}
+
+#[test]
+fn test_execute_pledge_rejects_before_interval() {
+    let c = setup_campaign();
+    let backer = schedule_funded_pledge(&c, 10_000, 600, 3);
+
+    c.client.execute_pledge(&backer);
+    c.env
+        .ledger()
+        .set_timestamp(c.env.ledger().timestamp() + 599);
+
+    let result = c.client.try_execute_pledge(&backer);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::PledgeNotDue);
+    assert_eq!(c.client.contribution(&backer), 10_000);
+}
+
+#[test]
+#[should_panic(expected = "pledge schedule must have a positive interval and count")]
+fn test_schedule_pledge_rejects_zero_count() {
+    let c = setup_campaign();
+    let backer = funded_backer(&c, 10_000);
+    c.client.schedule_pledge(&backer, &10_000, &600, &0);
+}
+
+#[test]
+fn test_execute_pledge_is_logged() {
+    let c = setup_campaign();
+    let backer = schedule_funded_pledge(&c, 10_000, 600, 2);
+    let now = c.env.ledger().timestamp();
+
+    c.client.execute_pledge(&backer);
+
+    let log = c.client.contribution_log(&backer);
+    assert_eq!(log.len(), 1);
+    assert_eq!(
+        log.get(0).unwrap(),
+        ContributionEntry {
+            timestamp: now,
+            amount: 10_000,
+        }
+    );
+}
+
+#[test]
+fn test_schedule_pledge_rejects_escrow_and_off_level_amounts() {
+    let c = setup_escrow_campaign();
+    let backer = funded_backer(&c, 10_000);
+    let result = c.client.try_schedule_pledge(&backer, &10_000, &600, &1);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::EscrowUnsupported
+    );
+
+    let c = setup_campaign();
+    c.client
+        .set_fixed_levels(&c.creator, &soroban_sdk::vec![&c.env, 25_000]);
+    let backer = funded_backer(&c, 10_000);
+    let result = c.client.try_schedule_pledge(&backer, &10_000, &600, &1);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::InvalidPledgeLevel
+    );
+}
+
+#[test]
+fn test_execute_pledge_respects_single_contribution() {
+    let c = setup_campaign_with(CampaignOptions {
+        single_contribution: true,
+        ..CampaignOptions::default()
+    });
+    let backer = schedule_funded_pledge(&c, 10_000, 600, 1);
+    c.client.execute_pledge(&backer);
+    assert_eq!(c.client.contribution(&backer), 10_000);
+
+    let result = c.client.try_schedule_pledge(&backer, &10_000, &600, &1);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::AlreadyContributed
+    );
+}
+
+#[test]
+#[should_panic(expected = "single-contribution campaigns allow one instalment")]
+fn test_schedule_pledge_single_contribution_rejects_instalments() {
+    let c = setup_campaign_with(CampaignOptions {
+        single_contribution: true,
+        ..CampaignOptions::default()
+    });
+    schedule_funded_pledge(&c, 10_000, 600, 2);
+}
+
+#[test]
+fn test_execute_pledge_respects_contributor_cap() {
+    let c = setup_campaign_with(CampaignOptions {
+        max_contributors: Some(1),
+        ..CampaignOptions::default()
+    });
+    let backer = schedule_funded_pledge(&c, 10_000, 600, 1);
+    let alice = funded_backer(&c, 10_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+
+    let result = c.client.try_execute_pledge(&backer);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::ContributorLimitReached
+    );
+}
+
+#[test]
+fn test_execute_pledge_rejects_before_start_time() {
+    let c = setup_campaign_with(CampaignOptions {
+        start_time: 600,
+        ..CampaignOptions::default()
+    });
+    let backer = schedule_funded_pledge(&c, 10_000, 600, 1);
+
+    let result = c.client.try_execute_pledge(&backer);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignNotStarted
+    );
+
+    c.env.ledger().set_timestamp(600);
+    c.client.execute_pledge(&backer);
+    assert_eq!(c.client.contribution(&backer), 10_000);
+}
+
+// ── Deadline View Tests ────────────────────────────────────────────────────
+
+#[test]
+fn test_time_remaining_counts_down_to_zero() {
+    let c = setup_campaign();
+    let start = c.env.ledger().timestamp();
+    assert_eq!(c.client.time_remaining(), c.deadline - start);
+    assert!(!c.client.has_ended());
+
+    c.env.ledger().set_timestamp(c.deadline);
+    assert_eq!(c.client.time_remaining(), 0);
+    assert!(!c.client.has_ended());
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    assert_eq!(c.client.time_remaining(), 0);
+    assert!(c.client.has_ended());
+}
+
+// ── Roadmap Governance Tests ───────────────────────────────────────────────
+
+/// Add one roadmap item and back the campaign with 600_000 and 400_000.
+fn setup_roadmap_vote(c: &Campaign) -> (Address, Address) {
+    c.client
+        .add_roadmap_item(&(c.deadline + 86_400), &String::from_str(&c.env, "Ship v1"));
+
+    let alice = funded_backer(c, 600_000);
+    let bob = funded_backer(c, 400_000);
+    c.client
+        .contribute(&alice, &600_000, &None, &false, &0, &None);
+    c.client
+        .contribute(&bob, &400_000, &None, &false, &0, &None);
+    (alice, bob)
+}
+
+#[test]
+fn test_roadmap_change_applies_after_weighted_majority() {
+    let c = setup_campaign();
+    let (alice, bob) = setup_roadmap_vote(&c);
+    let new_description = String::from_str(&c.env, "Ship v1 with audits");
+
+    let id = c
+        .client
+        .propose_roadmap_change(&c.creator, &0, &new_description);
+    assert_eq!(id, 0);
+
+    // 400_000 of 1_000_000 is not a majority.
+    c.client.vote(&bob, &id, &true);
+    assert!(!c.client.proposal(&id).unwrap().resolved);
+    assert_eq!(
+        c.client.roadmap().get(0).unwrap().description,
+        String::from_str(&c.env, "Ship v1")
+    );
+
+    c.client.vote(&alice, &id, &true);
+    let proposal = c.client.proposal(&id).unwrap();
+    assert!(proposal.resolved && proposal.approved);
+    assert_eq!(proposal.yes_weight, 1_000_000);
+    assert_eq!(
+        c.client.roadmap().get(0).unwrap().description,
+        new_description
+    );
+}
+
+#[test]
+fn test_roadmap_change_rejected_by_majority_no_votes() {
+    let c = setup_campaign();
+    let (alice, bob) = setup_roadmap_vote(&c);
+    let id = c
+        .client
+        .propose_roadmap_change(&c.creator, &0, &String::from_str(&c.env, "Drop v1"));
+
+    c.client.vote(&alice, &id, &false);
+    let proposal = c.client.proposal(&id).unwrap();
+    assert!(proposal.resolved && !proposal.approved);
+
+    let result = c.client.try_vote(&bob, &id, &true);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::ProposalResolved
+    );
+    assert_eq!(
+        c.client.roadmap().get(0).unwrap().description,
+        String::from_str(&c.env, "Ship v1")
+    );
+}
+
+#[test]
+fn test_vote_rejects_double_votes_and_non_contributors() {
+    let c = setup_campaign();
+    let (_alice, bob) = setup_roadmap_vote(&c);
+    let id = c
+        .client
+        .propose_roadmap_change(&c.creator, &0, &String::from_str(&c.env, "Ship v2"));
+
+    c.client.vote(&bob, &id, &true);
+    let result = c.client.try_vote(&bob, &id, &true);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::AlreadyVoted);
+
+    let outsider = Address::generate(&c.env);
+    let result = c.client.try_vote(&outsider, &id, &true);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::NotContributor);
+}
+
+#[test]
+#[should_panic(expected = "campaign is not active")]
+fn test_vote_rejected_after_withdraw() {
+    let c = setup_campaign();
+    let (alice, bob) = setup_roadmap_vote(&c);
+    let id = c
+        .client
+        .propose_roadmap_change(&c.creator, &0, &String::from_str(&c.env, "Ship v2"));
+    c.client.vote(&bob, &id, &true);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    c.client.vote(&alice, &id, &true);
+}
+
+#[test]
+#[should_panic(expected = "campaign is not active")]
+fn test_propose_roadmap_change_rejected_after_settlement() {
+    let c = setup_campaign();
+    setup_roadmap_vote(&c);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    c.client
+        .propose_roadmap_change(&c.creator, &0, &String::from_str(&c.env, "Ship v2"));
+}
+
+#[test]
+fn test_proposal_threshold_fixed_at_creation() {
+    let c = setup_campaign();
+    let (_alice, bob) = setup_roadmap_vote(&c);
+    let id = c
+        .client
+        .propose_roadmap_change(&c.creator, &0, &String::from_str(&c.env, "Ship v2"));
+    assert_eq!(c.client.proposal(&id).unwrap().total_raised, 1_000_000);
+
+    // 600_000 is a majority of the 1_000_000 base, though not of the
+    // 1_200_000 raised by the time the votes land.
+    let extra = funded_backer(&c, 200_000);
+    c.client
+        .contribute(&extra, &200_000, &None, &false, &0, &None);
+    c.client.vote(&extra, &id, &true);
+    c.client.vote(&bob, &id, &true);
+
+    let proposal = c.client.proposal(&id).unwrap();
+    assert!(proposal.resolved && proposal.approved);
+    assert_eq!(proposal.total_raised, 1_000_000);
+}
+
+#[test]
+fn test_vote_locks_stake_until_proposal_resolves() {
+    let c = setup_campaign();
+    let (alice, bob) = setup_roadmap_vote(&c);
+    let id = c
+        .client
+        .propose_roadmap_change(&c.creator, &0, &String::from_str(&c.env, "Ship v2"));
+    c.client.vote(&bob, &id, &true);
+
+    // Moving the stake would let the same funds vote again.
+    let new_wallet = Address::generate(&c.env);
+    let result = c.client.try_transfer_contribution(&bob, &new_wallet);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::StakeLocked);
+    let result = c.client.try_reduce_contribution(&bob, &100_000);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::StakeLocked);
+
+    c.client.vote(&alice, &id, &true);
+    c.client.transfer_contribution(&bob, &new_wallet);
+    assert_eq!(c.client.contribution(&new_wallet), 400_000);
+}
+
+#[test]
+fn test_flag_locks_stake() {
+    let c = setup_campaign();
+    let (alice, _bob) = setup_roadmap_vote(&c);
+    c.client.flag_campaign(&alice);
+
+    let new_wallet = Address::generate(&c.env);
+    let result = c.client.try_transfer_contribution(&alice, &new_wallet);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::StakeLocked);
+    let result = c.client.try_reduce_contribution(&alice, &100_000);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::StakeLocked);
+}
+
+// ── Unclaimed Refund Sweep Tests ───────────────────────────────────────────
+
+#[test]
+fn test_sweep_unclaimed_after_claim_window() {
+    let c = setup_campaign_with(CampaignOptions {
+        claim_window: Some(86_400),
+        ..Default::default()
+    });
+    assert_eq!(c.client.claim_window(), Some(86_400));
+
+    let alice = funded_backer(&c, 100_000);
+    let bob = funded_backer(&c, 50_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &50_000, &None, &false, &0, &None);
+
+    // Refunds still work while the window is open.
+    c.env.ledger().set_timestamp(c.deadline + 86_400);
+    c.client.finalize(&c.creator);
+    c.client.refund_single(&alice);
+    let result = c.client.try_sweep_unclaimed();
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::ClaimWindowOpen);
+
+    c.env.ledger().set_timestamp(c.deadline + 86_401);
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let creator_before = token_client.balance(&c.creator);
+    assert_eq!(c.client.sweep_unclaimed(), 50_000);
+
+    assert_eq!(token_client.balance(&c.creator), creator_before + 50_000);
+    assert_eq!(token_client.balance(&c.client.address), 0);
+    assert!(c.client.status() == Status::Cancelled);
+}
+
+#[test]
+fn test_sweep_unclaimed_takes_only_backer_stakes() {
+    let c = setup_campaign_with(CampaignOptions {
+        claim_window: Some(86_400),
+        ..Default::default()
+    });
+    let alice = funded_backer(&c, 100_000);
+    let bob = funded_backer(&c, 50_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &50_000, &None, &true, &0, &None);
+
+    // A stray transfer of the campaign token is not a backer's stake.
+    c.token_admin_client.mint(&c.client.address, &7_000);
+
+    c.env.ledger().set_timestamp(c.deadline + 86_401);
+    c.client.finalize(&c.creator);
+    assert_eq!(c.client.sweep_unclaimed(), 150_000);
+
+    assert_eq!(c.client.contribution(&alice), 0);
+    assert_eq!(c.client.contribution(&bob), 0);
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    assert_eq!(token_client.balance(&c.client.address), 7_000);
+}
+
+#[test]
+fn test_sweep_unclaimed_requires_claim_window() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1_000_000);
+    c.client.finalize(&c.creator);
+
+    let result = c.client.try_sweep_unclaimed();
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::ClaimWindowOpen);
+}
+
+// ── Token Decimals Tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_token_decimals_pinned_at_initialize() {
+    let c = setup_campaign();
+    let decimals = token::Client::new(&c.env, &c.token_address).decimals();
+    assert_eq!(decimals, 7);
+    assert_eq!(c.client.token_decimals(), decimals);
+}
+
+// ── Blocklist Tests ────────────────────────────────────────────────────────
+
+#[test]
+fn test_blocked_address_cannot_contribute_until_unblocked() {
+    let c = setup_campaign();
+    let mallory = funded_backer(&c, 20_000);
+
+    c.client.block_address(&c.admin, &mallory);
+    assert!(c.client.is_blocked(&mallory));
+
+    let result = c
+        .client
+        .try_contribute(&mallory, &10_000, &None, &false, &0, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::AddressBlocked);
+    assert_eq!(c.client.total_raised(), 0);
+
+    c.client.unblock_address(&c.admin, &mallory);
+    assert!(!c.client.is_blocked(&mallory));
+    c.client
+        .contribute(&mallory, &10_000, &None, &false, &0, &None);
+    assert_eq!(c.client.contribution(&mallory), 10_000);
+}
+
+#[test]
+#[should_panic(expected = "not authorized")]
+fn test_block_address_rejects_non_admin() {
+    let c = setup_campaign();
+    let target = Address::generate(&c.env);
+    c.client.block_address(&c.creator, &target);
+}
+
+// ── Reference Goal Tests ───────────────────────────────────────────────────
+
+#[contract]
+struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    pub fn set_price(env: Env, price: i128, timestamp: u64) {
+        env.storage()
+            .instance()
+            .set(&1u32, &PriceData { price, timestamp });
+    }
+
+    pub fn price(env: Env, _asset: Address) -> PriceData {
+        env.storage().instance().get(&1u32).unwrap()
+    }
+}
+
+/// A campaign whose goal is 1_000 reference units, priced so that one whole
+/// (10^7 base unit) token is worth 20_000 units: a 500_000 token goal.
+fn setup_reference_goal_campaign() -> (Campaign, MockOracleClient<'static>) {
+    let (env, client, creator, token_address, token_admin_client) = setup_env();
+    let oracle_id = env.register(MockOracle, ());
+    let oracle = MockOracleClient::new(&env, &oracle_id);
+
+    let admin = Address::generate(&env);
+    let goal: i128 = 1_000_000;
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &admin,
+        &creator,
+        &token_address,
+        &goal,
+        &deadline,
+        &1_000,
+        &symbol_short!("tech"),
+        &None,
+        &None,
+        &Some(CampaignOptions {
+            reference_goal: Some(ReferenceGoal {
+                oracle: oracle_id,
+                amount: 1_000,
+            }),
+            ..Default::default()
+        }),
+    );
+    oracle.set_price(&20_000, &deadline);
+
+    let c = Campaign {
+        env,
+        client,
+        admin,
+        creator,
+        token_address,
+        token_admin_client,
+        goal,
+        deadline,
+    };
+    (c, oracle)
+}
+
+#[test]
+fn test_withdraw_uses_oracle_converted_goal() {
+    let (c, _oracle) = setup_reference_goal_campaign();
+    assert_eq!(c.client.goal_in_token(), 500_000);
+
+    // Below the 1_000_000 token goal, but above the converted target.
+    let alice = funded_backer(&c, 600_000);
+    c.client
+        .contribute(&alice, &600_000, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+
+    c.client.withdraw();
+    assert!(c.client.status() == Status::Successful);
+}
+
+#[test]
+fn test_stale_price_at_finalize_fails_campaign() {
+    let (c, oracle) = setup_reference_goal_campaign();
+    let alice = funded_backer(&c, 600_000);
+    c.client
+        .contribute(&alice, &600_000, &None, &false, &0, &None);
+
+    oracle.set_price(&20_000, &0);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    assert!(c.client.finalize(&c.creator) == Status::Refunded);
+
+    // A fresh price afterwards does not revive the campaign.
+    oracle.set_price(&20_000, &(c.deadline + 1));
+    let result = c.client.try_withdraw();
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::GoalNotReached);
+
+    c.client.refund_single(&alice);
+    assert_eq!(c.client.contribution(&alice), 0);
+}
+
+#[test]
+fn test_finalize_pins_oracle_goal() {
+    let (c, oracle) = setup_reference_goal_campaign();
+    let alice = funded_backer(&c, 600_000);
+    c.client
+        .contribute(&alice, &600_000, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    assert!(c.client.finalize(&c.creator) == Status::Successful);
+
+    // Halving the price would double the token goal past the total raised.
+    oracle.set_price(&10_000, &(c.deadline + 1));
+    assert_eq!(c.client.goal_in_token(), 500_000);
+
+    c.client.withdraw();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    assert_eq!(token_client.balance(&c.creator), 10_000_000 + 600_000);
+}
+
+#[test]
+fn test_goal_in_token_rejects_zero_price() {
+    let (c, oracle) = setup_reference_goal_campaign();
+    oracle.set_price(&0, &c.deadline);
+
+    let result = c.client.try_goal_in_token();
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::InvalidPrice);
+}
+
+#[test]
+fn test_goal_in_token_without_reference_goal() {
+    let c = setup_campaign();
+    let result = c.client.try_goal_in_token();
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::NoReferenceGoal);
+}
+
+// ── Refund Status Tests ────────────────────────────────────────────────────
+
+#[test]
+fn test_refund_status_before_and_after_refund_single() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 100_000);
+    let bob = funded_backer(&c, 50_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &50_000, &None, &false, &0, &None);
+    assert_eq!(c.client.refund_status(&alice), RefundStatus::NotApplicable);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    assert_eq!(c.client.refund_status(&alice), RefundStatus::NotApplicable);
+
+    c.client.finalize(&c.creator);
+    assert_eq!(c.client.refund_status(&alice), RefundStatus::Pending);
+
+    c.client.refund_single(&alice);
+    assert_eq!(c.client.refund_status(&alice), RefundStatus::Refunded);
+    assert_eq!(c.client.refund_status(&bob), RefundStatus::Pending);
+
+    c.client.refund_single(&bob);
+    assert!(c.client.status() == Status::Refunded);
+    assert_eq!(c.client.refund_status(&bob), RefundStatus::Refunded);
+
+    let outsider = Address::generate(&c.env);
+    assert_eq!(
+        c.client.refund_status(&outsider),
+        RefundStatus::NotApplicable
+    );
+}
+
+#[test]
+fn test_refund_status_not_applicable_on_success() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+
+    assert_eq!(c.client.refund_status(&alice), RefundStatus::NotApplicable);
+}
+
+// ── Leaderboard Tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_top_contributors_sorted_with_stable_ties() {
+    let c = setup_campaign();
+    let mut backers = Vec::new(&c.env);
+    for amount in [20_000i128, 50_000, 10_000, 50_000, 30_000] {
+        let backer = funded_backer(&c, amount);
+        c.client
+            .contribute(&backer, &amount, &None, &false, &0, &None);
+        backers.push_back(backer);
+    }
+
+    let top = c.client.top_contributors(&3);
+    assert_eq!(top.len(), 3);
+    assert_eq!(top.get(0).unwrap(), (backers.get(1).unwrap(), 50_000));
+    assert_eq!(top.get(1).unwrap(), (backers.get(3).unwrap(), 50_000));
+    assert_eq!(top.get(2).unwrap(), (backers.get(4).unwrap(), 30_000));
+
+    assert_eq!(c.client.top_contributors(&100).len(), 5);
+    assert_eq!(c.client.top_contributors(&0).len(), 0);
+}
+
+// ── Invariant Check Tests ──────────────────────────────────────────────────
+
+#[test]
+fn test_check_invariants_after_contributions_and_refund() {
+    let c = setup_campaign();
+    assert!(c.client.check_invariants());
+
+    let alice = funded_backer(&c, 100_000);
+    let bob = funded_backer(&c, 50_000);
+    let carol = funded_backer(&c, 30_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &50_000, &None, &false, &0, &None);
+    c.client
+        .contribute(&carol, &30_000, &None, &true, &0, &None);
+    c.client.reduce_contribution(&bob, &20_000);
+    assert!(c.client.check_invariants());
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.refund_single(&alice);
+    assert!(c.client.check_invariants());
+}
+
+#[test]
+fn test_check_invariants_detects_storage_drift() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+
+    c.env.as_contract(&c.client.address, || {
+        c.env
+            .storage()
+            .instance()
+            .set(&crate::DataKey::TotalRaised, &90_000i128);
+    });
+    assert!(!c.client.check_invariants());
+}
+
+// ── Minimum Contribution Update Tests ──────────────────────────────────────
+
+#[test]
+fn test_set_min_contribution_applies_to_later_contributions() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 1_000);
+    c.client
+        .contribute(&alice, &1_000, &None, &false, &0, &None);
+
+    c.client.set_min_contribution(&c.creator, &100);
+    assert_eq!(c.client.min_contribution(), 100);
+    assert_eq!(c.client.contribution(&alice), 1_000);
+
+    let bob = funded_backer(&c, 100);
+    c.client.contribute(&bob, &100, &None, &false, &0, &None);
+    assert_eq!(c.client.contribution(&bob), 100);
+}
+
+#[test]
+fn test_set_min_contribution_rejects_non_positive() {
+    let c = setup_campaign();
+    let result = c.client.try_set_min_contribution(&c.creator, &0);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::InvalidMinContribution
+    );
+    assert_eq!(c.client.min_contribution(), 1_000);
+}
+
+#[test]
+fn test_set_min_contribution_rejects_minimum_above_fixed_level() {
+    let c = setup_campaign_with_levels();
+    let result = c.client.try_set_min_contribution(&c.creator, &30_000);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::InvalidMinContribution
+    );
+    assert_eq!(c.client.min_contribution(), 1_000);
+
+    c.client.set_min_contribution(&c.creator, &25_000);
+    assert_eq!(c.client.min_contribution(), 25_000);
+}
+
+#[test]
+#[should_panic(expected = "campaign is not active")]
+fn test_set_min_contribution_rejected_after_settlement() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    c.client.set_min_contribution(&c.creator, &500);
+}
+
+// ── Payout Split Tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_withdraw_splits_net_payout_between_recipients() {
+    let (c, platform) = setup_campaign_with_fee();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let partner = Address::generate(&c.env);
+    c.client.set_payout_splits(
+        &c.creator,
+        &soroban_sdk::vec![&c.env, (c.creator.clone(), 7_000), (partner.clone(), 3_000)],
+    );
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    let creator_before = token_client.balance(&c.creator);
+    c.client.withdraw();
+
+    // 5% fee stays for the platform; the remaining 950_000 is split 70/30.
+    assert_eq!(token_client.balance(&c.creator), creator_before + 665_000);
+    assert_eq!(token_client.balance(&partner), 285_000);
+    c.client.collect_fee(&c.admin);
+    assert_eq!(token_client.balance(&platform), 50_000);
+}
+
+#[test]
+#[should_panic(expected = "payout splits must sum to 10000 bps")]
+fn test_set_payout_splits_rejects_bad_total() {
+    let c = setup_campaign();
+    let partner = Address::generate(&c.env);
+    c.client.set_payout_splits(
+        &c.creator,
+        &soroban_sdk::vec![&c.env, (c.creator.clone(), 6_000), (partner, 3_000)],
+    );
+}
+
+// ── Cancellation Tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_cancel_records_reason_and_refunds_backers() {
+    let c = setup_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 100_000);
+    let anon = funded_backer(&c, 40_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.client.contribute(&anon, &40_000, &None, &true, &0, &None);
+    assert_eq!(c.client.cancel_reason(), None);
+
+    let reason = String::from_str(&c.env, "Supplier fell through");
+    c.client.cancel(&reason, &true);
+
+    assert!(c.client.status() == Status::Cancelled);
+    assert_eq!(c.client.cancel_reason(), Some(reason));
+    assert_eq!(token_client.balance(&alice), 100_000);
+
+    // Anonymous backers reclaim their stake themselves.
+    assert_eq!(c.client.refund_status(&anon), RefundStatus::Pending);
+    c.client.refund_single(&anon);
+    assert_eq!(token_client.balance(&anon), 40_000);
+    assert_eq!(c.client.total_raised(), 0);
+}
+
+#[test]
+#[should_panic(expected = "cancel reason cannot be empty")]
+fn test_cancel_rejects_empty_reason() {
+    let c = setup_campaign();
+    c.client.cancel(&String::from_str(&c.env, ""), &true);
+}
+
+#[test]
+fn test_cancel_without_auto_refund_leaves_backers_to_self_claim() {
+    let c = setup_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 100_000);
+    let bob = funded_backer(&c, 60_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &60_000, &None, &false, &0, &None);
+
+    c.client
+        .cancel(&String::from_str(&c.env, "Too many backers"), &false);
+
+    assert!(c.client.status() == Status::Cancelled);
+    assert_eq!(token_client.balance(&alice), 0);
+    assert_eq!(c.client.total_raised(), 160_000);
+    assert_eq!(c.client.refund_status(&alice), RefundStatus::Pending);
+
+    c.client.refund_single(&alice);
+    c.client.refund_single(&bob);
+    assert_eq!(token_client.balance(&alice), 100_000);
+    assert_eq!(token_client.balance(&bob), 60_000);
+    assert_eq!(c.client.total_raised(), 0);
+    assert!(c.client.status() == Status::Cancelled);
+}
+
+// ── Upgrade Tests ──────────────────────────────────────────────────────────
+
+#[test]
+#[should_panic(expected = "not authorized")]
+fn test_upgrade_rejects_non_admin() {
+    let c = setup_campaign();
+    let hash = soroban_sdk::BytesN::from_array(&c.env, &[7; 32]);
+    c.client.upgrade(&c.creator, &hash, &4);
+}
+
+#[test]
+fn test_upgrade_runs_new_code_on_existing_storage() {
+    let (env, _client, creator, token_address, token_admin_client) = setup_env();
+    let contract_id = env.register(crowdfund_wasm::WASM, ());
+    let v1 = crowdfund_wasm::Client::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 3600;
+    v1.initialize(
+        &admin,
+        &creator,
+        &token_address,
+        &1_000_000,
+        &deadline,
+        &1_000,
+        &symbol_short!("tech"),
+        &None,
+        &None,
+        &None,
+    );
+    let alice = Address::generate(&env);
+    token_admin_client.mint(&alice, &100_000);
+    v1.contribute(&alice, &100_000, &None, &false, &0, &None);
+
+    let hash = env.deployer().upload_contract_wasm(upgraded_wasm::WASM);
+    v1.upgrade(&admin, &hash, &4);
+
+    let v2 = upgraded_wasm::Client::new(&env, &contract_id);
+    assert_eq!(v2.version(), 4);
+    assert_eq!(v2.summary(), (creator, 100_000));
+
+    let history: Vec<VersionRecord> = env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .get(&crate::DataKey::VersionHistory)
+            .unwrap()
+    });
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().version, 3);
+    assert_eq!(history.get(1).unwrap().version, 4);
+}
+
+// ── New Contributor Event Tests ────────────────────────────────────────────
+
+/// Count `("campaign", "new_contributor")` events from the last invocation.
+fn new_contributor_events(c: &Campaign) -> u32 {
+    use soroban_sdk::{testutils::Events, IntoVal, Symbol, Val};
+
+    let topics: Vec<Val> = (
+        Symbol::new(&c.env, "campaign"),
+        Symbol::new(&c.env, "new_contributor"),
+    )
+        .into_val(&c.env);
+    c.env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, event_topics, _)| *event_topics == topics)
+        .count() as u32
+}
+
+#[test]
+fn test_new_contributor_event_only_on_first_contribution() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 20_000);
+
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    assert_eq!(new_contributor_events(&c), 1);
+
+    c.env
+        .ledger()
+        .set_timestamp(c.env.ledger().timestamp() + 60);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    assert_eq!(new_contributor_events(&c), 0);
+}
+
+// ── Withdraw Preview Tests ─────────────────────────────────────────────────
+
+#[test]
+fn test_can_withdraw_reports_each_blocker() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, c.goal);
+
+    let result = c.client.try_can_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignStillActive
+    );
+
+    c.env.ledger().set_timestamp(c.deadline - 100);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    let result = c.client.try_can_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignNotSettled
+    );
+
+    c.client.finalize(&c.creator);
+    c.client.can_withdraw();
+
+    // The preview moved no funds, so the real call still succeeds.
+    c.client.withdraw();
+    assert!(c.client.status() == Status::Successful);
+
+    let result = c.client.try_can_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignNotActive
+    );
+}
+
+#[test]
+fn test_can_withdraw_rejects_vested_campaign() {
+    let c = setup_campaign();
+    c.client.set_vesting(&2, &1_000);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+
+    let result = c.client.try_can_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::VestingConfigured
+    );
+}
+
+#[test]
+fn test_can_withdraw_rejects_paused_campaign() {
+    let c = setup_campaign();
+    c.client.pause(&c.admin);
+
+    let result = c.client.try_can_withdraw();
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::ContractPaused);
+}
+
+// ── Referral Tests ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_referral_volume_tallies_referred_contributions() {
+    let c = setup_campaign();
+    let referrer = Address::generate(&c.env);
+    let alice = funded_backer(&c, 30_000);
+    let bob = funded_backer(&c, 20_000);
+
+    c.client
+        .contribute(&alice, &30_000, &Some(referrer.clone()), &false, &0, &None);
+    c.client
+        .contribute(&bob, &20_000, &Some(referrer.clone()), &false, &0, &None);
+
+    assert_eq!(c.client.referral_volume(&referrer), 50_000);
+    assert_eq!(c.client.referral_volume(&alice), 0);
+}
+
+#[test]
+fn test_contribute_rejects_self_referral() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+
+    let result = c
+        .client
+        .try_contribute(&alice, &10_000, &Some(alice.clone()), &false, &0, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::SelfReferral);
+    assert_eq!(c.client.total_raised(), 0);
+}
+
+// ── Fixed Pledge Level Tests ───────────────────────────────────────────────
+
+fn setup_campaign_with_levels() -> Campaign {
+    let c = setup_campaign();
+    let mut levels = Vec::new(&c.env);
+    levels.push_back(25_000);
+    levels.push_back(50_000);
+    levels.push_back(100_000);
+    c.client.set_fixed_levels(&c.creator, &levels);
+    c
+}
+
+#[test]
+fn test_fixed_levels_accepts_matching_amount() {
+    let c = setup_campaign_with_levels();
+    let alice = funded_backer(&c, 50_000);
+
+    c.client
+        .contribute(&alice, &50_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.total_raised(), 50_000);
+    assert_eq!(c.client.fixed_levels().len(), 3);
+}
+
+#[test]
+fn test_fixed_levels_rejects_non_matching_amount() {
+    let c = setup_campaign_with_levels();
+    let alice = funded_backer(&c, 30_000);
+
+    let result = c
+        .client
+        .try_contribute(&alice, &30_000, &None, &false, &0, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::InvalidPledgeLevel
+    );
+    assert_eq!(c.client.total_raised(), 0);
+}
+
+#[test]
+fn test_clearing_fixed_levels_accepts_any_amount() {
+    let c = setup_campaign_with_levels();
+    c.client.set_fixed_levels(&c.creator, &Vec::new(&c.env));
+    let alice = funded_backer(&c, 30_000);
+
+    c.client
+        .contribute(&alice, &30_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.total_raised(), 30_000);
+    assert!(c.client.fixed_levels().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "pledge levels are locked")]
+fn test_fixed_levels_locked_after_first_contribution() {
+    let c = setup_campaign_with_levels();
+    let alice = funded_backer(&c, 25_000);
+    c.client
+        .contribute(&alice, &25_000, &None, &false, &0, &None);
+
+    c.client
+        .set_fixed_levels(&c.creator, &soroban_sdk::vec![&c.env, 30_000]);
+}
+
+#[test]
+#[should_panic(expected = "pledge level below minimum")]
+fn test_fixed_levels_rejects_level_below_minimum() {
+    let c = setup_campaign();
+    c.client
+        .set_fixed_levels(&c.creator, &soroban_sdk::vec![&c.env, 500]);
+}
+
+// ── Contribution Memo Tests ────────────────────────────────────────────────
+
+#[test]
+fn test_memo_stored_and_replaced_on_repeat_contribution() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 40_000);
+    let first = String::from_str(&c.env, "Go team!");
+    let second = String::from_str(&c.env, "Still cheering");
+
+    c.client
+        .contribute(&alice, &20_000, &None, &false, &0, &Some(first.clone()));
+    assert_eq!(c.client.memo_of(&alice), Some(first));
+
+    c.env
+        .ledger()
+        .set_timestamp(c.env.ledger().timestamp() + 60);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &Some(second.clone()));
+    assert_eq!(c.client.memo_of(&alice), Some(second.clone()));
+
+    c.env
+        .ledger()
+        .set_timestamp(c.env.ledger().timestamp() + 60);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    assert_eq!(c.client.memo_of(&alice), Some(second));
+}
+
+#[test]
+fn test_memo_of_without_memo_is_none() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.memo_of(&alice), None);
+}
+
+#[test]
+#[should_panic(expected = "memo too long")]
+fn test_contribute_rejects_oversized_memo() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+    let memo = String::from_bytes(&c.env, &[b'a'; 141]);
+
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &Some(memo));
+}
+
+// ── Roadmap Filter Tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_roadmap_due_before_and_upcoming_split_by_date() {
+    let c = setup_campaign();
+    let now = c.env.ledger().timestamp();
+    let alpha = String::from_str(&c.env, "Alpha");
+    let beta = String::from_str(&c.env, "Beta");
+    let launch = String::from_str(&c.env, "Launch");
+    c.client.add_roadmap_item(&(now + 100), &alpha);
+    c.client.add_roadmap_item(&(now + 300), &launch);
+    c.client.add_roadmap_item(&(now + 200), &beta);
+
+    let due = c.client.roadmap_due_before(&(now + 200));
+    assert_eq!(due.len(), 2);
+    assert_eq!(due.get(0).unwrap().description, alpha);
+    assert_eq!(due.get(1).unwrap().description, beta);
+
+    c.env.ledger().set_timestamp(now + 150);
+    let upcoming = c.client.roadmap_upcoming();
+    assert_eq!(upcoming.len(), 2);
+    assert_eq!(upcoming.get(0).unwrap().description, launch);
+    assert_eq!(upcoming.get(1).unwrap().description, beta);
+}
+
+#[test]
+fn test_roadmap_filters_empty_without_items() {
+    let c = setup_campaign();
+
+    assert!(c.client.roadmap_due_before(&u64::MAX).is_empty());
+    assert!(c.client.roadmap_upcoming().is_empty());
+}
+
+// ── Roadmap Editing Tests ──────────────────────────────────────────────────
+
+fn setup_campaign_with_roadmap() -> Campaign {
+    let c = setup_campaign();
+    let now = c.env.ledger().timestamp();
+    c.client
+        .add_roadmap_item(&(now + 100), &String::from_str(&c.env, "Alpha"));
+    c.client
+        .add_roadmap_item(&(now + 200), &String::from_str(&c.env, "Beta"));
+    c
+}
+
+#[test]
+fn test_remove_roadmap_item_shifts_later_items() {
+    let c = setup_campaign_with_roadmap();
+
+    c.client.remove_roadmap_item(&c.creator, &0);
+
+    let roadmap = c.client.roadmap();
+    assert_eq!(roadmap.len(), 1);
+    assert_eq!(
+        roadmap.get(0).unwrap().description,
+        String::from_str(&c.env, "Beta")
+    );
+}
+
+#[test]
+fn test_edit_roadmap_item_replaces_date_and_description() {
+    let c = setup_campaign_with_roadmap();
+    let date = c.env.ledger().timestamp() + 500;
+    let description = String::from_str(&c.env, "Alpha, delayed");
+
+    c.client
+        .edit_roadmap_item(&c.creator, &0, &date, &description);
+
+    let item = c.client.roadmap().get(0).unwrap();
+    assert_eq!(item.date, date);
+    assert_eq!(item.description, description);
+    assert_eq!(c.client.roadmap().len(), 2);
+}
+
+#[test]
+fn test_roadmap_item_out_of_range_returns_error() {
+    let c = setup_campaign_with_roadmap();
+    let date = c.env.ledger().timestamp() + 500;
+    let description = String::from_str(&c.env, "Gamma");
+
+    let result = c.client.try_remove_roadmap_item(&c.creator, &2);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::RoadmapItemNotFound
+    );
+    let result = c
+        .client
+        .try_edit_roadmap_item(&c.creator, &2, &date, &description);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::RoadmapItemNotFound
+    );
+}
+
+#[test]
+#[should_panic(expected = "date must be in the future")]
+fn test_edit_roadmap_item_rejects_past_date() {
+    let c = setup_campaign_with_roadmap();
+    let now = c.env.ledger().timestamp();
+
+    c.client
+        .edit_roadmap_item(&c.creator, &0, &now, &String::from_str(&c.env, "Alpha"));
+}
+
+#[test]
+fn test_roadmap_locked_while_proposal_open() {
+    let c = setup_campaign_with_roadmap();
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    let id = c
+        .client
+        .propose_roadmap_change(&c.creator, &1, &String::from_str(&c.env, "Beta v2"));
+
+    let date = c.env.ledger().timestamp() + 500;
+    let result = c.client.try_remove_roadmap_item(&c.creator, &0);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::ProposalOpen);
+    let result = c.client.try_edit_roadmap_item(
+        &c.creator,
+        &1,
+        &date,
+        &String::from_str(&c.env, "Beta, delayed"),
+    );
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::ProposalOpen);
+
+    c.client.vote(&alice, &id, &true);
+    assert_eq!(
+        c.client.roadmap().get(1).unwrap().description,
+        String::from_str(&c.env, "Beta v2")
+    );
+    c.client.remove_roadmap_item(&c.creator, &0);
+    assert_eq!(c.client.roadmap().len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "not authorized")]
+fn test_remove_roadmap_item_rejects_non_creator() {
+    let c = setup_campaign_with_roadmap();
+    let stranger = Address::generate(&c.env);
+
+    c.client.remove_roadmap_item(&stranger, &0);
+}
+
+// ── Early Backer Bonus Tests ───────────────────────────────────────────────
+
+#[test]
+fn test_withdraw_mints_bonus_nft_for_early_backers() {
+    let c = setup_campaign();
+    let nft_id = c.env.register(MockNftContract, ());
+    let nft_client = MockNftContractClient::new(&c.env, &nft_id);
+    c.client.set_nft_contract(&c.creator, &nft_id, &2);
+
+    let mut backers = Vec::new(&c.env);
+    for _ in 0..5 {
+        let backer = funded_backer(&c, 200_000);
+        c.client
+            .contribute(&backer, &200_000, &None, &false, &0, &None);
+        backers.push_back(backer);
+    }
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    let minted = nft_client.minted();
+    assert_eq!(minted.len(), 7);
+    for (position, backer) in backers.iter().enumerate() {
+        let count = minted.iter().filter(|record| record.to == backer).count();
+        let expected = if position < 2 { 2 } else { 1 };
+        assert_eq!(count, expected);
+    }
+}
+
+// ── Transfer Failure Tests ─────────────────────────────────────────────────
+
+#[test]
+fn test_contribute_with_rejected_transfer_returns_transfer_failed() {
+    let c = setup_campaign();
+    // The token rejects the transfer: the backer cannot cover the amount.
+    let alice = funded_backer(&c, 5_000);
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    let result = c
+        .client
+        .try_contribute(&alice, &10_000, &None, &false, &0, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::TransferFailed);
+    assert_eq!(c.client.total_raised(), 0);
+    assert_eq!(token_client.balance(&alice), 5_000);
+}
+
+#[test]
+fn test_contribute_with_rejected_tip_returns_transfer_failed() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    let result = c
+        .client
+        .try_contribute(&alice, &10_000, &None, &false, &500, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::TransferFailed);
+    assert_eq!(token_client.balance(&alice), 10_000);
+}
+
+// ── Goal Milestone Tests ───────────────────────────────────────────────────
+
+/// Percentages announced by `("campaign", "milestone")` events from the last
+/// invocation.
+fn milestone_events(c: &Campaign) -> Vec<u32> {
+    use soroban_sdk::{testutils::Events, IntoVal, Symbol, TryFromVal, Val};
+
+    let topics: Vec<Val> = (
+        Symbol::new(&c.env, "campaign"),
+        Symbol::new(&c.env, "milestone"),
+    )
+        .into_val(&c.env);
+    let mut percents = Vec::new(&c.env);
+    for (_, event_topics, data) in c.env.events().all().iter() {
+        if event_topics == topics {
+            percents.push_back(u32::try_from_val(&c.env, &data).unwrap());
+        }
+    }
+    percents
+}
+
+#[test]
+fn test_milestones_fire_once_as_quartiles_are_crossed() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 200_000);
+    let bob = funded_backer(&c, 400_000);
+    let carol = funded_backer(&c, 500_000);
+    let dave = funded_backer(&c, 100_000);
+
+    c.client
+        .contribute(&alice, &200_000, &None, &false, &0, &None);
+    assert!(milestone_events(&c).is_empty());
+
+    c.client
+        .contribute(&bob, &400_000, &None, &false, &0, &None);
+    assert_eq!(milestone_events(&c), soroban_sdk::vec![&c.env, 25, 50]);
+
+    c.client
+        .contribute(&carol, &500_000, &None, &false, &0, &None);
+    assert_eq!(milestone_events(&c), soroban_sdk::vec![&c.env, 75, 100]);
+
+    c.client
+        .contribute(&dave, &100_000, &None, &false, &0, &None);
+    assert!(milestone_events(&c).is_empty());
+}
+
+// ── Creator Bond Tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_bond_locked_at_init_and_returned_on_withdraw() {
+    let c = setup_campaign_with(CampaignOptions {
+        bond: 50_000,
+        ..CampaignOptions::default()
+    });
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    assert_eq!(c.client.bond(), 50_000);
+    assert_eq!(token_client.balance(&c.creator), 9_950_000);
+    assert_eq!(c.client.solvency_delta(), 0);
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    assert_eq!(c.client.bond(), 0);
+    assert_eq!(token_client.balance(&c.creator), 10_000_000 + c.goal);
+}
+
+#[test]
+fn test_bond_forfeited_to_platform_on_cancel() {
+    let c = setup_campaign_with(CampaignOptions {
+        bond: 50_000,
+        ..CampaignOptions::default()
+    });
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 10_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+
+    c.client
+        .cancel(&String::from_str(&c.env, "Supplier fell through"), &true);
+
+    assert_eq!(c.client.bond(), 0);
+    assert_eq!(token_client.balance(&c.admin), 50_000);
+    assert_eq!(token_client.balance(&c.creator), 9_950_000);
+    assert_eq!(token_client.balance(&alice), 10_000);
+}
+
+#[test]
+fn test_bond_returned_when_vested_campaign_settles() {
+    let c = setup_campaign_with(CampaignOptions {
+        bond: 50_000,
+        ..CampaignOptions::default()
+    });
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    c.client.set_vesting(&4, &1_000);
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    assert_eq!(c.client.withdraw_vested(), 250_000);
+    assert_eq!(c.client.bond(), 0);
+    assert_eq!(token_client.balance(&c.creator), 10_000_000 + 250_000);
+}
+
+#[test]
+fn test_bond_returned_to_creator_when_campaign_fails() {
+    let c = setup_campaign_with(CampaignOptions {
+        bond: 50_000,
+        ..CampaignOptions::default()
+    });
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 10_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    assert!(c.client.finalize(&c.creator) == Status::Refunded);
+    assert_eq!(c.client.bond(), 0);
+    assert_eq!(token_client.balance(&c.creator), 10_000_000);
+
+    c.client.refund_single(&alice);
+    assert_eq!(token_client.balance(&alice), 10_000);
+    assert_eq!(token_client.balance(&c.client.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "bond cannot be negative")]
+fn test_initialize_rejects_negative_bond() {
+    setup_campaign_with(CampaignOptions {
+        bond: -1,
+        ..CampaignOptions::default()
+    });
+}
+
+// ── Foreign Token Sweep Tests ──────────────────────────────────────────────
+
+#[test]
+fn test_sweep_foreign_token_sends_full_balance() {
+    let c = setup_campaign();
+    let issuer = Address::generate(&c.env);
+    let stray = c.env.register_stellar_asset_contract_v2(issuer).address();
+    token::StellarAssetClient::new(&c.env, &stray).mint(&c.client.address, &4_200);
+    let recipient = Address::generate(&c.env);
+
+    let swept = c.client.sweep_foreign_token(&c.admin, &stray, &recipient);
+
+    let stray_client = token::Client::new(&c.env, &stray);
+    assert_eq!(swept, 4_200);
+    assert_eq!(stray_client.balance(&recipient), 4_200);
+    assert_eq!(stray_client.balance(&c.client.address), 0);
+}
+
+#[test]
+fn test_sweep_foreign_token_rejects_campaign_token() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+
+    let result = c
+        .client
+        .try_sweep_foreign_token(&c.admin, &c.token_address, &c.admin);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignTokenNotSweepable
+    );
+    assert_eq!(c.client.token_balance(), 10_000);
+}
+
+#[test]
+#[should_panic(expected = "not authorized")]
+fn test_sweep_foreign_token_rejects_non_admin() {
+    let c = setup_campaign();
+    let issuer = Address::generate(&c.env);
+    let stray = c.env.register_stellar_asset_contract_v2(issuer).address();
+
+    c.client.sweep_foreign_token(&c.creator, &stray, &c.creator);
+}
+
+// ── Contribution Receipt Tests ─────────────────────────────────────────────
+
+#[test]
+fn test_contribute_returns_receipt() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 30_000);
+    let bob = funded_backer(&c, 50_000);
+
+    let receipt = c
+        .client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    assert_eq!(
+        receipt,
+        ContributionReceipt {
+            total_contributed: 10_000,
+            campaign_total: 10_000,
+            rank: 1,
+        }
+    );
+
+    let receipt = c.client.contribute(&bob, &50_000, &None, &false, &0, &None);
+    assert_eq!(receipt.total_contributed, 50_000);
+    assert_eq!(receipt.campaign_total, 60_000);
+    assert_eq!(receipt.rank, 2);
+
+    c.env
+        .ledger()
+        .set_timestamp(c.env.ledger().timestamp() + 60);
+    let receipt = c
+        .client
+        .contribute(&alice, &20_000, &None, &false, &0, &None);
+    assert_eq!(receipt.total_contributed, 30_000);
+    assert_eq!(receipt.campaign_total, 80_000);
+    assert_eq!(receipt.rank, 1);
+}
+
+#[test]
+fn test_anonymous_contribution_receipt_has_no_rank() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+
+    let receipt = c
+        .client
+        .contribute(&alice, &10_000, &None, &true, &0, &None);
+
+    assert_eq!(receipt.total_contributed, 10_000);
+    assert_eq!(receipt.rank, 0);
+}
+
+// ── Stretch Goal Tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_set_stretch_goal_raises_goal_and_keeps_original() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+
+    c.client.set_stretch_goal(&c.creator, &1_500_000);
+
+    assert_eq!(c.client.goal(), 1_500_000);
+    assert_eq!(c.client.original_goal(), c.goal);
+    assert_eq!(c.client.remaining_to_goal(), 500_000);
+
+    // A further stretch needs the new goal met first.
+    let result = c.client.try_set_stretch_goal(&c.creator, &1_800_000);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::GoalNotReached);
+}
+
+#[test]
+fn test_set_stretch_goal_requires_goal_met() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 500_000);
+    c.client
+        .contribute(&alice, &500_000, &None, &false, &0, &None);
+
+    let result = c.client.try_set_stretch_goal(&c.creator, &1_500_000);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::GoalNotReached);
+    assert_eq!(c.client.original_goal(), c.goal);
+}
+
+#[test]
+#[should_panic(expected = "stretch goal must exceed the current goal")]
+fn test_set_stretch_goal_rejects_lower_goal() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+
+    c.client.set_stretch_goal(&c.creator, &c.goal);
+}
+
+#[test]
+fn test_set_stretch_goal_stays_within_hard_cap() {
+    let c = setup_campaign_with(CampaignOptions {
+        hard_cap: Some(1_200_000),
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+
+    let result = c.client.try_set_stretch_goal(&c.creator, &1_500_000);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::HardCapExceeded);
+
+    c.client.set_stretch_goal(&c.creator, &1_200_000);
+    assert_eq!(c.client.goal(), 1_200_000);
+}
+
+#[test]
+fn test_set_stretch_goal_rejected_in_donation_mode() {
+    let c = setup_donation_campaign();
+    let result = c.client.try_set_stretch_goal(&c.creator, &500_000);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::DonationMode);
+    assert_eq!(c.client.goal(), 0);
+}
+
+// ── Contributor Cap Tests ──────────────────────────────────────────────────
+
+#[test]
+fn test_contributor_cap_rejects_new_backer_but_allows_top_up() {
+    let c = setup_campaign_with(CampaignOptions {
+        max_contributors: Some(2),
+        ..CampaignOptions::default()
+    });
+    assert_eq!(c.client.max_contributors(), Some(2));
+    let alice = funded_backer(&c, 20_000);
+    let bob = funded_backer(&c, 10_000);
+    let carol = funded_backer(&c, 10_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &10_000, &None, &false, &0, &None);
+
+    let result = c
+        .client
+        .try_contribute(&carol, &10_000, &None, &false, &0, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::ContributorLimitReached
+    );
+
+    c.env
+        .ledger()
+        .set_timestamp(c.env.ledger().timestamp() + 60);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    assert_eq!(c.client.contribution(&alice), 20_000);
+    assert_eq!(c.client.contributors().len(), 2);
+}
+
+#[test]
+fn test_max_contributors_none_by_default() {
+    let c = setup_campaign();
+
+    assert_eq!(c.client.max_contributors(), None);
+}
+
+// ── Donation Mode Tests ────────────────────────────────────────────────────
+
+/// Initialize a zero-goal donation drive with a one-hour deadline.
+fn setup_donation_campaign() -> Campaign {
+    let (env, client, creator, token_address, token_admin_client) = setup_env();
+
+    let admin = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &admin,
+        &creator,
+        &token_address,
+        &0,
+        &deadline,
+        &1_000,
+        &symbol_short!("tech"),
+        &None,
+        &None,
+        &None,
+    );
+
+    Campaign {
+        env,
+        client,
+        admin,
+        creator,
+        token_address,
+        token_admin_client,
+        goal: 0,
+        deadline,
+    }
+}
+
+#[test]
+fn test_donation_mode_withdraws_any_positive_total() {
+    let c = setup_donation_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    assert_eq!(c.client.get_stats().progress_bps, 0);
+
+    let alice = funded_backer(&c, 25_000);
+    c.client
+        .contribute(&alice, &25_000, &None, &false, &0, &None);
+    assert_eq!(c.client.get_stats().progress_bps, 10_000);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    assert!(c.client.status() == Status::Successful);
+    assert_eq!(token_client.balance(&c.creator), 10_000_000 + 25_000);
+}
+
+#[test]
+fn test_donation_mode_withdraw_rejects_empty_total() {
+    let c = setup_donation_campaign();
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+
+    let result = c.client.try_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::NothingToWithdraw
+    );
+}
+
+#[test]
+fn test_donation_mode_never_refunds() {
+    let c = setup_donation_campaign();
+    let alice = funded_backer(&c, 25_000);
+    c.client
+        .contribute(&alice, &25_000, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+
+    let result = c.client.try_refund_single(&alice);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::DonationMode);
+    let result = c.client.try_creator_refund_all();
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::DonationMode);
+    assert_eq!(c.client.refund_status(&alice), RefundStatus::NotApplicable);
+    assert_eq!(c.client.total_raised(), 25_000);
+}
+
+// ── Contribution Ledger Tests ──────────────────────────────────────────────
+
+#[test]
+fn test_all_contributions_includes_refunded_addresses() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 100_000);
+    let bob = funded_backer(&c, 50_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &50_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.refund_single(&bob);
+
+    let ledger = c.client.all_contributions();
+    assert_eq!(ledger.len(), 2);
+    assert_eq!(ledger.get(0).unwrap(), (alice, 100_000));
+    assert_eq!(ledger.get(1).unwrap(), (bob, 0));
+}
+
+#[test]
+fn test_all_contributions_page_returns_bounded_slices() {
+    let c = setup_campaign();
+    let mut backers = Vec::new(&c.env);
+    for i in 1..=5 {
+        let amount = 1_000 * i as i128;
+        let backer = funded_backer(&c, amount);
+        c.client
+            .contribute(&backer, &amount, &None, &false, &0, &None);
+        backers.push_back(backer);
+    }
+
+    let page = c.client.all_contributions_page(&1, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap(), (backers.get(1).unwrap(), 2_000));
+    assert_eq!(page.get(1).unwrap(), (backers.get(2).unwrap(), 3_000));
+
+    assert_eq!(c.client.all_contributions_page(&4, &10).len(), 1);
+    assert!(c.client.all_contributions_page(&5, &10).is_empty());
+}
+
+// ── Roadmap-Gated Payout Tests ─────────────────────────────────────────────
+
+/// Add four roadmap items, enable gating and fund the goal.
+fn setup_roadmap_gated_campaign() -> Campaign {
+    let c = setup_campaign();
+    for i in 1..=4u64 {
+        c.client.add_roadmap_item(
+            &(c.deadline + i * 86_400),
+            &String::from_str(&c.env, "Deliverable"),
+        );
+    }
+    c.client.set_roadmap_gating();
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c
+}
+
+#[test]
+fn test_withdraw_milestone_releases_share_of_completed_items() {
+    let c = setup_roadmap_gated_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    c.client.complete_roadmap_item(&c.creator, &0);
+    c.client.complete_roadmap_item(&c.creator, &1);
+    c.client.complete_roadmap_item(&c.creator, &2);
+    assert_eq!(c.client.roadmap_progress(), 7_500);
+
+    assert_eq!(c.client.withdraw_milestone(), 750_000);
+    assert!(c.client.status() == Status::Successful);
+    assert_eq!(token_client.balance(&c.creator), 10_000_000 + 750_000);
+
+    let result = c.client.try_withdraw_milestone();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::NothingToWithdraw
+    );
+
+    c.client.complete_roadmap_item(&c.creator, &3);
+    assert_eq!(c.client.withdraw_milestone(), 250_000);
+    assert_eq!(token_client.balance(&c.creator), 10_000_000 + c.goal);
+}
+
+#[test]
+fn test_withdraw_milestone_with_nothing_completed() {
+    let c = setup_roadmap_gated_campaign();
+
+    assert_eq!(c.client.roadmap_progress(), 0);
+    let result = c.client.try_withdraw_milestone();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::NothingToWithdraw
+    );
+}
+
+#[test]
+fn test_roadmap_gating_disables_withdraw() {
+    let c = setup_roadmap_gated_campaign();
+
+    let result = c.client.try_withdraw();
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::RoadmapGated);
+}
+
+#[test]
+fn test_withdraw_milestone_requires_gating() {
+    let c = setup_campaign();
+    c.env.ledger().set_timestamp(c.deadline + 1);
+
+    let result = c.client.try_withdraw_milestone();
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::NotRoadmapGated);
+}
+
+#[test]
+fn test_remove_roadmap_item_shifts_completion_flags() {
+    let c = setup_campaign();
+    for i in 1..=3u64 {
+        c.client.add_roadmap_item(
+            &(c.deadline + i * 86_400),
+            &String::from_str(&c.env, "Deliverable"),
+        );
+    }
+    c.client.complete_roadmap_item(&c.creator, &2);
+
+    c.client.remove_roadmap_item(&c.creator, &0);
+
+    assert_eq!(c.client.roadmap_progress(), 5_000);
+    c.client.complete_roadmap_item(&c.creator, &0);
+    assert_eq!(c.client.roadmap_progress(), 10_000);
+}
+
+// ── Campaign Duration Tests ────────────────────────────────────────────────
+
+/// Try to initialize a campaign whose deadline is `duration` seconds away.
+fn try_initialize_with_duration(duration: u64) -> Result<(), ContractError> {
+    let (env, client, creator, token_address, _) = setup_env();
+    let admin = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + duration;
+    client
+        .try_initialize(
+            &admin,
+            &creator,
+            &token_address,
+            &1_000_000,
+            &deadline,
+            &1_000,
+            &symbol_short!("tech"),
+            &None,
+            &None,
+            &None,
+        )
+        .map(|_| ())
+        .map_err(|err| err.unwrap())
+}
+
+#[test]
+fn test_initialize_accepts_minimum_duration() {
+    assert_eq!(
+        try_initialize_with_duration(crate::MIN_CAMPAIGN_DURATION),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_initialize_rejects_duration_below_minimum() {
+    assert_eq!(
+        try_initialize_with_duration(crate::MIN_CAMPAIGN_DURATION - 1),
+        Err(ContractError::DurationTooShort)
+    );
+}
+
+#[test]
+fn test_initialize_rejects_deadline_in_the_past() {
+    let (env, client, creator, token_address, _) = setup_env();
+    env.ledger().set_timestamp(10_000);
+
+    let result = client.try_initialize(
+        &Address::generate(&env),
+        &creator,
+        &token_address,
+        &1_000_000,
+        &5_000,
+        &1_000,
+        &symbol_short!("tech"),
+        &None,
+        &None,
+        &None,
+    );
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::DurationTooShort
+    );
+}
+
+#[test]
+fn test_initialize_accepts_maximum_duration() {
+    assert_eq!(
+        try_initialize_with_duration(crate::MAX_CAMPAIGN_DURATION),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_initialize_rejects_duration_above_maximum() {
+    assert_eq!(
+        try_initialize_with_duration(crate::MAX_CAMPAIGN_DURATION + 1),
+        Err(ContractError::DurationTooLong)
+    );
+}
+
+// ── Snapshot Tests ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_snapshot_survives_later_refunds() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 100_000);
+    let bob = funded_backer(&c, 50_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &50_000, &None, &false, &0, &None);
+
+    let id = c.client.snapshot(&c.creator);
+    assert_eq!(id, 0);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.refund_single(&bob);
+
+    let snapshot = c.client.get_snapshot(&id);
+    assert_eq!(snapshot.len(), 2);
+    assert_eq!(snapshot.get(0).unwrap(), (alice, 100_000));
+    assert_eq!(snapshot.get(1).unwrap(), (bob.clone(), 50_000));
+    assert_eq!(c.client.contribution(&bob), 0);
+}
+
+#[test]
+fn test_multiple_snapshots_coexist() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 100_000);
+    let bob = funded_backer(&c, 50_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    let first_taken_at = c.env.ledger().timestamp();
+    let first = c.client.snapshot(&c.creator);
+
+    c.env.ledger().set_timestamp(first_taken_at + 600);
+    c.client.contribute(&bob, &50_000, &None, &false, &0, &None);
+    let second = c.client.snapshot(&c.creator);
+
+    assert_eq!(second, 1);
+    assert_eq!(c.client.snapshot_count(), 2);
+    assert_eq!(c.client.get_snapshot(&first).len(), 1);
+    assert_eq!(c.client.get_snapshot(&second).len(), 2);
+    assert_eq!(c.client.snapshot_timestamp(&first), Some(first_taken_at));
+    assert_eq!(
+        c.client.snapshot_timestamp(&second),
+        Some(first_taken_at + 600)
+    );
+}
+
+#[test]
+fn test_get_snapshot_unknown_id_is_empty() {
+    let c = setup_campaign();
+
+    assert!(c.client.get_snapshot(&0).is_empty());
+    assert_eq!(c.client.snapshot_timestamp(&0), None);
+    assert_eq!(c.client.snapshot_count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "not authorized")]
+fn test_snapshot_rejects_non_creator() {
+    let c = setup_campaign();
+    let stranger = Address::generate(&c.env);
+
+    c.client.snapshot(&stranger);
+}
+
+// ── Platform Fee Recipient Tests ───────────────────────────────────────────
+
+/// Initialize a campaign whose `fee_bps` platform fee is split between new
+/// recipients with the given shares, returning the campaign and recipients.
+fn setup_campaign_with_fee_recipients(fee_bps: u32, shares: &[u32]) -> (Campaign, Vec<Address>) {
+    let mut recipients = None;
+    let campaign = setup_campaign_with_platform_config(|env| {
+        let mut addresses = Vec::new(env);
+        let mut split = Vec::new(env);
+        for bps in shares {
+            let recipient = Address::generate(env);
+            addresses.push_back(recipient.clone());
+            split.push_back((recipient, *bps));
+        }
+        recipients = Some(addresses);
+        PlatformConfig {
+            address: Address::generate(env),
+            fee_bps,
+            entry_fee_bps: 0,
+            fee_recipients: Some(split),
+        }
+    });
+    (campaign, recipients.unwrap())
+}
+
+/// Recipients and amounts of the `fee_transferred` events from the last call.
+fn fee_transferred_events(c: &Campaign) -> Vec<(Address, i128)> {
+    use soroban_sdk::{testutils::Events, IntoVal, Symbol, TryFromVal, Val};
+
+    let topics: Vec<Val> = (
+        Symbol::new(&c.env, "campaign"),
+        Symbol::new(&c.env, "fee_transferred"),
+    )
+        .into_val(&c.env);
+    let mut transfers = Vec::new(&c.env);
+    for (_, event_topics, data) in c.env.events().all().iter() {
+        if event_topics == topics {
+            transfers.push_back(<(Address, i128)>::try_from_val(&c.env, &data).unwrap());
+        }
+    }
+    transfers
+}
+
+#[test]
+fn test_collect_fee_splits_fee_between_recipients() {
+    let (c, recipients) = setup_campaign_with_fee_recipients(500, &[7_000, 3_000]);
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let treasury = recipients.get(0).unwrap();
+    let partner = recipients.get(1).unwrap();
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+    assert_eq!(c.client.collect_fee(&c.admin), 50_000);
+
+    assert_eq!(token_client.balance(&treasury), 35_000);
+    assert_eq!(token_client.balance(&partner), 15_000);
+    assert_eq!(c.client.fees_collected(), 50_000);
+    assert_eq!(
+        fee_transferred_events(&c),
+        soroban_sdk::vec![&c.env, (treasury, 35_000), (partner, 15_000)]
+    );
+}
+
+#[test]
+fn test_last_fee_recipient_receives_rounding_remainder() {
+    let (c, recipients) = setup_campaign_with_fee_recipients(333, &[3_333, 3_333, 3_334]);
+    let token_client = token::Client::new(&c.env, &c.token_address);
+
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+    c.client.collect_fee(&c.admin);
+
+    assert_eq!(token_client.balance(&recipients.get(0).unwrap()), 11_098);
+    assert_eq!(token_client.balance(&recipients.get(1).unwrap()), 11_098);
+    assert_eq!(token_client.balance(&recipients.get(2).unwrap()), 11_104);
+    assert_eq!(c.client.fees_collected(), 33_300);
+}
+
+#[test]
+#[should_panic(expected = "fee recipients must sum to 10000 bps")]
+fn test_initialize_rejects_fee_recipients_not_summing_to_total() {
+    setup_campaign_with_fee_recipients(500, &[7_000, 2_000]);
+}
+
+#[test]
+#[should_panic(expected = "fee recipients cannot be empty")]
+fn test_initialize_rejects_empty_fee_recipients() {
+    setup_campaign_with_fee_recipients(500, &[]);
+}
+
+// ── Escrow Tests ───────────────────────────────────────────────────────────
+
+/// Initialize a campaign that holds contributions for creator acceptance.
+fn setup_escrow_campaign() -> Campaign {
+    setup_campaign_with(CampaignOptions {
+        escrow: true,
+        ..CampaignOptions::default()
+    })
+}
+
+#[test]
+fn test_escrow_contribution_is_parked_as_pending() {
+    let c = setup_escrow_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 100_000);
+
+    let receipt = c
+        .client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+
+    assert_eq!(receipt.total_contributed, 0);
+    assert_eq!(receipt.campaign_total, 0);
+    assert_eq!(c.client.pending_contribution(&alice), 100_000);
+    assert_eq!(c.client.pending_total(), 100_000);
+    assert_eq!(c.client.contribution(&alice), 0);
+    assert_eq!(c.client.total_raised(), 0);
+    assert!(c.client.contributors().is_empty());
+    assert_eq!(token_client.balance(&c.client.address), 100_000);
+    assert_eq!(c.client.solvency_delta(), 0);
+}
+
+#[test]
+fn test_accept_contribution_credits_ledger() {
+    let c = setup_escrow_campaign();
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.accept_contribution(&c.creator, &alice), 100_000);
+
+    assert_eq!(c.client.pending_contribution(&alice), 0);
+    assert_eq!(c.client.pending_total(), 0);
+    assert_eq!(c.client.contribution(&alice), 100_000);
+    assert_eq!(c.client.total_raised(), 100_000);
+    assert_eq!(c.client.contributors(), soroban_sdk::vec![&c.env, alice]);
+    assert_eq!(c.client.solvency_delta(), 0);
+}
+
+#[test]
+fn test_reject_contribution_refunds_pending_amount() {
+    let c = setup_escrow_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.reject_contribution(&c.creator, &alice), 100_000);
+
+    assert_eq!(token_client.balance(&alice), 100_000);
+    assert_eq!(token_client.balance(&c.client.address), 0);
+    assert_eq!(c.client.pending_contribution(&alice), 0);
+    assert_eq!(c.client.total_raised(), 0);
+    assert!(c.client.contributors().is_empty());
+}
+
+#[test]
+fn test_reject_contribution_allowed_after_deadline() {
+    let c = setup_escrow_campaign();
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+
+    let result = c.client.try_accept_contribution(&c.creator, &alice);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::CampaignEnded);
+    assert_eq!(c.client.reject_contribution(&c.creator, &alice), 100_000);
+}
+
+#[test]
+fn test_accept_contribution_without_pending_fails() {
+    let c = setup_escrow_campaign();
+    let alice = Address::generate(&c.env);
+
+    let result = c.client.try_accept_contribution(&c.creator, &alice);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::NoPendingContribution
+    );
+    let result = c.client.try_reject_contribution(&c.creator, &alice);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::NoPendingContribution
+    );
+}
+
+#[test]
+#[should_panic(expected = "not authorized")]
+fn test_accept_contribution_rejects_non_creator() {
+    let c = setup_escrow_campaign();
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+
+    c.client.accept_contribution(&alice, &alice);
+}
+
+#[test]
+fn test_escrow_rejects_anonymous_and_referred_contributions() {
+    let c = setup_escrow_campaign();
+    let alice = funded_backer(&c, 100_000);
+
+    let result = c
+        .client
+        .try_contribute(&alice, &100_000, &None, &true, &0, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::EscrowUnsupported
+    );
+    let referrer = Some(Address::generate(&c.env));
+    let result = c
+        .client
+        .try_contribute(&alice, &100_000, &referrer, &false, &0, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::EscrowUnsupported
+    );
+}
+
+#[test]
+fn test_withdraw_pending_after_deadline() {
+    let c = setup_escrow_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+
+    let result = c.client.try_withdraw_pending(&alice);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignStillActive
+    );
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    assert_eq!(c.client.withdraw_pending(&alice), 100_000);
+    assert_eq!(token_client.balance(&alice), 100_000);
+    assert_eq!(c.client.pending_total(), 0);
+
+    let result = c.client.try_withdraw_pending(&alice);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::NoPendingContribution
+    );
+}
+
+#[test]
+fn test_cancel_returns_pending_contributions() {
+    let c = setup_escrow_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 100_000);
+    let bob = funded_backer(&c, 50_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &50_000, &None, &false, &0, &None);
+    c.client.accept_contribution(&c.creator, &alice);
+
+    c.client
+        .cancel(&String::from_str(&c.env, "Venue cancelled"), &true);
+
+    assert_eq!(token_client.balance(&alice), 100_000);
+    assert_eq!(token_client.balance(&bob), 50_000);
+    assert_eq!(c.client.pending_total(), 0);
+    assert_eq!(token_client.balance(&c.client.address), 0);
+}
+
+#[test]
+fn test_refund_single_returns_pending_contribution() {
+    let c = setup_escrow_campaign();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &60_000, &None, &false, &0, &None);
+    c.client.accept_contribution(&c.creator, &alice);
+    c.env
+        .ledger()
+        .set_timestamp(c.env.ledger().timestamp() + 120);
+    c.client
+        .contribute(&alice, &40_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.refund_single(&alice);
+
+    assert_eq!(token_client.balance(&alice), 100_000);
+    assert_eq!(c.client.pending_contribution(&alice), 0);
+    assert_eq!(c.client.total_raised(), 0);
+}
+
+#[test]
+fn test_contributions_credit_immediately_without_escrow() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+
+    assert!(!c.client.escrow_mode());
+    assert_eq!(c.client.pending_contribution(&alice), 0);
+    assert_eq!(c.client.contribution(&alice), 100_000);
+}
+
+// ── Withdrawal History Tests ───────────────────────────────────────────────
+
+#[test]
+fn test_withdrawal_history_accumulates_across_milestones() {
+    let c = setup_roadmap_gated_campaign();
+    let first_at = c.env.ledger().timestamp();
+
+    c.client.complete_roadmap_item(&c.creator, &0);
+    c.client.withdraw_milestone();
+
+    c.env.ledger().set_timestamp(first_at + 86_400);
+    c.client.complete_roadmap_item(&c.creator, &1);
+    c.client.complete_roadmap_item(&c.creator, &2);
+    c.client.withdraw_milestone();
+
+    c.env.ledger().set_timestamp(first_at + 2 * 86_400);
+    c.client.complete_roadmap_item(&c.creator, &3);
+    c.client.withdraw_milestone();
+
+    assert_eq!(
+        c.client.withdrawal_history(),
+        soroban_sdk::vec![
+            &c.env,
+            Withdrawal {
+                timestamp: first_at,
+                amount: 250_000,
+            },
+            Withdrawal {
+                timestamp: first_at + 86_400,
+                amount: 500_000,
+            },
+            Withdrawal {
+                timestamp: first_at + 2 * 86_400,
+                amount: 250_000,
+            },
+        ]
+    );
+    assert_eq!(c.client.total_withdrawn(), c.goal);
+}
+
+#[test]
+fn test_withdraw_records_net_payout() {
+    let (c, _platform) = setup_campaign_with_fee();
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+
+    assert!(c.client.withdrawal_history().is_empty());
+    assert_eq!(c.client.total_withdrawn(), 0);
+
+    c.client.withdraw();
+
+    let history = c.client.withdrawal_history();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0).unwrap().amount, 950_000);
+    assert_eq!(history.get(0).unwrap().timestamp, c.deadline + 1);
+    assert_eq!(c.client.total_withdrawn(), 950_000);
+}
+
+// ── Contribution Transfer Tests ────────────────────────────────────────────
+
+#[test]
+fn test_transfer_contribution_moves_balance_and_list_position() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 100_000);
+    let bob = funded_backer(&c, 50_000);
+    let carol = Address::generate(&c.env);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &50_000, &None, &false, &0, &None);
+
+    c.client.transfer_contribution(&alice, &carol);
+
+    assert_eq!(c.client.contribution(&alice), 0);
+    assert_eq!(c.client.contribution(&carol), 100_000);
+    assert_eq!(c.client.total_raised(), 150_000);
+    assert_eq!(
+        c.client.contributors(),
+        soroban_sdk::vec![&c.env, carol, bob]
+    );
+}
+
+#[test]
+fn test_transfer_contribution_merges_into_listed_recipient() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 100_000);
+    let bob = funded_backer(&c, 50_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &50_000, &None, &false, &0, &None);
+
+    c.client.transfer_contribution(&alice, &bob);
+
+    assert_eq!(c.client.contribution(&bob), 150_000);
+    assert_eq!(c.client.contributors(), soroban_sdk::vec![&c.env, bob]);
+    assert_eq!(c.client.total_raised(), 150_000);
+}
+
+#[test]
+fn test_transfer_contribution_without_contribution_fails() {
+    let c = setup_campaign();
+    let alice = Address::generate(&c.env);
+    let bob = Address::generate(&c.env);
+
+    let result = c.client.try_transfer_contribution(&alice, &bob);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::NotContributor);
+}
+
+#[test]
+#[should_panic(expected = "campaign is not active")]
+fn test_transfer_contribution_rejected_after_withdraw() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    c.client
+        .transfer_contribution(&alice, &Address::generate(&c.env));
+}
+
+#[test]
+fn test_transfer_contribution_respects_single_contribution() {
+    let c = setup_campaign_with(CampaignOptions {
+        single_contribution: true,
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, 100_000);
+    let bob = funded_backer(&c, 50_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &50_000, &None, &false, &0, &None);
+
+    let result = c.client.try_transfer_contribution(&alice, &bob);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::AlreadyContributed
+    );
+    assert_eq!(c.client.contribution(&bob), 50_000);
+
+    let new_wallet = Address::generate(&c.env);
+    c.client.transfer_contribution(&alice, &new_wallet);
+    assert_eq!(c.client.contribution(&new_wallet), 100_000);
+}
+
+#[test]
+fn test_nft_mints_to_transfer_recipient() {
+    let c = setup_campaign();
+    let nft_id = c.env.register(MockNftContract, ());
+    let nft_client = MockNftContractClient::new(&c.env, &nft_id);
+    c.client.set_nft_contract(&c.creator, &nft_id, &0);
+
+    let alice = funded_backer(&c, c.goal);
+    let new_wallet = Address::generate(&c.env);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.client.transfer_contribution(&alice, &new_wallet);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    let minted = nft_client.minted();
+    assert_eq!(minted.len(), 1);
+    assert_eq!(minted.get(0).unwrap().to, new_wallet);
+}
+
+// ── Contribution Cooldown Tests ────────────────────────────────────────────
+
+#[test]
+fn test_default_cooldown_boundary() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+    let start = c.env.ledger().timestamp();
+    c.client
+        .contribute(&alice, &1_000, &None, &false, &0, &None);
+    assert_eq!(c.client.contribution_cooldown(), 60);
+
+    c.env.ledger().set_timestamp(start + 59);
+    assert_eq!(c.client.cooldown_remaining(&alice), 1);
+    let result = c
+        .client
+        .try_contribute(&alice, &1_000, &None, &false, &0, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::RateLimitExceeded
+    );
+
+    c.env.ledger().set_timestamp(start + 60);
+    assert_eq!(c.client.cooldown_remaining(&alice), 0);
+    c.client
+        .contribute(&alice, &1_000, &None, &false, &0, &None);
+    assert_eq!(c.client.contribution(&alice), 2_000);
+}
+
+#[test]
+fn test_custom_cooldown_boundary() {
+    let c = setup_campaign_with(CampaignOptions {
+        contribution_cooldown: Some(300),
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, 10_000);
+    let start = c.env.ledger().timestamp();
+    c.client
+        .contribute(&alice, &1_000, &None, &false, &0, &None);
+    assert_eq!(c.client.cooldown_remaining(&alice), 300);
+
+    c.env.ledger().set_timestamp(start + 299);
+    assert_eq!(c.client.cooldown_remaining(&alice), 1);
+    let result = c
+        .client
+        .try_contribute(&alice, &1_000, &None, &false, &0, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::RateLimitExceeded
+    );
+
+    c.env.ledger().set_timestamp(start + 300);
+    c.client
+        .contribute(&alice, &1_000, &None, &false, &0, &None);
+    assert_eq!(c.client.cooldown_remaining(&alice), 300);
+}
+
+#[test]
+fn test_zero_cooldown_allows_back_to_back_contributions() {
+    let c = setup_campaign_with(CampaignOptions {
+        contribution_cooldown: Some(0),
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, 10_000);
+
+    c.client
+        .contribute(&alice, &1_000, &None, &false, &0, &None);
+    assert_eq!(c.client.cooldown_remaining(&alice), 0);
+    c.client
+        .contribute(&alice, &1_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.contribution(&alice), 2_000);
+}
+
+#[test]
+fn test_cooldown_remaining_for_new_address_is_zero() {
+    let c = setup_campaign();
+
+    assert_eq!(c.client.cooldown_remaining(&Address::generate(&c.env)), 0);
+}
+
+// ── Refundable Amount Tests ────────────────────────────────────────────────
+
+#[test]
+fn test_refundable_amount_matches_refund_on_failed_campaign() {
+    let (c, _platform) = setup_campaign_with_platform_fees(0, 200);
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+
+    assert_eq!(c.client.refundable_amount(&alice), 98_000);
+
+    c.client.refund_single(&alice);
+    assert_eq!(token_client.balance(&alice), 98_000);
+    assert_eq!(c.client.refundable_amount(&alice), 0);
+}
+
+#[test]
+fn test_refundable_amount_is_zero_while_active() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.refundable_amount(&alice), 0);
+}
+
+#[test]
+fn test_refundable_amount_is_zero_once_goal_met() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+
+    assert_eq!(c.client.refundable_amount(&alice), 0);
+}
+
+// ── Start Time Tests ───────────────────────────────────────────────────────
+
+#[test]
+fn test_contribute_before_start_time_fails() {
+    // The ledger starts at 0, so the campaign opens ten minutes in.
+    let start_time = 600;
+    let c = setup_campaign_with(CampaignOptions {
+        start_time,
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, 10_000);
+
+    assert!(!c.client.has_started());
+    assert_eq!(c.client.start_time(), start_time);
+    let result = c
+        .client
+        .try_contribute(&alice, &1_000, &None, &false, &0, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignNotStarted
+    );
+    assert_eq!(c.client.roadmap().len(), 0);
+
+    c.env.ledger().set_timestamp(start_time);
+    assert!(c.client.has_started());
+    c.client
+        .contribute(&alice, &1_000, &None, &false, &0, &None);
+    assert_eq!(c.client.total_raised(), 1_000);
+}
+
+#[test]
+fn test_campaign_starts_immediately_by_default() {
+    let c = setup_campaign();
+
+    assert!(c.client.has_started());
+    assert_eq!(c.client.start_time(), 0);
+}
+
+#[test]
+#[should_panic(expected = "start time must be before the deadline")]
+fn test_initialize_rejects_start_time_at_deadline() {
+    // Matches the one-hour deadline `setup_campaign_with` uses.
+    setup_campaign_with(CampaignOptions {
+        start_time: 3600,
+        ..CampaignOptions::default()
+    });
+}
+
+// ── Relayed Contribution Tests ─────────────────────────────────────────────
+
+#[test]
+fn test_contribute_with_auth_rejects_replayed_nonce() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+    assert_eq!(c.client.nonce(&alice), 0);
+
+    c.client.contribute_with_auth(&alice, &1_000, &0);
+    assert_eq!(c.client.nonce(&alice), 1);
+
+    let start = c.env.ledger().timestamp();
+    c.env.ledger().set_timestamp(start + 60);
+    let result = c.client.try_contribute_with_auth(&alice, &1_000, &0);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::InvalidNonce);
+
+    c.client.contribute_with_auth(&alice, &1_000, &1);
+    assert_eq!(c.client.nonce(&alice), 2);
+    assert_eq!(c.client.contribution(&alice), 2_000);
+}
+
+#[test]
+fn test_contribute_with_auth_rejects_future_nonce() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+
+    let result = c.client.try_contribute_with_auth(&alice, &1_000, &1);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::InvalidNonce);
+    assert_eq!(c.client.nonce(&alice), 0);
+    assert_eq!(c.client.contribution(&alice), 0);
+}
+
+// ── Refund Fee Waiver Tests ────────────────────────────────────────────────
+
+#[test]
+fn test_refund_single_never_charges_platform_fee() {
+    let (c, platform) = setup_campaign_with_fee();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+
+    c.client.refund_single(&alice);
+
+    assert_eq!(token_client.balance(&alice), 100_000);
+    assert_eq!(c.client.fees_collected(), 0);
+    assert_eq!(token_client.balance(&platform), 0);
+    assert!(c.client.status() == Status::Refunded);
+
+    let result = c.client.try_collect_fee(&c.admin);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::GoalNotReached);
+    assert_eq!(token_client.balance(&platform), 0);
+}
+
+#[test]
+fn test_creator_refund_all_never_charges_platform_fee() {
+    let (c, platform) = setup_campaign_with_fee();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 100_000);
+    let bob = funded_backer(&c, 50_000);
+    c.client
+        .contribute(&alice, &100_000, &None, &false, &0, &None);
+    c.client.contribute(&bob, &50_000, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+
+    c.client.creator_refund_all();
+
+    assert_eq!(token_client.balance(&alice), 100_000);
+    assert_eq!(token_client.balance(&bob), 50_000);
+    assert_eq!(c.client.fees_collected(), 0);
+    assert_eq!(token_client.balance(&platform), 0);
+}
+
+// ── Goal Reached Tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_goal_reached_at_exact_goal() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &(c.goal - 1_000), &None, &false, &0, &None);
+    assert!(!c.client.goal_reached());
+
+    c.env
+        .ledger()
+        .set_timestamp(c.env.ledger().timestamp() + 60);
+    c.client
+        .contribute(&alice, &1_000, &None, &false, &0, &None);
+    assert_eq!(c.client.total_raised(), c.goal);
+    assert!(c.client.goal_reached());
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    let result = c.client.try_refund_single(&alice);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::GoalReached);
+    c.client.withdraw();
+}
+
+// ── Post-Goal Window Tests ─────────────────────────────────────────────────
+
+#[test]
+fn test_post_goal_window_closes_contributions() {
+    let c = setup_campaign_with(CampaignOptions {
+        post_goal_window: Some(600),
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, c.goal);
+    let bob = funded_backer(&c, 10_000);
+    let carol = funded_backer(&c, 10_000);
+    let reached_at = c.env.ledger().timestamp();
+
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    assert_eq!(c.client.goal_reached_time(), Some(reached_at));
+    assert_eq!(c.client.contribution_deadline(), reached_at + 600);
+
+    c.env.ledger().set_timestamp(reached_at + 600);
+    c.client.contribute(&bob, &1_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(reached_at + 601);
+    assert!(c.env.ledger().timestamp() < c.deadline);
+    let result = c
+        .client
+        .try_contribute(&carol, &1_000, &None, &false, &0, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::CampaignEnded);
+    assert_eq!(c.client.total_raised(), c.goal + 1_000);
+}
+
+#[test]
+fn test_post_goal_window_waits_for_goal() {
+    let c = setup_campaign_with(CampaignOptions {
+        post_goal_window: Some(600),
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, 10_000);
+
+    c.client
+        .contribute(&alice, &1_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.goal_reached_time(), None);
+    assert_eq!(c.client.contribution_deadline(), c.deadline);
+}
+
+#[test]
+fn test_post_goal_window_never_extends_deadline() {
+    let c = setup_campaign_with(CampaignOptions {
+        post_goal_window: Some(7_200),
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, c.goal);
+
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+
+    assert_eq!(c.client.contribution_deadline(), c.deadline);
+}
+
+// ── Finalize Tests ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_finalize_resolves_successful_campaign() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+
+    assert!(c.client.finalize(&c.creator) == Status::Successful);
+    assert!(c.client.status() == Status::Successful);
+
+    c.client.withdraw();
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    assert_eq!(token_client.balance(&c.creator), c.goal);
+    assert_eq!(c.client.total_raised(), 0);
+}
+
+#[test]
+fn test_finalize_resolves_failed_campaign() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+
+    assert!(c.client.finalize(&c.creator) == Status::Refunded);
+    assert!(c.client.status() == Status::Refunded);
+    assert_eq!(c.client.refund_status(&alice), RefundStatus::Pending);
+
+    c.client.refund_single(&alice);
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    assert_eq!(token_client.balance(&alice), 10_000);
+    assert_eq!(c.client.refund_status(&alice), RefundStatus::Refunded);
+}
+
+#[test]
+fn test_finalize_rejects_before_deadline() {
+    let c = setup_campaign();
+    let result = c.client.try_finalize(&c.creator);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignStillActive
+    );
+}
+
+#[test]
+#[should_panic(expected = "not authorized")]
+fn test_finalize_rejects_non_creator() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 1_000);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&alice);
+}
+
+#[test]
+fn test_settlement_waits_for_finalize() {
+    let (c, _platform) = setup_campaign_with_fee();
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+
+    let result = c.client.try_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignNotSettled
+    );
+    let result = c.client.try_collect_fee(&c.admin);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignNotSettled
+    );
+    let result = c.client.try_refund_single(&alice);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignNotSettled
+    );
+    let result = c.client.try_creator_refund_all();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignNotSettled
+    );
+}
+
+#[test]
+fn test_finalize_leaves_successful_vested_campaign_to_tranches() {
+    let c = setup_campaign();
+    c.client.set_vesting(&2, &1_000);
+    let alice = funded_backer(&c, c.goal);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+
+    let result = c.client.try_finalize(&c.creator);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::VestingConfigured
+    );
+}
+
+#[test]
+fn test_finalize_opens_refunds_on_failed_vested_campaign() {
+    let c = setup_campaign();
+    c.client.set_vesting(&2, &1_000);
+    let alice = funded_backer(&c, 10_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+
+    assert!(c.client.finalize(&c.creator) == Status::Refunded);
+    c.client.refund_single(&alice);
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    assert_eq!(token_client.balance(&alice), 10_000);
+}
+
+#[test]
+#[should_panic(expected = "campaign is not active")]
+fn test_finalize_only_once() {
+    let c = setup_campaign();
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.finalize(&c.creator);
+}
+
+// ── Whole-Unit Minimum Tests ───────────────────────────────────────────────
+
+#[test]
+fn test_min_contribution_whole_scales_by_decimals() {
+    let c = setup_campaign_with(CampaignOptions {
+        min_contribution_whole: Some(2),
+        hard_cap: Some(100_000_000),
+        ..CampaignOptions::default()
+    });
+    assert_eq!(c.client.token_decimals(), 7);
+
+    assert_eq!(c.client.min_contribution(), 20_000_000);
+    assert_eq!(c.client.min_contribution_whole(), 2);
+
+    let alice = funded_backer(&c, 20_000_000);
+    c.client
+        .contribute(&alice, &20_000_000, &None, &false, &0, &None);
+    assert_eq!(c.client.total_raised(), 20_000_000);
+}
+
+#[test]
+#[should_panic(expected = "amount below minimum")]
+fn test_min_contribution_whole_rejects_base_unit_amounts() {
+    let c = setup_campaign_with(CampaignOptions {
+        min_contribution_whole: Some(2),
+        hard_cap: Some(100_000_000),
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, 19_999_999);
+    c.client
+        .contribute(&alice, &19_999_999, &None, &false, &0, &None);
+}
+
+#[test]
+fn test_min_contribution_whole_rounds_down_base_units() {
+    let c = setup_campaign();
+    assert_eq!(c.client.min_contribution(), 1_000);
+    assert_eq!(c.client.min_contribution_whole(), 0);
+}
+
+/// Token reporting more decimals than an `i128` can scale by.
+#[contract]
+struct WideDecimalsToken;
+
+#[contractimpl]
+impl WideDecimalsToken {
+    pub fn decimals(_env: Env) -> u32 {
+        39
+    }
+}
+
+#[test]
+fn test_min_contribution_whole_rejects_unscalable_decimals() {
+    let (env, client, creator, _, _) = setup_env();
+    let token = env.register(WideDecimalsToken, ());
+    let deadline = env.ledger().timestamp() + 3600;
+
+    let result = client.try_initialize(
+        &Address::generate(&env),
+        &creator,
+        &token,
+        &1_000_000,
+        &deadline,
+        &1_000,
+        &symbol_short!("tech"),
+        &None,
+        &None,
+        &Some(CampaignOptions {
+            min_contribution_whole: Some(1),
+            ..CampaignOptions::default()
+        }),
+    );
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::Overflow);
+}
+
+// ── Event Index Tests ──────────────────────────────────────────────────────
+
+/// Names of the `("campaign", name)` events emitted by the last call, in
+/// order.
+fn campaign_event_names(c: &Campaign) -> Vec<soroban_sdk::Symbol> {
+    use soroban_sdk::{testutils::Events, Symbol, TryFromVal};
+
+    let namespace = Symbol::new(&c.env, "campaign");
+    let mut names = Vec::new(&c.env);
+    for (_, topics, _) in c.env.events().all().iter() {
+        if let Ok(first) = Symbol::try_from_val(&c.env, &topics.get(0).unwrap()) {
+            if first == namespace {
+                names.push_back(Symbol::try_from_val(&c.env, &topics.get(1).unwrap()).unwrap());
+            }
+        }
+    }
+    names
+}
+
+/// The `(old, new)` pair of the last `status_changed` event.
+fn last_status_change(c: &Campaign) -> (Status, Status) {
+    use soroban_sdk::{testutils::Events, IntoVal, Symbol, TryFromVal, Val};
+
+    let topics: Vec<Val> = (
+        Symbol::new(&c.env, "campaign"),
+        Symbol::new(&c.env, "status_changed"),
+    )
+        .into_val(&c.env);
+    let mut change = None;
+    for (_, event_topics, data) in c.env.events().all().iter() {
+        if event_topics == topics {
+            change = Some(<(Status, Status)>::try_from_val(&c.env, &data).unwrap());
+        }
+    }
+    change.unwrap()
+}
+
+fn symbols(env: &Env, names: &[&str]) -> Vec<soroban_sdk::Symbol> {
+    let mut symbols = Vec::new(env);
+    for name in names {
+        symbols.push_back(soroban_sdk::Symbol::new(env, name));
+    }
+    symbols
+}
+
+#[test]
+fn test_events_trace_successful_lifecycle() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, c.goal);
+
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    assert_eq!(
+        campaign_event_names(&c),
+        symbols(
+            &c.env,
+            &[
+                "new_contributor",
+                "contributed",
+                "milestone",
+                "milestone",
+                "milestone",
+                "milestone",
+            ]
+        )
+    );
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    assert_eq!(
+        campaign_event_names(&c),
+        symbols(&c.env, &["status_changed", "finalized"])
+    );
+    assert!(last_status_change(&c) == (Status::Active, Status::Successful));
+
+    c.client.withdraw();
+    assert_eq!(campaign_event_names(&c), symbols(&c.env, &["withdrawn"]));
+}
+
+#[test]
+fn test_events_trace_refunds() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+
+    c.client.finalize(&c.creator);
+    assert_eq!(
+        campaign_event_names(&c),
+        symbols(&c.env, &["status_changed", "finalized"])
+    );
+    assert!(last_status_change(&c) == (Status::Active, Status::Refunded));
+
+    c.client.refund_single(&alice);
+    assert_eq!(campaign_event_names(&c), symbols(&c.env, &["refunded"]));
+}
+
+#[test]
+fn test_events_trace_cancel_with_auto_refund() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+
+    c.client
+        .cancel(&String::from_str(&c.env, "postponed"), &true);
+    assert_eq!(
+        campaign_event_names(&c),
+        symbols(&c.env, &["status_changed", "refunded", "cancelled"])
+    );
+    assert!(last_status_change(&c) == (Status::Active, Status::Cancelled));
+}
+
+// ── Arithmetic Overflow Tests ──────────────────────────────────────────────
+
+#[test]
+fn test_contribute_total_overflow_returns_error() {
+    // A donation campaign has no default hard cap to stop the total first.
+    let c = setup_donation_campaign();
+    let alice = funded_backer(&c, 1_000);
+    c.env.as_contract(&c.client.address, || {
+        c.env
+            .storage()
+            .instance()
+            .set(&crate::DataKey::TotalRaised, &(i128::MAX - 500));
+    });
+
+    let result = c
+        .client
+        .try_contribute(&alice, &1_000, &None, &false, &0, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::Overflow);
+    assert_eq!(c.client.total_raised(), i128::MAX - 500);
+}
+
+#[test]
+fn test_contribute_balance_overflow_returns_error() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 1_000);
+    c.env.as_contract(&c.client.address, || {
+        c.env.storage().persistent().set(
+            &crate::DataKey::Contribution(alice.clone()),
+            &(i128::MAX - 500),
+        );
+    });
+
+    let result = c
+        .client
+        .try_contribute(&alice, &1_000, &None, &false, &0, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::Overflow);
+}
+
+#[test]
+fn test_contribute_verified_total_overflow_returns_error() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 1_000);
+    c.client.set_verified(&c.admin, &alice, &true);
+    c.env.as_contract(&c.client.address, || {
+        c.env
+            .storage()
+            .instance()
+            .set(&crate::DataKey::VerifiedRaised, &(i128::MAX - 500));
+    });
+
+    let result = c
+        .client
+        .try_contribute(&alice, &1_000, &None, &false, &0, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::Overflow);
+    assert_eq!(c.client.total_raised(), 0);
+}
+
+#[test]
+fn test_contribute_anonymous_total_overflow_returns_error() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 1_000);
+    c.env.as_contract(&c.client.address, || {
+        c.env
+            .storage()
+            .instance()
+            .set(&crate::DataKey::AnonymousTotal, &(i128::MAX - 500));
+    });
+
+    let result = c
+        .client
+        .try_contribute(&alice, &1_000, &None, &true, &0, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::Overflow);
+    assert_eq!(c.client.total_raised(), 0);
+}
+
+#[test]
+fn test_get_stats_progress_near_i128_max() {
+    let c = setup_campaign();
+    c.env.as_contract(&c.client.address, || {
+        c.env
+            .storage()
+            .instance()
+            .set(&crate::DataKey::TotalRaised, &i128::MAX);
+    });
+    assert_eq!(c.client.get_stats().progress_bps, 10_000);
+
+    c.env.as_contract(&c.client.address, || {
+        let storage = c.env.storage().instance();
+        storage.set(&crate::DataKey::Goal, &i128::MAX);
+        storage.set(&crate::DataKey::TotalRaised, &(i128::MAX / 2));
+    });
+    assert_eq!(c.client.get_stats().progress_bps, 5_000);
+}
+
+// ── Churn Cooldown Tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_churn_cooldown_blocks_reduce_until_elapsed() {
+    let c = setup_campaign_with(CampaignOptions {
+        churn_cooldown: Some(300),
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, 10_000);
+    let start = c.env.ledger().timestamp();
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    assert_eq!(c.client.churn_cooldown(), 300);
+
+    c.env.ledger().set_timestamp(start + 299);
+    let result = c.client.try_reduce_contribution(&alice, &4_000);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::CooldownActive);
+
+    c.env.ledger().set_timestamp(start + 300);
+    c.client.reduce_contribution(&alice, &4_000);
+    assert_eq!(c.client.contribution(&alice), 6_000);
+}
+
+#[test]
+fn test_churn_cooldown_blocks_transfer() {
+    let c = setup_campaign_with(CampaignOptions {
+        churn_cooldown: Some(300),
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, 10_000);
+    let bob = Address::generate(&c.env);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+
+    let result = c.client.try_transfer_contribution(&alice, &bob);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::CooldownActive);
+}
+
+#[test]
+fn test_churn_cooldown_off_by_default() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.churn_cooldown(), 0);
+    c.client.reduce_contribution(&alice, &4_000);
+    assert_eq!(c.client.contribution(&alice), 6_000);
+}
+
+// ── NFT Batch Limit Tests ──────────────────────────────────────────────────
+
+#[test]
+fn test_withdraw_mints_up_to_nft_batch_limit() {
+    let c = setup_campaign();
+    let nft_id = c.env.register(MockNftContract, ());
+    let nft_client = MockNftContractClient::new(&c.env, &nft_id);
+    c.client.set_nft_contract(&c.creator, &nft_id, &0);
+    c.client.set_nft_batch_limit(&c.creator, &2);
+    assert_eq!(c.client.nft_batch_limit(), Some(2));
+
+    let mut backers = Vec::new(&c.env);
+    for _ in 0..3 {
+        let backer = funded_backer(&c, 400_000);
+        c.client
+            .contribute(&backer, &400_000, &None, &false, &0, &None);
+        backers.push_back(backer);
+    }
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    let minted = nft_client.minted();
+    assert_eq!(minted.len(), 2);
+    assert_eq!(minted.get(0).unwrap().to, backers.get(0).unwrap());
+    assert_eq!(minted.get(1).unwrap().to, backers.get(1).unwrap());
+    assert!(c.client.nft_claimed(&backers.get(0).unwrap()));
+    assert!(!c.client.nft_claimed(&backers.get(2).unwrap()));
+}
+
+#[test]
+fn test_claim_nft_mints_for_backers_past_batch_limit() {
+    let c = setup_campaign();
+    let nft_id = c.env.register(MockNftContract, ());
+    let nft_client = MockNftContractClient::new(&c.env, &nft_id);
+    c.client.set_nft_contract(&c.creator, &nft_id, &0);
+    c.client.set_nft_batch_limit(&c.creator, &1);
+
+    let alice = funded_backer(&c, 600_000);
+    let bob = funded_backer(&c, 400_000);
+    c.client
+        .contribute(&alice, &600_000, &None, &false, &0, &None);
+    c.client
+        .contribute(&bob, &400_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+    assert_eq!(nft_client.minted().len(), 1);
+
+    c.client.claim_nft(&bob);
+    let minted = nft_client.minted();
+    assert_eq!(minted.len(), 2);
+    assert_eq!(minted.get(1).unwrap().to, bob);
+    assert!(c.client.nft_claimed(&bob));
+
+    let result = c.client.try_claim_nft(&bob);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::NftAlreadyClaimed
+    );
+    let result = c.client.try_claim_nft(&alice);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::NftAlreadyClaimed
+    );
+}
+
+#[test]
+fn test_claim_nft_requires_successful_campaign() {
+    let c = setup_campaign();
+    let nft_id = c.env.register(MockNftContract, ());
+    c.client.set_nft_contract(&c.creator, &nft_id, &0);
+    let alice = funded_backer(&c, 10_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+
+    let result = c.client.try_claim_nft(&alice);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::CampaignNotSettled
+    );
+}
+
+#[test]
+fn test_claim_nft_rejects_non_contributor() {
+    let c = setup_campaign();
+    let nft_id = c.env.register(MockNftContract, ());
+    c.client.set_nft_contract(&c.creator, &nft_id, &0);
+    c.client.set_nft_batch_limit(&c.creator, &0);
+    let alice = funded_backer(&c, c.goal);
+    let bob = Address::generate(&c.env);
+    c.client
+        .contribute(&alice, &c.goal, &None, &false, &0, &None);
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.withdraw();
+
+    let result = c.client.try_claim_nft(&bob);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::NotContributor);
+}
+
+// ── Hard Cap Tests ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_hard_cap_rejects_overshoot_by_default() {
+    let c = setup_campaign_with(CampaignOptions {
+        hard_cap: Some(1_500_000),
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, 1_000_000);
+    let bob = funded_backer(&c, 600_000);
+    c.client
+        .contribute(&alice, &1_000_000, &None, &false, &0, &None);
+    assert!(c.client.refund_policy() == RefundPolicy::Reject);
+
+    let result = c
+        .client
+        .try_contribute(&bob, &600_000, &None, &false, &0, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::HardCapExceeded);
+    assert_eq!(c.client.total_raised(), 1_000_000);
+}
+
+#[test]
+fn test_hard_cap_refund_excess_accepts_what_fits() {
+    let c = setup_campaign_with(CampaignOptions {
+        hard_cap: Some(1_500_000),
+        refund_policy: RefundPolicy::RefundExcess,
+        ..CampaignOptions::default()
+    });
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 1_000_000);
+    let bob = funded_backer(&c, 600_000);
+    c.client
+        .contribute(&alice, &1_000_000, &None, &false, &0, &None);
+
+    let receipt = c
+        .client
+        .contribute(&bob, &600_000, &None, &false, &0, &None);
+
+    assert_eq!(receipt.total_contributed, 500_000);
+    assert_eq!(c.client.hard_cap(), Some(c.client.total_raised()));
+    assert_eq!(c.client.contribution(&bob), 500_000);
+    assert_eq!(token_client.balance(&bob), 100_000);
+    assert_eq!(token_client.balance(&c.client.address), 1_500_000);
+}
+
+#[test]
+fn test_hard_cap_refund_excess_caps_net_after_entry_fee() {
+    let (c, platform) = setup_campaign_with_platform_fees(0, 200);
+    c.env.as_contract(&c.client.address, || {
+        c.env
+            .storage()
+            .instance()
+            .set(&crate::DataKey::RefundPolicy, &RefundPolicy::RefundExcess);
+    });
+    let token_client = token::Client::new(&c.env, &c.token_address);
+    let alice = funded_backer(&c, 2_000_000);
+    let bob = funded_backer(&c, 100_000);
+    c.client
+        .contribute(&alice, &2_000_000, &None, &false, &0, &None);
+
+    // 40_000 of room is left; 40_816 gross nets exactly that after the fee.
+    c.client
+        .contribute(&bob, &100_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.contribution(&bob), 40_000);
+    assert_eq!(c.client.total_raised(), 2_000_000);
+    assert_eq!(token_client.balance(&bob), 59_184);
+    assert_eq!(token_client.balance(&platform), 40_816);
+}
+
+#[test]
+fn test_hard_cap_refund_excess_rejects_when_full() {
+    let c = setup_campaign_with(CampaignOptions {
+        hard_cap: Some(1_000_000),
+        refund_policy: RefundPolicy::RefundExcess,
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, 1_000_000);
+    let bob = funded_backer(&c, 1_000);
+    c.client
+        .contribute(&alice, &1_000_000, &None, &false, &0, &None);
+
+    let result = c
+        .client
+        .try_contribute(&bob, &1_000, &None, &false, &0, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::HardCapExceeded);
+}
+
+#[test]
+fn test_hard_cap_refund_excess_rejects_room_below_minimum() {
+    let c = setup_campaign_with(CampaignOptions {
+        hard_cap: Some(1_000_500),
+        refund_policy: RefundPolicy::RefundExcess,
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, 1_000_000);
+    let bob = funded_backer(&c, 5_000);
+    c.client
+        .contribute(&alice, &1_000_000, &None, &false, &0, &None);
+
+    // Only 500 fits, which is below the 1_000 minimum.
+    let result = c
+        .client
+        .try_contribute(&bob, &5_000, &None, &false, &0, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::HardCapExceeded);
+    assert_eq!(c.client.contribution(&bob), 0);
+}
+
+#[test]
+fn test_hard_cap_refund_excess_rejects_room_off_fixed_level() {
+    let c = setup_campaign_with(CampaignOptions {
+        hard_cap: Some(1_015_000),
+        refund_policy: RefundPolicy::RefundExcess,
+        ..CampaignOptions::default()
+    });
+    c.client
+        .set_fixed_levels(&c.creator, &soroban_sdk::vec![&c.env, 10_000, 1_000_000]);
+    let alice = funded_backer(&c, 1_010_000);
+    c.client
+        .contribute(&alice, &1_000_000, &None, &false, &0, &None);
+    c.env
+        .ledger()
+        .set_timestamp(c.env.ledger().timestamp() + 60);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+
+    // 5_000 fits, but it is not one of the fixed levels.
+    let bob = funded_backer(&c, 10_000);
+    let result = c
+        .client
+        .try_contribute(&bob, &10_000, &None, &false, &0, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::HardCapExceeded);
+}
+
+#[test]
+fn test_hard_cap_defaults_to_twice_goal() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 3_000_000);
+    assert_eq!(c.client.hard_cap(), Some(2 * c.goal));
+
+    let result = c
+        .client
+        .try_contribute(&alice, &3_000_000, &None, &false, &0, &None);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::HardCapExceeded);
+    assert_eq!(c.client.total_raised(), 0);
+}
+
+#[test]
+fn test_hard_cap_does_not_apply_to_donation_campaigns_by_default() {
+    let c = setup_donation_campaign();
+    let alice = funded_backer(&c, 25_000);
+
+    c.client
+        .contribute(&alice, &25_000, &None, &false, &0, &None);
+
+    assert_eq!(c.client.total_raised(), 25_000);
+    assert_eq!(c.client.hard_cap(), None);
+}
+
+#[test]
+fn test_hard_cap_rejects_scheduled_pledge_past_cap() {
+    let c = setup_campaign_with(CampaignOptions {
+        hard_cap: Some(1_005_000),
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, 1_000_000);
+    c.client
+        .contribute(&alice, &1_000_000, &None, &false, &0, &None);
+    let backer = schedule_funded_pledge(&c, 10_000, 600, 1);
+
+    let result = c.client.try_execute_pledge(&backer);
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::HardCapExceeded);
+    assert_eq!(c.client.total_raised(), 1_000_000);
+}
+
+#[test]
+fn test_initialize_rejects_hard_cap_below_goal() {
+    let (env, client, creator, token_address, _) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+
+    let result = client.try_initialize(
+        &Address::generate(&env),
+        &creator,
+        &token_address,
+        &1_000_000,
+        &deadline,
+        &1_000,
+        &symbol_short!("tech"),
+        &None,
+        &None,
+        &Some(CampaignOptions {
+            hard_cap: Some(999_999),
+            ..CampaignOptions::default()
+        }),
+    );
+    assert_eq!(result.unwrap_err().unwrap(), ContractError::InvalidHardCap);
+}
+
+// ── Version History Tests ──────────────────────────────────────────────────
+
+#[test]
+fn test_initialize_records_version() {
+    let c = setup_campaign();
+    assert_eq!(
+        c.client.version_history(),
+        soroban_sdk::vec![
+            &c.env,
+            VersionRecord {
+                version: 3,
+                timestamp: c.env.ledger().timestamp(),
+            }
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "version must increase")]
+fn test_upgrade_rejects_non_increasing_version() {
+    let c = setup_campaign();
+    let hash = soroban_sdk::BytesN::from_array(&c.env, &[7; 32]);
+    c.client.upgrade(&c.admin, &hash, &3);
+}
+
+// ── Single Contribution Tests ──────────────────────────────────────────────
+
+#[test]
+fn test_single_contribution_rejects_repeat() {
+    let c = setup_campaign_with(CampaignOptions {
+        single_contribution: true,
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, 20_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    assert!(c.client.single_contribution());
+
+    c.env
+        .ledger()
+        .set_timestamp(c.env.ledger().timestamp() + 120);
+    let result = c
+        .client
+        .try_contribute(&alice, &10_000, &None, &false, &0, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        ContractError::AlreadyContributed
+    );
+    assert_eq!(c.client.contribution(&alice), 10_000);
+}
+
+#[test]
+fn test_repeat_contributions_allowed_by_default() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 20_000);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    assert!(!c.client.single_contribution());
+
+    c.env
+        .ledger()
+        .set_timestamp(c.env.ledger().timestamp() + 120);
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+    assert_eq!(c.client.contribution(&alice), 20_000);
+    assert_eq!(c.client.contributor_count(), 1);
+}
+
+// ── Contribution Log Tests ─────────────────────────────────────────────────
+
+#[test]
+fn test_contribution_log_records_each_contribution() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 30_000);
+    let start = c.env.ledger().timestamp();
+
+    for (offset, amount) in [(0, 5_000), (100, 10_000), (200, 15_000)] {
+        c.env.ledger().set_timestamp(start + offset);
+        c.client
+            .contribute(&alice, &amount, &None, &false, &0, &None);
+    }
+
+    let log = c.client.contribution_log(&alice);
+    assert_eq!(log.len(), 3);
+    assert_eq!(
+        log.get(1).unwrap(),
+        ContributionEntry {
+            timestamp: start + 100,
+            amount: 10_000,
+        }
+    );
+    assert_eq!(
+        c.client
+            .contributions_in_range(&alice, &(start + 50), &(start + 200)),
+        25_000
+    );
+    assert_eq!(
+        c.client.contributions_in_range(&alice, &start, &start),
+        5_000
+    );
+    assert_eq!(
+        c.client
+            .contributions_in_range(&alice, &(start + 201), &(start + 3_000)),
+        0
+    );
+}
+
+#[test]
+fn test_contribution_log_is_bounded() {
+    let c = setup_campaign_with(CampaignOptions {
+        contribution_cooldown: Some(0),
+        ..CampaignOptions::default()
+    });
+    let alice = funded_backer(&c, 51_000);
+    let start = c.env.ledger().timestamp();
+
+    for offset in 0..51 {
+        c.env.ledger().set_timestamp(start + offset);
+        c.client
+            .contribute(&alice, &1_000, &None, &false, &0, &None);
+    }
+
+    let log = c.client.contribution_log(&alice);
+    assert_eq!(log.len(), 50);
+    assert_eq!(log.get(0).unwrap().timestamp, start + 1);
+    assert_eq!(c.client.contribution(&alice), 51_000);
+}
+
+#[test]
+fn test_contribution_log_records_reductions_and_refunds() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 30_000);
+    let start = c.env.ledger().timestamp();
+    c.client
+        .contribute(&alice, &30_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(start + 100);
+    c.client.reduce_contribution(&alice, &10_000);
+    assert_eq!(
+        c.client
+            .contributions_in_range(&alice, &start, &(start + 100)),
+        c.client.contribution(&alice)
+    );
+
+    c.env.ledger().set_timestamp(c.deadline + 1);
+    c.client.finalize(&c.creator);
+    c.client.refund_single(&alice);
+
+    let log = c.client.contribution_log(&alice);
+    assert_eq!(log.len(), 3);
+    assert_eq!(log.get(1).unwrap().amount, -10_000);
+    assert_eq!(log.get(2).unwrap().amount, -20_000);
+    assert_eq!(
+        c.client
+            .contributions_in_range(&alice, &start, &(c.deadline + 1)),
+        0
+    );
+}
+
+#[test]
+fn test_contribution_log_records_transfers_on_both_sides() {
+    let c = setup_campaign();
+    let alice = funded_backer(&c, 10_000);
+    let new_wallet = Address::generate(&c.env);
+    let start = c.env.ledger().timestamp();
+    c.client
+        .contribute(&alice, &10_000, &None, &false, &0, &None);
+
+    c.env.ledger().set_timestamp(start + 100);
+    c.client.transfer_contribution(&alice, &new_wallet);
+
+    assert_eq!(c.client.contribution_log(&alice).len(), 2);
+    assert_eq!(
+        c.client
+            .contributions_in_range(&alice, &start, &(start + 100)),
+        0
+    );
+    assert_eq!(
+        c.client.contribution_log(&new_wallet),
+        soroban_sdk::vec![
+            &c.env,
+            ContributionEntry {
+                timestamp: start + 100,
+                amount: 10_000,
+            }
+        ]
+    );
+}
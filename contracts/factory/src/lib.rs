@@ -1,17 +1,78 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, Address, BytesN, Env, IntoVal, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, Address, BytesN, Env, IntoVal, Symbol, Vec,
 };
 
 #[cfg(test)]
 mod test;
 
+/// Default shortest window (in seconds) a new campaign may run for. Matches
+/// the crowdfund contract's own floor, so configured bounds cannot go lower.
+const DEFAULT_MIN_DURATION: u64 = 3_600;
+/// Default longest window (in seconds) a new campaign may run for. Matches
+/// the crowdfund contract's own ceiling, so configured bounds cannot go higher.
+const DEFAULT_MAX_DURATION: u64 = 365 * 24 * 3_600;
+
+/// Mirror of the crowdfund contract's `Status`; the variants must match so
+/// values reported by campaigns decode here.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum CampaignStatus {
+    Active,
+    Successful,
+    Refunded,
+    Cancelled,
+    Paused,
+}
+
+/// Campaign counts by status across the active registry and the archive.
+///
+/// Paused campaigns count as `active`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FactoryStats {
+    pub total_campaigns: u32,
+    pub successful: u32,
+    pub refunded: u32,
+    pub cancelled: u32,
+    pub active: u32,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
-    /// List of all deployed campaign addresses.
+    /// List of active (non-archived) campaign addresses.
     Campaigns,
+    /// Campaign addresses currently in the given status.
+    StatusBucket(CampaignStatus),
+    /// Campaign addresses tagged with the given category.
+    CategoryIndex(Symbol),
+    /// Factory administrator allowed to curate the registry.
+    Admin,
+    /// Campaign addresses removed from the active registry.
+    Archived,
+    /// Net contributions reported by campaigns, across the whole registry.
+    AggregateRaised,
+    /// Shortest campaign window accepted by `create_campaign`, in seconds.
+    MinDuration,
+    /// Longest campaign window accepted by `create_campaign`, in seconds.
+    MaxDuration,
+    /// Whether `create_campaign` is halted by the admin.
+    FactoryPaused,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FactoryError {
+    NotRegistered = 1,
+    AlreadyInitialized = 2,
+    DurationTooShort = 3,
+    DurationTooLong = 4,
+    FactoryPaused = 5,
+    NotInitialized = 6,
+    InvalidDurationBounds = 7,
 }
 
 #[contract]
@@ -19,6 +80,16 @@ pub struct FactoryContract;
 
 #[contractimpl]
 impl FactoryContract {
+    /// Set the factory administrator. Can only be called once.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), FactoryError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(FactoryError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
     /// Deploy a new crowdfund campaign contract.
     ///
     /// # Arguments
@@ -26,20 +97,43 @@ impl FactoryContract {
     /// * `token`     – The token contract address used for contributions.
     /// * `goal`      – The funding goal (in the token's smallest unit).
     /// * `deadline`  – The campaign deadline as a ledger timestamp.
+    /// * `category`  – The browsing category, e.g. `art` or `tech`.
     /// * `wasm_hash` – The hash of the crowdfund contract WASM to deploy.
     ///
     /// # Returns
-    /// The address of the newly deployed campaign contract.
+    /// The address of the newly deployed campaign contract, `FactoryPaused`
+    /// while the admin has halted creation, `NotInitialized` before the
+    /// factory has an admin, or `DurationTooShort` / `DurationTooLong` if
+    /// `deadline` falls outside the configured duration bounds.
     pub fn create_campaign(
         env: Env,
         creator: Address,
         token: Address,
         goal: i128,
         deadline: u64,
+        category: Symbol,
         wasm_hash: BytesN<32>,
-    ) -> Address {
+    ) -> Result<Address, FactoryError> {
+        if Self::is_factory_paused(env.clone()) {
+            return Err(FactoryError::FactoryPaused);
+        }
+
+        let duration = deadline.saturating_sub(env.ledger().timestamp());
+        if duration < Self::min_duration(env.clone()) {
+            return Err(FactoryError::DurationTooShort);
+        }
+        if duration > Self::max_duration(env.clone()) {
+            return Err(FactoryError::DurationTooLong);
+        }
+
         creator.require_auth();
 
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(FactoryError::NotInitialized)?;
+
         // Deploy the crowdfund contract from the WASM hash.
         let salt = BytesN::from_array(&env, &[0; 32]);
         let deployed_address = env
@@ -49,19 +143,27 @@ impl FactoryContract {
 
         // Initialize the deployed contract.
         // Keep factory API stable: use default min contribution and no platform config.
+        // The factory admin becomes the campaign's platform admin, and the
+        // factory is registered for status callbacks.
         let min_contribution: i128 = 1_000;
         let no_platform_config: Option<soroban_sdk::Val> = None;
+        let no_options: Option<soroban_sdk::Val> = None;
+        let factory = Some(env.current_contract_address());
         let _: () = env.invoke_contract(
             &deployed_address,
             &Symbol::new(&env, "initialize"),
             soroban_sdk::vec![
                 &env,
+                admin.into_val(&env),
                 creator.into_val(&env),
                 token.into_val(&env),
                 goal.into_val(&env),
                 deadline.into_val(&env),
                 min_contribution.into_val(&env),
+                category.into_val(&env),
                 no_platform_config.into_val(&env),
+                factory.into_val(&env),
+                no_options.into_val(&env),
             ],
         );
 
@@ -76,18 +178,350 @@ impl FactoryContract {
             .instance()
             .set(&DataKey::Campaigns, &campaigns);
 
-        deployed_address
+        let active_key = DataKey::StatusBucket(CampaignStatus::Active);
+        let mut active: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&active_key)
+            .unwrap_or(Vec::new(&env));
+        active.push_back(deployed_address.clone());
+        env.storage().instance().set(&active_key, &active);
+
+        let category_key = DataKey::CategoryIndex(category);
+        let mut in_category: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&category_key)
+            .unwrap_or(Vec::new(&env));
+        in_category.push_back(deployed_address.clone());
+        env.storage().instance().set(&category_key, &in_category);
+
+        Ok(deployed_address)
+    }
+
+    /// Halt new campaign creation, e.g. during maintenance or an incident.
+    ///
+    /// Only the factory admin may pause. Existing campaigns are unaffected.
+    pub fn pause_factory(env: Env, admin: Address) {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("not authorized");
+        }
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::FactoryPaused, &true);
+        env.events().publish(("factory", "paused"), admin);
+    }
+
+    /// Resume new campaign creation after `pause_factory`.
+    pub fn unpause_factory(env: Env, admin: Address) {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("not authorized");
+        }
+        admin.require_auth();
+
+        env.storage().instance().remove(&DataKey::FactoryPaused);
+        env.events().publish(("factory", "unpaused"), admin);
+    }
+
+    /// Returns whether new campaign creation is halted.
+    pub fn is_factory_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::FactoryPaused)
+            .unwrap_or(false)
+    }
+
+    /// Set the shortest and longest campaign windows, in seconds, that
+    /// `create_campaign` accepts.
+    ///
+    /// Campaigns themselves refuse windows under one hour or over one year,
+    /// so the bounds may only tighten that range. Returns
+    /// `InvalidDurationBounds` if they fall outside it or `min_duration`
+    /// exceeds `max_duration`.
+    pub fn set_duration_bounds(
+        env: Env,
+        admin: Address,
+        min_duration: u64,
+        max_duration: u64,
+    ) -> Result<(), FactoryError> {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("not authorized");
+        }
+        admin.require_auth();
+
+        if min_duration < DEFAULT_MIN_DURATION
+            || max_duration > DEFAULT_MAX_DURATION
+            || min_duration > max_duration
+        {
+            return Err(FactoryError::InvalidDurationBounds);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MinDuration, &min_duration);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxDuration, &max_duration);
+
+        env.events().publish(
+            ("factory", "duration_bounds_set"),
+            (min_duration, max_duration),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the shortest campaign window `create_campaign` accepts.
+    pub fn min_duration(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinDuration)
+            .unwrap_or(DEFAULT_MIN_DURATION)
+    }
+
+    /// Returns the longest campaign window `create_campaign` accepts.
+    pub fn max_duration(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxDuration)
+            .unwrap_or(DEFAULT_MAX_DURATION)
+    }
+
+    /// Record a status transition reported by a registered campaign.
+    ///
+    /// Campaigns call this from every status change so the status buckets
+    /// stay current without scanning the registry.
+    pub fn report_status(
+        env: Env,
+        campaign: Address,
+        old: CampaignStatus,
+        new: CampaignStatus,
+    ) -> Result<(), FactoryError> {
+        campaign.require_auth();
+
+        // Archived campaigns keep reporting so the status buckets stay accurate.
+        if !is_registered(&env, &campaign) {
+            return Err(FactoryError::NotRegistered);
+        }
+
+        let old_key = DataKey::StatusBucket(old.clone());
+        let mut old_bucket: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&old_key)
+            .unwrap_or(Vec::new(&env));
+        if let Some(index) = old_bucket.first_index_of(&campaign) {
+            old_bucket.remove(index);
+            env.storage().instance().set(&old_key, &old_bucket);
+        }
+
+        let new_key = DataKey::StatusBucket(new.clone());
+        let mut new_bucket: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&new_key)
+            .unwrap_or(Vec::new(&env));
+        if !new_bucket.contains(&campaign) {
+            new_bucket.push_back(campaign.clone());
+            env.storage().instance().set(&new_key, &new_bucket);
+        }
+
+        env.events()
+            .publish(("factory", "status_reported"), (campaign, old, new));
+
+        Ok(())
+    }
+
+    /// Record a contribution (or, with a negative `amount`, a refund)
+    /// reported by a registered campaign.
+    ///
+    /// Keeps `aggregate_raised` current without calling every campaign.
+    pub fn report_contribution(
+        env: Env,
+        campaign: Address,
+        amount: i128,
+    ) -> Result<(), FactoryError> {
+        campaign.require_auth();
+
+        if !is_registered(&env, &campaign) {
+            return Err(FactoryError::NotRegistered);
+        }
+
+        let aggregate: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AggregateRaised)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::AggregateRaised, &(aggregate + amount));
+
+        Ok(())
+    }
+
+    /// Move a campaign from the active registry to the archive.
+    ///
+    /// Only the factory admin may archive. Status and category indexes are
+    /// left untouched.
+    pub fn archive_campaign(
+        env: Env,
+        admin: Address,
+        campaign: Address,
+    ) -> Result<(), FactoryError> {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("not authorized");
+        }
+        admin.require_auth();
+
+        let mut campaigns: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Campaigns)
+            .unwrap_or(Vec::new(&env));
+        let index = campaigns
+            .first_index_of(&campaign)
+            .ok_or(FactoryError::NotRegistered)?;
+        campaigns.remove(index);
+        env.storage()
+            .instance()
+            .set(&DataKey::Campaigns, &campaigns);
+
+        let mut archived: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Archived)
+            .unwrap_or(Vec::new(&env));
+        archived.push_back(campaign.clone());
+        env.storage().instance().set(&DataKey::Archived, &archived);
+
+        env.events()
+            .publish(("factory", "campaign_archived"), campaign);
+
+        Ok(())
     }
 
-    /// Returns the list of all deployed campaign addresses.
+    /// Returns the campaigns tagged with `category`.
+    pub fn campaigns_by_category(env: Env, category: Symbol) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::CategoryIndex(category))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns the campaigns currently in `status`.
+    pub fn campaigns_by_status(env: Env, status: CampaignStatus) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::StatusBucket(status))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns the active (non-archived) campaign addresses.
     pub fn campaigns(env: Env) -> Vec<Address> {
+        Self::active_campaigns(env)
+    }
+
+    /// Returns the active (non-archived) campaign addresses.
+    pub fn active_campaigns(env: Env) -> Vec<Address> {
         env.storage()
             .instance()
             .get(&DataKey::Campaigns)
             .unwrap_or(Vec::new(&env))
     }
 
-    /// Returns the total number of deployed campaigns.
+    /// Returns the archived campaign addresses.
+    pub fn archived_campaigns(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Archived)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns the net contributions reported by campaigns through
+    /// `report_contribution`.
+    pub fn aggregate_raised(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::AggregateRaised)
+            .unwrap_or(0)
+    }
+
+    /// Sum `total_raised` across every active and archived campaign.
+    ///
+    /// Makes one cross-contract call per campaign, so prefer
+    /// `aggregate_raised` on large registries. Campaigns whose view fails are
+    /// skipped.
+    pub fn total_raised_all(env: Env) -> i128 {
+        let mut campaigns = Self::active_campaigns(env.clone());
+        campaigns.append(&Self::archived_campaigns(env.clone()));
+
+        let mut total: i128 = 0;
+        for campaign in campaigns.iter() {
+            if let Ok(Ok(raised)) = env.try_invoke_contract::<i128, soroban_sdk::Error>(
+                &campaign,
+                &Symbol::new(&env, "total_raised"),
+                Vec::new(&env),
+            ) {
+                total += raised;
+            }
+        }
+        total
+    }
+
+    /// Campaign counts by status, read from the status buckets that
+    /// campaigns keep current through `report_status`.
+    pub fn factory_stats(env: Env) -> FactoryStats {
+        let bucket_len = |status: CampaignStatus| -> u32 {
+            Self::campaigns_by_status(env.clone(), status).len()
+        };
+        FactoryStats {
+            total_campaigns: Self::active_campaigns(env.clone()).len()
+                + Self::archived_campaigns(env.clone()).len(),
+            successful: bucket_len(CampaignStatus::Successful),
+            refunded: bucket_len(CampaignStatus::Refunded),
+            cancelled: bucket_len(CampaignStatus::Cancelled),
+            active: bucket_len(CampaignStatus::Active) + bucket_len(CampaignStatus::Paused),
+        }
+    }
+
+    /// Like `factory_stats`, but asks every campaign for its `status`.
+    ///
+    /// Makes one cross-contract call per campaign, so prefer `factory_stats`
+    /// on large registries. Campaigns whose view fails count only toward
+    /// `total_campaigns`.
+    pub fn factory_stats_scan(env: Env) -> FactoryStats {
+        let mut campaigns = Self::active_campaigns(env.clone());
+        campaigns.append(&Self::archived_campaigns(env.clone()));
+
+        let mut stats = FactoryStats {
+            total_campaigns: campaigns.len(),
+            successful: 0,
+            refunded: 0,
+            cancelled: 0,
+            active: 0,
+        };
+        for campaign in campaigns.iter() {
+            if let Ok(Ok(status)) = env.try_invoke_contract::<CampaignStatus, soroban_sdk::Error>(
+                &campaign,
+                &Symbol::new(&env, "status"),
+                Vec::new(&env),
+            ) {
+                match status {
+                    CampaignStatus::Successful => stats.successful += 1,
+                    CampaignStatus::Refunded => stats.refunded += 1,
+                    CampaignStatus::Cancelled => stats.cancelled += 1,
+                    CampaignStatus::Active | CampaignStatus::Paused => stats.active += 1,
+                }
+            }
+        }
+        stats
+    }
+
+    /// Returns the number of active (non-archived) campaigns.
     pub fn campaign_count(env: Env) -> u32 {
         let campaigns: Vec<Address> = env
             .storage()
@@ -97,3 +531,18 @@ impl FactoryContract {
         campaigns.len()
     }
 }
+
+/// Whether `campaign` is in the active registry or the archive.
+fn is_registered(env: &Env, campaign: &Address) -> bool {
+    let campaigns: Vec<Address> = env
+        .storage()
+        .instance()
+        .get(&DataKey::Campaigns)
+        .unwrap_or(Vec::new(env));
+    let archived: Vec<Address> = env
+        .storage()
+        .instance()
+        .get(&DataKey::Archived)
+        .unwrap_or(Vec::new(env));
+    campaigns.contains(campaign) || archived.contains(campaign)
+}
@@ -1,5 +1,41 @@
-use crate::{FactoryContract, FactoryContractClient};
-use soroban_sdk::Env;
+use crate::{
+    CampaignStatus, DataKey, FactoryContract, FactoryContractClient, FactoryError, FactoryStats,
+};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+    token, vec, Address, BytesN, Env,
+};
+
+/// The real crowdfund contract, built with
+/// `cargo build --release --target wasm32-unknown-unknown -p crowdfund`.
+mod crowdfund {
+    soroban_sdk::contractimport!(
+        file = "../../target/wasm32-unknown-unknown/release/crowdfund.wasm"
+    );
+}
+
+/// Register a factory whose registry already lists `campaign` as active.
+fn setup_with_campaign() -> (Env, FactoryContractClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let factory_id = env.register(FactoryContract, ());
+    let factory = FactoryContractClient::new(&env, &factory_id);
+
+    let campaign = Address::generate(&env);
+    env.as_contract(&factory_id, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::Campaigns, &vec![&env, campaign.clone()]);
+        env.storage().instance().set(
+            &DataKey::StatusBucket(CampaignStatus::Active),
+            &vec![&env, campaign.clone()],
+        );
+    });
+
+    (env, factory, campaign)
+}
 
 #[test]
 fn test_empty_registry() {
@@ -12,3 +48,435 @@ fn test_empty_registry() {
     assert_eq!(campaigns.len(), 0);
     assert_eq!(factory.campaign_count(), 0);
 }
+
+#[test]
+fn test_report_status_moves_campaign_between_buckets() {
+    let (_env, factory, campaign) = setup_with_campaign();
+
+    factory.report_status(
+        &campaign,
+        &CampaignStatus::Active,
+        &CampaignStatus::Successful,
+    );
+
+    assert_eq!(
+        factory.campaigns_by_status(&CampaignStatus::Active).len(),
+        0
+    );
+    let successful = factory.campaigns_by_status(&CampaignStatus::Successful);
+    assert_eq!(successful.len(), 1);
+    assert_eq!(successful.get(0).unwrap(), campaign);
+}
+
+#[test]
+fn test_report_status_rejects_unregistered_campaign() {
+    let (env, factory, _campaign) = setup_with_campaign();
+
+    let stranger = Address::generate(&env);
+    let result = factory.try_report_status(
+        &stranger,
+        &CampaignStatus::Active,
+        &CampaignStatus::Successful,
+    );
+    assert_eq!(result.unwrap_err().unwrap(), FactoryError::NotRegistered);
+}
+
+#[test]
+fn test_campaigns_by_category_empty_for_unknown_category() {
+    let env = Env::default();
+
+    let factory_id = env.register(FactoryContract, ());
+    let factory = FactoryContractClient::new(&env, &factory_id);
+
+    assert_eq!(
+        factory.campaigns_by_category(&symbol_short!("art")).len(),
+        0
+    );
+}
+
+#[test]
+fn test_archive_campaign_moves_to_archive() {
+    let (env, factory, campaign) = setup_with_campaign();
+    let admin = Address::generate(&env);
+    factory.initialize(&admin);
+
+    factory.archive_campaign(&admin, &campaign);
+
+    assert_eq!(factory.campaigns().len(), 0);
+    assert_eq!(factory.active_campaigns().len(), 0);
+    assert_eq!(factory.archived_campaigns(), vec![&env, campaign.clone()]);
+
+    // Archived campaigns can still report status changes.
+    factory.report_status(
+        &campaign,
+        &CampaignStatus::Active,
+        &CampaignStatus::Successful,
+    );
+}
+
+#[test]
+fn test_archive_unregistered_campaign_fails() {
+    let (env, factory, _campaign) = setup_with_campaign();
+    let admin = Address::generate(&env);
+    factory.initialize(&admin);
+
+    let stranger = Address::generate(&env);
+    let result = factory.try_archive_campaign(&admin, &stranger);
+    assert_eq!(result.unwrap_err().unwrap(), FactoryError::NotRegistered);
+}
+
+/// Campaign stand-in exposing only the `total_raised` and `status` views.
+#[contract]
+struct MockCampaign;
+
+#[contractimpl]
+impl MockCampaign {
+    pub fn set_total_raised(env: Env, total: i128) {
+        env.storage().instance().set(&1u32, &total);
+    }
+
+    pub fn total_raised(env: Env) -> i128 {
+        env.storage().instance().get(&1u32).unwrap_or(0)
+    }
+
+    pub fn set_status(env: Env, status: CampaignStatus) {
+        env.storage().instance().set(&2u32, &status);
+    }
+
+    pub fn status(env: Env) -> CampaignStatus {
+        env.storage()
+            .instance()
+            .get(&2u32)
+            .unwrap_or(CampaignStatus::Active)
+    }
+}
+
+#[test]
+fn test_total_raised_all_sums_active_and_archived_campaigns() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let factory_id = env.register(FactoryContract, ());
+    let factory = FactoryContractClient::new(&env, &factory_id);
+
+    let first = env.register(MockCampaign, ());
+    let second = env.register(MockCampaign, ());
+    MockCampaignClient::new(&env, &first).set_total_raised(&300);
+    MockCampaignClient::new(&env, &second).set_total_raised(&200);
+    // Campaigns without the view are skipped rather than failing the scan.
+    let broken = Address::generate(&env);
+    env.as_contract(&factory_id, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::Campaigns, &vec![&env, first.clone(), broken]);
+        env.storage()
+            .instance()
+            .set(&DataKey::Archived, &vec![&env, second.clone()]);
+    });
+
+    assert_eq!(factory.total_raised_all(), 500);
+}
+
+#[test]
+fn test_report_contribution_updates_aggregate() {
+    let (_env, factory, campaign) = setup_with_campaign();
+    assert_eq!(factory.aggregate_raised(), 0);
+
+    factory.report_contribution(&campaign, &1_000);
+    factory.report_contribution(&campaign, &500);
+    factory.report_contribution(&campaign, &-200);
+
+    assert_eq!(factory.aggregate_raised(), 1_300);
+}
+
+#[test]
+fn test_report_contribution_rejects_unregistered_campaign() {
+    let (env, factory, _campaign) = setup_with_campaign();
+
+    let stranger = Address::generate(&env);
+    let result = factory.try_report_contribution(&stranger, &1_000);
+    assert_eq!(result.unwrap_err().unwrap(), FactoryError::NotRegistered);
+    assert_eq!(factory.aggregate_raised(), 0);
+}
+
+#[test]
+fn test_duration_bounds_default_to_one_hour_and_one_year() {
+    let env = Env::default();
+    let factory_id = env.register(FactoryContract, ());
+    let factory = FactoryContractClient::new(&env, &factory_id);
+
+    assert_eq!(factory.min_duration(), 3_600);
+    assert_eq!(factory.max_duration(), 365 * 24 * 3_600);
+}
+
+#[test]
+fn test_admin_sets_duration_bounds() {
+    let (env, factory, _campaign) = setup_with_campaign();
+    let admin = Address::generate(&env);
+    factory.initialize(&admin);
+
+    factory.set_duration_bounds(&admin, &7_200, &86_400);
+
+    assert_eq!(factory.min_duration(), 7_200);
+    assert_eq!(factory.max_duration(), 86_400);
+}
+
+#[test]
+#[should_panic(expected = "not authorized")]
+fn test_set_duration_bounds_rejects_non_admin() {
+    let (env, factory, _campaign) = setup_with_campaign();
+    let admin = Address::generate(&env);
+    factory.initialize(&admin);
+
+    let stranger = Address::generate(&env);
+    factory.set_duration_bounds(&stranger, &7_200, &86_400);
+}
+
+#[test]
+fn test_set_duration_bounds_rejects_inverted_range() {
+    let (env, factory, _campaign) = setup_with_campaign();
+    let admin = Address::generate(&env);
+    factory.initialize(&admin);
+
+    let result = factory.try_set_duration_bounds(&admin, &86_400, &7_200);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        FactoryError::InvalidDurationBounds
+    );
+    assert_eq!(factory.min_duration(), 3_600);
+}
+
+#[test]
+fn test_set_duration_bounds_stays_within_campaign_limits() {
+    let (env, factory, _campaign) = setup_with_campaign();
+    let admin = Address::generate(&env);
+    factory.initialize(&admin);
+
+    let result = factory.try_set_duration_bounds(&admin, &60, &86_400);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        FactoryError::InvalidDurationBounds
+    );
+    let result = factory.try_set_duration_bounds(&admin, &7_200, &(2 * 365 * 24 * 3_600));
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        FactoryError::InvalidDurationBounds
+    );
+    assert_eq!(factory.max_duration(), 365 * 24 * 3_600);
+}
+
+#[test]
+fn test_deployed_campaign_reports_to_factory() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let factory_id = env.register(FactoryContract, ());
+    let factory = FactoryContractClient::new(&env, &factory_id);
+    let admin = Address::generate(&env);
+    factory.initialize(&admin);
+
+    let token_address = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let wasm_hash = env.deployer().upload_contract_wasm(crowdfund::WASM);
+    let creator = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 3_600;
+    let campaign = factory.create_campaign(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &deadline,
+        &symbol_short!("tech"),
+        &wasm_hash,
+    );
+
+    // The factory admin, not the factory itself, administers the campaign.
+    let campaign_client = crowdfund::Client::new(&env, &campaign);
+    assert_eq!(campaign_client.admin(), admin);
+    assert_eq!(campaign_client.factory(), Some(factory_id));
+
+    let backer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token_address).mint(&backer, &1_000_000);
+    campaign_client.contribute(&backer, &1_000_000, &None, &false, &0, &None);
+    assert_eq!(factory.aggregate_raised(), 1_000_000);
+
+    env.ledger().set_timestamp(deadline + 1);
+    campaign_client.withdraw();
+
+    assert_eq!(
+        factory.campaigns_by_status(&CampaignStatus::Successful),
+        vec![&env, campaign]
+    );
+    assert_eq!(
+        factory.campaigns_by_status(&CampaignStatus::Active).len(),
+        0
+    );
+}
+
+#[test]
+fn test_create_campaign_requires_initialized_factory() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let factory_id = env.register(FactoryContract, ());
+    let factory = FactoryContractClient::new(&env, &factory_id);
+
+    let result = factory.try_create_campaign(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &1_000_000,
+        &(env.ledger().timestamp() + 3_600),
+        &symbol_short!("tech"),
+        &BytesN::from_array(&env, &[0; 32]),
+    );
+    assert_eq!(result.unwrap_err().unwrap(), FactoryError::NotInitialized);
+}
+
+#[test]
+fn test_create_campaign_rejects_duration_outside_bounds() {
+    let (env, factory, _campaign) = setup_with_campaign();
+    let admin = Address::generate(&env);
+    factory.initialize(&admin);
+    factory.set_duration_bounds(&admin, &7_200, &86_400);
+
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let wasm_hash = BytesN::from_array(&env, &[0; 32]);
+    let now = env.ledger().timestamp();
+
+    let too_short = factory.try_create_campaign(
+        &creator,
+        &token,
+        &1_000_000,
+        &(now + 7_199),
+        &symbol_short!("tech"),
+        &wasm_hash,
+    );
+    assert_eq!(
+        too_short.unwrap_err().unwrap(),
+        FactoryError::DurationTooShort
+    );
+
+    let too_long = factory.try_create_campaign(
+        &creator,
+        &token,
+        &1_000_000,
+        &(now + 86_401),
+        &symbol_short!("tech"),
+        &wasm_hash,
+    );
+    assert_eq!(
+        too_long.unwrap_err().unwrap(),
+        FactoryError::DurationTooLong
+    );
+    assert_eq!(factory.campaign_count(), 1);
+}
+
+#[test]
+fn test_pause_factory_blocks_campaign_creation() {
+    let (env, factory, campaign) = setup_with_campaign();
+    let admin = Address::generate(&env);
+    factory.initialize(&admin);
+    assert!(!factory.is_factory_paused());
+
+    factory.pause_factory(&admin);
+    assert!(factory.is_factory_paused());
+
+    let creator = Address::generate(&env);
+    let result = factory.try_create_campaign(
+        &creator,
+        &Address::generate(&env),
+        &1_000_000,
+        &(env.ledger().timestamp() + 3_600),
+        &symbol_short!("tech"),
+        &BytesN::from_array(&env, &[0; 32]),
+    );
+    assert_eq!(result.unwrap_err().unwrap(), FactoryError::FactoryPaused);
+
+    // Existing campaigns keep reporting while the factory is paused.
+    factory.report_contribution(&campaign, &1_000);
+    assert_eq!(factory.aggregate_raised(), 1_000);
+
+    factory.unpause_factory(&admin);
+    assert!(!factory.is_factory_paused());
+}
+
+#[test]
+#[should_panic(expected = "not authorized")]
+fn test_pause_factory_rejects_non_admin() {
+    let (env, factory, _campaign) = setup_with_campaign();
+    let admin = Address::generate(&env);
+    factory.initialize(&admin);
+
+    factory.pause_factory(&Address::generate(&env));
+}
+
+#[test]
+fn test_factory_stats_reads_reported_statuses() {
+    let (_env, factory, campaign) = setup_with_campaign();
+    assert_eq!(
+        factory.factory_stats(),
+        FactoryStats {
+            total_campaigns: 1,
+            successful: 0,
+            refunded: 0,
+            cancelled: 0,
+            active: 1,
+        }
+    );
+
+    factory.report_status(
+        &campaign,
+        &CampaignStatus::Active,
+        &CampaignStatus::Successful,
+    );
+
+    assert_eq!(
+        factory.factory_stats(),
+        FactoryStats {
+            total_campaigns: 1,
+            successful: 1,
+            refunded: 0,
+            cancelled: 0,
+            active: 0,
+        }
+    );
+}
+
+#[test]
+fn test_factory_stats_scan_queries_each_campaign() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let factory_id = env.register(FactoryContract, ());
+    let factory = FactoryContractClient::new(&env, &factory_id);
+
+    let successful = env.register(MockCampaign, ());
+    let refunded = env.register(MockCampaign, ());
+    let paused = env.register(MockCampaign, ());
+    let active = env.register(MockCampaign, ());
+    MockCampaignClient::new(&env, &successful).set_status(&CampaignStatus::Successful);
+    MockCampaignClient::new(&env, &refunded).set_status(&CampaignStatus::Refunded);
+    MockCampaignClient::new(&env, &paused).set_status(&CampaignStatus::Paused);
+    let broken = Address::generate(&env);
+    env.as_contract(&factory_id, || {
+        env.storage().instance().set(
+            &DataKey::Campaigns,
+            &vec![&env, successful, paused, active, broken],
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::Archived, &vec![&env, refunded]);
+    });
+
+    assert_eq!(
+        factory.factory_stats_scan(),
+        FactoryStats {
+            total_campaigns: 5,
+            successful: 1,
+            refunded: 1,
+            cancelled: 0,
+            active: 2,
+        }
+    );
+}
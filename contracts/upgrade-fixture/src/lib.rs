@@ -0,0 +1,30 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+/// The subset of the crowdfund storage keys this release reads.
+#[contracttype]
+pub enum DataKey {
+    Creator,
+    TotalRaised,
+}
+
+/// Stand-in for a later crowdfund release, used by the crowdfund tests to
+/// exercise `upgrade`. It is never deployed.
+#[contract]
+pub struct UpgradedCrowdfund;
+
+#[contractimpl]
+impl UpgradedCrowdfund {
+    pub fn version(_env: Env) -> u32 {
+        4
+    }
+
+    /// New in this release: the creator and total raised in one call, read
+    /// from the storage the previous code wrote.
+    pub fn summary(env: Env) -> (Address, i128) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
+        (creator, total)
+    }
+}